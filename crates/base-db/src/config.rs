@@ -15,6 +15,8 @@ pub struct Config {
     pub syntax: SyntaxConfig,
     pub completion: CompletionConfig,
     pub inlay_hints: InlayHintConfig,
+    pub code_actions: CodeActionConfig,
+    pub citation: CitationConfig,
 }
 
 #[derive(Debug)]
@@ -27,6 +29,20 @@ pub struct BuildConfig {
     pub log_dir: String,
     pub pdf_dir: String,
     pub output_filename: Option<String>,
+    pub profiles: Vec<BuildProfile>,
+    pub default_profile: Option<String>,
+}
+
+/// A named alternative to the default build command.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BuildProfile {
+    pub name: String,
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub aux_dir: Option<String>,
+    pub log_dir: Option<String>,
+    pub pdf_dir: Option<String>,
 }
 
 #[derive(Debug)]
@@ -57,6 +73,10 @@ pub struct FormattingConfig {
     pub bib_formatter: Formatter,
     pub latex_indent: LatexIndentConfig,
     pub line_length: usize,
+    /// Lets the server formatter put each sentence of a LaTeX document on its own line.
+    /// External formatters like `latexindent` ignore this setting.
+    pub semantic_line_breaks: bool,
+    pub bibtex: BibtexFormattingConfig,
 }
 
 #[derive(Debug)]
@@ -67,6 +87,40 @@ pub enum Formatter {
     TexFmt,
 }
 
+/// The style of the BibTeX files that the server formatter writes.
+#[derive(Debug)]
+pub struct BibtexFormattingConfig {
+    pub field_order: Vec<String>,
+    pub entry_type_case: KeyCase,
+    pub field_name_case: KeyCase,
+    pub delimiter: Option<BibtexDelimiter>,
+    pub align_equals: bool,
+    pub trailing_comma: bool,
+    pub remove_empty_fields: bool,
+    pub remove_fields: Vec<String>,
+    pub sort_entries: Option<BibtexSortKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum KeyCase {
+    Preserve,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BibtexDelimiter {
+    Braces,
+    Quotes,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BibtexSortKey {
+    Key,
+    Year,
+    Author,
+}
+
 #[derive(Debug, Default)]
 pub struct LatexIndentConfig {
     pub local: Option<String>,
@@ -106,6 +160,21 @@ pub struct InlayHintConfig {
     pub max_length: Option<usize>,
 }
 
+#[derive(Debug, Default)]
+pub struct CodeActionConfig {
+    /// The directory relative to the source directory where extracted files are created.
+    /// Defaults to the directory of the document that contains the selection.
+    pub extract_dir: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct CitationConfig {
+    /// The name of a bundled CSL style or the path to a `.csl` file relative to the workspace folder
+    /// that is used to render citations in hover and completion.
+    /// Only used if the document does not select a known style with `biblatex` or `\bibliographystyle`.
+    pub style: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct CompletionConfig {
     pub matcher: MatchingAlgo,
@@ -119,6 +188,15 @@ pub enum MatchingAlgo {
     PrefixIgnoreCase,
 }
 
+impl BuildConfig {
+    /// Looks up a build profile by its name, ignoring case.
+    pub fn profile(&self, name: &str) -> Option<&BuildProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+}
+
 impl Default for BuildConfig {
     fn default() -> Self {
         Self {
@@ -133,6 +211,8 @@ impl Default for BuildConfig {
             log_dir: String::from("."),
             pdf_dir: String::from("."),
             output_filename: None,
+            profiles: Vec::new(),
+            default_profile: None,
         }
     }
 }
@@ -155,6 +235,24 @@ impl Default for FormattingConfig {
             bib_formatter: Formatter::Server,
             line_length: 80,
             latex_indent: LatexIndentConfig::default(),
+            semantic_line_breaks: false,
+            bibtex: BibtexFormattingConfig::default(),
+        }
+    }
+}
+
+impl Default for BibtexFormattingConfig {
+    fn default() -> Self {
+        Self {
+            field_order: Vec::new(),
+            entry_type_case: KeyCase::Lower,
+            field_name_case: KeyCase::Preserve,
+            delimiter: None,
+            align_equals: false,
+            trailing_comma: true,
+            remove_empty_fields: false,
+            remove_fields: Vec::new(),
+            sort_entries: None,
         }
     }
 }
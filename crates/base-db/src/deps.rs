@@ -1,11 +1,13 @@
 mod discover;
 mod graph;
+mod profile;
 mod project;
 mod root;
 
 pub use self::{
     discover::{discover, watch},
     graph::{DirectLinkData, Edge, EdgeData, Graph, HOME_DIR},
+    profile::{UnknownProfileError, find_magic_program, find_profile},
     project::{Project, parents},
     root::ProjectRoot,
 };
@@ -48,7 +48,7 @@ pub fn discover(workspace: &mut Workspace, checked_paths: &mut FxHashSet<PathBuf
 fn discover_parents(workspace: &mut Workspace, checked_paths: &mut FxHashSet<PathBuf>) -> bool {
     let dirs = workspace
         .iter()
-        .filter(|document| document.language != Language::Bib)
+        .filter(|document| !matches!(document.language, Language::Bib | Language::CslJson))
         .filter_map(|document| document.path.as_deref())
         .flat_map(|path| path.ancestors().skip(1))
         .filter(|path| workspace.contains(path))
@@ -108,7 +108,7 @@ fn discover_children(workspace: &mut Workspace, checked_paths: &mut FxHashSet<Pa
 
     let mut changed = false;
     for file in files {
-        let language = Language::from_path(&file).unwrap_or(Language::Tex);
+        let language = Language::from_linked_path(&file).unwrap_or(Language::Tex);
 
         if workspace.lookup_file(&file).is_none() && file.exists() {
             changed |= workspace.load(&file, language).is_ok();
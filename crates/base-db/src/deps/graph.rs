@@ -55,12 +55,10 @@ impl Graph {
             start: start.uri.clone(),
         };
 
-        let Some(start_dir) = &start.dir else {
+        let Some(root) = ProjectRoot::from_document(workspace, start) else {
             return graph;
         };
 
-        let root = ProjectRoot::walk_and_find(workspace, start_dir);
-
         let mut stack = vec![(start, Rc::new(root))];
         let mut visited = FxHashSet::default();
 
@@ -148,7 +146,10 @@ impl Graph {
             .chain(file_list.outputs.iter())
             .filter(|path| {
                 path.is_relative()
-                    || Language::from_path(&path) == Some(Language::Bib)
+                    || matches!(
+                        Language::from_linked_path(&path),
+                        Some(Language::Bib | Language::CslJson)
+                    )
                     || home_dir.is_some_and(|home_dir| path.starts_with(home_dir))
             })
             .filter_map(|path| working_dir.join(path.to_str()?).ok())
@@ -203,7 +204,10 @@ impl Graph {
             })
             .filter(|path| {
                 home_dir.is_some_and(|dir| path.starts_with(dir))
-                    || Language::from_path(path) == Some(Language::Bib)
+                    || matches!(
+                        Language::from_linked_path(path),
+                        Some(Language::Bib | Language::CslJson)
+                    )
             })
             .flat_map(Url::from_file_path);
 
@@ -0,0 +1,61 @@
+use syntax::latex;
+use thiserror::Error;
+
+use crate::{BuildConfig, BuildProfile, Document};
+
+#[derive(Debug, Error)]
+#[error("Build profile \"{0}\" is not configured")]
+pub struct UnknownProfileError(pub String);
+
+/// Selects the build profile of a root document.
+/// An explicitly named profile takes precedence over the `% !TEX program` magic comment,
+/// which takes precedence over the configured default profile.
+/// Magic comments that do not name a configured profile are ignored.
+pub fn find_profile<'a>(
+    config: &'a BuildConfig,
+    document: &Document,
+    name: Option<&str>,
+) -> Result<Option<&'a BuildProfile>, UnknownProfileError> {
+    let lookup = |name: &str| {
+        config
+            .profile(name)
+            .ok_or_else(|| UnknownProfileError(name.into()))
+    };
+
+    if let Some(name) = name {
+        return lookup(name).map(Some);
+    }
+
+    if let Some(program) = find_magic_program(document) {
+        match config.profile(&program) {
+            Some(profile) => return Ok(Some(profile)),
+            None => log::debug!("Ignoring unknown build profile \"{program}\" of magic comment"),
+        }
+    }
+
+    config.default_profile.as_deref().map(lookup).transpose()
+}
+
+/// Finds magic comments like `% !TEX program = lualatex` or `% !TeX TS-program = xelatex`.
+/// Like other tools, only the comments at the top of the document are considered.
+pub fn find_magic_program(document: &Document) -> Option<String> {
+    let data = document.data.as_tex()?;
+    data.root_node()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .take_while(|token| matches!(token.kind(), latex::COMMENT | latex::WHITESPACE))
+        .filter(|token| token.kind() == latex::COMMENT)
+        .find_map(|token| {
+            let text = token.text().trim_start_matches('%').trim_start();
+            text.get(..4)
+                .filter(|magic| magic.eq_ignore_ascii_case("!tex"))?;
+
+            let (key, value) = text[4..].split_once('=')?;
+            let key = key.trim();
+            let value = value.trim();
+            let is_program =
+                key.eq_ignore_ascii_case("program") || key.eq_ignore_ascii_case("ts-program");
+
+            (is_program && !value.is_empty()).then(|| value.to_string())
+        })
+}
@@ -19,6 +19,30 @@ impl<'a> Project<'a> {
 
         Self { documents }
     }
+
+    /// The main language of the project as selected with `babel` or `polyglossia`.
+    /// Languages that are set in a root document take precedence.
+    pub fn language(&self) -> Option<&'a str> {
+        self.documents
+            .iter()
+            .filter_map(|document| document.data.as_tex())
+            .map(|data| &data.semantics)
+            .filter(|semantics| semantics.language.is_some())
+            .max_by_key(|semantics| semantics.can_be_root)
+            .and_then(|semantics| semantics.language.as_deref())
+    }
+
+    /// The bibliography style of the project as selected with `biblatex` or `\bibliographystyle`.
+    /// Styles that are set in a root document take precedence.
+    pub fn citation_style(&self) -> Option<&'a str> {
+        self.documents
+            .iter()
+            .filter_map(|document| document.data.as_tex())
+            .map(|data| &data.semantics)
+            .filter(|semantics| semantics.citation_style.is_some())
+            .max_by_key(|semantics| semantics.can_be_root)
+            .and_then(|semantics| semantics.citation_style.as_deref())
+    }
 }
 
 pub fn parents<'a>(workspace: &'a Workspace, child: &'a Document) -> FxHashSet<&'a Document> {
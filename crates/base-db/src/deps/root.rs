@@ -3,9 +3,9 @@ use std::sync::Arc;
 use distro::FileNameDB;
 use url::Url;
 
-use crate::{DocumentData, Workspace, util};
+use crate::{BuildProfile, Document, DocumentData, Workspace, util};
 
-use super::graph::HOME_DIR;
+use super::{find_profile, graph::HOME_DIR};
 
 #[derive(Clone)]
 pub struct ProjectRoot {
@@ -46,6 +46,23 @@ impl ProjectRoot {
         }
     }
 
+    /// Finds the root of the project compiled from the given document,
+    /// including the output directories of its build profile.
+    pub fn from_document(workspace: &Workspace, document: &Document) -> Option<Self> {
+        let root = Self::walk_and_find(workspace, document.dir.as_ref()?);
+        let name = workspace.build_profile(&document.uri);
+        let profile =
+            find_profile(&workspace.config().build, document, name).unwrap_or_else(|why| {
+                log::debug!("Ignoring build profile of \"{}\": {why}", document.uri);
+                None
+            });
+
+        Some(match profile {
+            Some(profile) => root.with_profile(workspace, profile),
+            None => root,
+        })
+    }
+
     pub fn from_tectonic(workspace: &Workspace, dir: &Url) -> Option<Self> {
         let exists = workspace
             .iter()
@@ -141,6 +158,23 @@ impl ProjectRoot {
         Some(Self::from_config(workspace, dir))
     }
 
+    /// Applies the output directories of a build profile, which are relative to the compilation directory.
+    pub fn with_profile(mut self, workspace: &Workspace, profile: &BuildProfile) -> Self {
+        let dir = &self.compile_dir;
+        let resolve = |path: &Option<String>| {
+            path.as_deref()
+                .and_then(|path| append_dir(dir, path, workspace).ok())
+        };
+
+        let aux_dir = resolve(&profile.aux_dir);
+        let log_dir = resolve(&profile.log_dir).or_else(|| aux_dir.clone());
+        let pdf_dir = resolve(&profile.pdf_dir);
+        self.aux_dir = aux_dir.unwrap_or(self.aux_dir);
+        self.log_dir = log_dir.unwrap_or(self.log_dir);
+        self.pdf_dir = pdf_dir.unwrap_or(self.pdf_dir);
+        self
+    }
+
     pub fn from_config(workspace: &Workspace, dir: &Url) -> Self {
         let compile_dir = dir.clone();
         let src_dir = dir.clone();
@@ -60,7 +60,7 @@ impl Document {
                     &params.config.syntax,
                     &latex::SyntaxNode::new_root(green.clone()),
                 );
-                DocumentData::Tex(TexDocumentData { green, semantics })
+                DocumentData::Tex(Box::new(TexDocumentData { green, semantics }))
             }
             Language::Bib => {
                 let green = parser::parse_bibtex(&text);
@@ -68,6 +68,13 @@ impl Document {
                 semantics.process_root(&bibtex::SyntaxNode::new_root(green.clone()));
                 DocumentData::Bib(BibDocumentData { green, semantics })
             }
+            Language::CslJson => {
+                let data = parser::parse_csl_json(&text);
+                let green = parser::parse_bibtex(&data.to_bibtex());
+                let mut semantics = semantics::bib::Semantics::default();
+                semantics.process_csl_json(&data);
+                DocumentData::CslJson(BibDocumentData { green, semantics })
+            }
             Language::Aux => {
                 let green = parser::parse_latex(&text, &params.config.syntax);
                 let mut semantics = semantics::auxiliary::Semantics::default();
@@ -140,8 +147,12 @@ impl std::hash::Hash for Document {
 
 #[derive(Debug, Clone)]
 pub enum DocumentData {
-    Tex(TexDocumentData),
+    Tex(Box<TexDocumentData>),
     Bib(BibDocumentData),
+    /// A CSL-JSON or BetterBibTeX JSON bibliography.
+    /// The syntax tree is a BibTeX rendition of the items,
+    /// while the semantic ranges refer to the JSON source.
+    CslJson(BibDocumentData),
     Aux(AuxDocumentData),
     Log(LogDocumentData),
     Root,
@@ -167,6 +178,14 @@ impl DocumentData {
         }
     }
 
+    /// Returns the entries of either a BibTeX or a JSON bibliography.
+    pub fn as_bibliography(&self) -> Option<&BibDocumentData> {
+        match self {
+            DocumentData::Bib(data) | DocumentData::CslJson(data) => Some(data),
+            _ => None,
+        }
+    }
+
     pub fn as_aux(&self) -> Option<&AuxDocumentData> {
         if let DocumentData::Aux(data) = self {
             Some(data)
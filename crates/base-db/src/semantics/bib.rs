@@ -1,10 +1,16 @@
 use bibtex_utils::field::text::TextFieldData;
 use itertools::Itertools;
 use rowan::{TextRange, ast::AstNode};
-use rustc_hash::FxHashMap;
-use syntax::bibtex::{self, HasName, HasType, HasValue};
+use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::{
+    bibtex::{self, HasName, HasType, HasValue},
+    csl_json::CslJsonData,
+};
 
-use crate::data::{BibtexEntryType, BibtexEntryTypeCategory};
+use crate::{
+    BibDocumentData, Workspace,
+    data::{BibtexEntryType, BibtexEntryTypeCategory},
+};
 
 use super::Span;
 
@@ -27,6 +33,31 @@ impl Semantics {
         }
     }
 
+    /// Collects the entries of a JSON bibliography, pointing into the JSON source.
+    pub fn process_csl_json(&mut self, data: &CslJsonData) {
+        for item in &data.items {
+            let entry_type = format!("@{}", item.entry_type);
+            let category = BibtexEntryType::find(&entry_type)
+                .map_or(BibtexEntryTypeCategory::Misc, |ty| ty.category);
+
+            let keywords = [item.key.as_str(), entry_type.as_str()]
+                .into_iter()
+                .chain(item.fields.iter().map(|(_, value)| value.as_str()))
+                .join(" ");
+
+            self.entries.push(Entry {
+                name: Span {
+                    range: item.key_range,
+                    text: item.key.clone(),
+                },
+                full_range: item.full_range,
+                category,
+                keywords,
+                parents: Vec::new(),
+            });
+        }
+    }
+
     fn process_entry(&mut self, entry: bibtex::Entry) {
         if let Some(name) = entry.name_token() {
             let type_token = entry.type_token().unwrap();
@@ -42,6 +73,8 @@ impl Semantics {
                 .chain(field_values)
                 .join(" ");
 
+            let parents = parent_keys(&entry, &self.expanded_defs);
+
             self.entries.push(Entry {
                 name: Span {
                     range: name.text_range(),
@@ -50,6 +83,7 @@ impl Semantics {
                 full_range: entry.syntax().text_range(),
                 category,
                 keywords,
+                parents,
             });
         }
     }
@@ -78,6 +112,8 @@ pub struct Entry {
     pub full_range: TextRange,
     pub keywords: String,
     pub category: BibtexEntryTypeCategory,
+    /// The keys of the entries referenced by `xdata` and `crossref`.
+    pub parents: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,3 +121,345 @@ pub struct StringDef {
     pub name: Span,
     pub full_range: TextRange,
 }
+
+/// The fields that an entry never inherits from its parents.
+const UNINHERITED_FIELDS: &[&str] = &[
+    "crossref",
+    "entryset",
+    "entrysubtype",
+    "execute",
+    "ids",
+    "label",
+    "options",
+    "presort",
+    "related",
+    "relatedoptions",
+    "relatedstring",
+    "relatedtype",
+    "shorthand",
+    "shorthandintro",
+    "sortkey",
+    "xdata",
+    "xref",
+];
+
+/// The fields that are inherited under a different name, grouped by the types of the parent
+/// and the child. For example, the `title` of a `@book` becomes the `booktitle` of an `@inbook`.
+const TITLE_MAPPINGS: &[(&[&str], &[&str], &str)] = &[
+    (
+        &["mvbook"],
+        &["book", "inbook", "bookinbook", "suppbook"],
+        "main",
+    ),
+    (
+        &["mvcollection", "mvreference"],
+        &[
+            "collection",
+            "reference",
+            "incollection",
+            "inreference",
+            "suppcollection",
+        ],
+        "main",
+    ),
+    (
+        &["mvproceedings"],
+        &["proceedings", "inproceedings"],
+        "main",
+    ),
+    (&["book"], &["inbook", "bookinbook", "suppbook"], "book"),
+    (
+        &["collection", "reference"],
+        &["incollection", "inreference", "suppcollection"],
+        "book",
+    ),
+    (&["proceedings"], &["inproceedings"], "book"),
+    (&["periodical"], &["article", "suppperiodical"], "journal"),
+];
+
+/// A field of an entry, which is either defined by the entry itself or inherited from a parent.
+#[derive(Clone)]
+pub struct ResolvedField<'a> {
+    /// The lowercase name of the field within the child entry.
+    pub name: String,
+    pub field: bibtex::Field,
+    /// The semantics of the document that defines the field, which are needed to expand strings.
+    pub semantics: &'a Semantics,
+}
+
+/// An entry together with the fields that it inherits through `xdata` and `crossref`.
+#[derive(Clone)]
+pub struct ResolvedEntry<'a> {
+    pub entry: bibtex::Entry,
+    pub fields: Vec<ResolvedField<'a>>,
+}
+
+impl<'a> ResolvedEntry<'a> {
+    /// Wraps an entry without looking at its parents.
+    pub fn new(entry: bibtex::Entry, semantics: &'a Semantics) -> Self {
+        let fields = own_fields(&entry, semantics).collect();
+        Self { entry, fields }
+    }
+
+    /// Resolves the parents of an entry across all bibliographies of the workspace
+    /// using the inheritance rules of `biblatex`.
+    /// The fields of the entry take precedence over the fields of its `xdata` entries,
+    /// which in turn take precedence over the fields of its `crossref` entry.
+    pub fn resolve(index: &EntryIndex<'a>, entry: bibtex::Entry, semantics: &'a Semantics) -> Self {
+        let mut visited = FxHashSet::default();
+        let fields = resolve_fields(index, &entry, semantics, &mut visited);
+        Self { entry, fields }
+    }
+
+    /// Checks whether the entry defines the field itself instead of inheriting it.
+    pub fn is_own(&self, field: &ResolvedField) -> bool {
+        field.field.syntax().parent().as_ref() == Some(self.entry.syntax())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ResolvedField<'a>> {
+        self.fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Maps the keys of the entries in all bibliographies of the workspace to their documents,
+/// which avoids searching every bibliography when resolving the parents of many entries.
+/// If several bibliographies define the same key, the first one wins.
+pub struct EntryIndex<'a> {
+    entries: FxHashMap<&'a str, (&'a Entry, &'a BibDocumentData)>,
+}
+
+impl<'a> EntryIndex<'a> {
+    pub fn new(workspace: &'a Workspace) -> Self {
+        let mut entries = FxHashMap::default();
+        for data in workspace
+            .iter()
+            .filter_map(|document| document.data.as_bibliography())
+        {
+            for entry in &data.semantics.entries {
+                entries
+                    .entry(entry.name.text.as_str())
+                    .or_insert((entry, data));
+            }
+        }
+
+        Self { entries }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a Entry> {
+        self.entries.get(key).map(|(entry, _)| *entry)
+    }
+
+    fn find(&self, key: &str) -> Option<(bibtex::Entry, &'a Semantics)> {
+        let (_, data) = self.entries.get(key)?;
+        let root = bibtex::Root::cast(data.root_node())?;
+        Some((root.find_entry(key)?, &data.semantics))
+    }
+}
+
+/// Appends the keywords of the parents of `entry` to its own keywords.
+pub fn resolve_keywords(index: &EntryIndex, entry: &Entry) -> String {
+    let mut keywords = entry.keywords.clone();
+    let mut visited = FxHashSet::default();
+    let mut stack: Vec<&str> = entry.parents.iter().map(String::as_str).collect();
+    while let Some(key) = stack.pop() {
+        if key == entry.name.text || !visited.insert(key) {
+            continue;
+        }
+
+        if let Some(parent) = index.get(key) {
+            keywords.push(' ');
+            keywords.push_str(&parent.keywords);
+            stack.extend(parent.parents.iter().map(String::as_str));
+        }
+    }
+
+    keywords
+}
+
+fn own_fields<'a>(
+    entry: &bibtex::Entry,
+    semantics: &'a Semantics,
+) -> impl Iterator<Item = ResolvedField<'a>> {
+    entry.fields().filter_map(move |field| {
+        let name = field.name_token()?.text().to_lowercase();
+        Some(ResolvedField {
+            name,
+            field,
+            semantics,
+        })
+    })
+}
+
+/// Collects the fields that an entry inherits from its parents, including the ones that it overrides.
+pub fn parent_fields<'a>(
+    index: &EntryIndex<'a>,
+    entry: &bibtex::Entry,
+    semantics: &'a Semantics,
+) -> Vec<ResolvedField<'a>> {
+    let mut fields = Vec::new();
+    inherit_fields(
+        index,
+        entry,
+        semantics,
+        &mut FxHashSet::default(),
+        &mut fields,
+    );
+    fields
+}
+
+fn resolve_fields<'a>(
+    index: &EntryIndex<'a>,
+    entry: &bibtex::Entry,
+    semantics: &'a Semantics,
+    visited: &mut FxHashSet<String>,
+) -> Vec<ResolvedField<'a>> {
+    let mut fields: Vec<_> = own_fields(entry, semantics).collect();
+    inherit_fields(index, entry, semantics, visited, &mut fields);
+    fields
+}
+
+/// Appends the fields of the parents of `entry` that are not yet part of `fields`.
+fn inherit_fields<'a>(
+    index: &EntryIndex<'a>,
+    entry: &bibtex::Entry,
+    semantics: &'a Semantics,
+    visited: &mut FxHashSet<String>,
+    fields: &mut Vec<ResolvedField<'a>>,
+) {
+    let Some(key) = entry.name_token() else {
+        return;
+    };
+
+    if !visited.insert(key.text().into()) {
+        return;
+    }
+
+    let child_type = entry_type(entry);
+    let mut defined: FxHashSet<_> = fields
+        .iter()
+        .map(|field| canonical_name(&field.name).to_string())
+        .collect();
+
+    let field_text = |name| {
+        entry
+            .fields()
+            .filter(|field| {
+                field
+                    .name_token()
+                    .is_some_and(|token| token.text().eq_ignore_ascii_case(name))
+            })
+            .find_map(|field| {
+                Some(TextFieldData::parse(&field.value()?, &semantics.expanded_defs)?.text)
+            })
+    };
+
+    let xdata = field_text("xdata").unwrap_or_default();
+    let xdata = xdata
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty());
+    let crossref = field_text("crossref");
+    let parents = xdata
+        .map(|key| (key.to_string(), false))
+        .chain(crossref.map(|key| (key.trim().to_string(), true)));
+
+    for (key, is_crossref) in parents {
+        let Some((parent, parent_semantics)) = index.find(&key) else {
+            continue;
+        };
+
+        let parent_type = entry_type(&parent);
+        for field in resolve_fields(index, &parent, parent_semantics, visited) {
+            if UNINHERITED_FIELDS.contains(&field.name.as_str()) {
+                continue;
+            }
+
+            let names = if is_crossref {
+                inherited_names(&parent_type, &child_type, &field.name)
+            } else {
+                vec![field.name.clone()]
+            };
+
+            for name in names {
+                if defined.insert(canonical_name(&name).to_string()) {
+                    fields.push(ResolvedField {
+                        name,
+                        ..field.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    visited.remove(key.text());
+}
+
+fn parent_keys(entry: &bibtex::Entry, expanded_defs: &FxHashMap<String, String>) -> Vec<String> {
+    entry
+        .fields()
+        .filter(|field| {
+            field.name_token().is_some_and(|name| {
+                name.text().eq_ignore_ascii_case("crossref")
+                    || name.text().eq_ignore_ascii_case("xdata")
+            })
+        })
+        .filter_map(|field| TextFieldData::parse(&field.value()?, expanded_defs))
+        .flat_map(|data| {
+            data.text
+                .split(',')
+                .map(|key| key.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+fn entry_type(entry: &bibtex::Entry) -> String {
+    let name = entry
+        .type_token()
+        .map(|token| token.text()[1..].to_lowercase())
+        .unwrap_or_default();
+
+    match name.as_str() {
+        "conference" => String::from("inproceedings"),
+        _ => name,
+    }
+}
+
+/// Maps BibTeX field names to the names that `biblatex` uses.
+fn canonical_name(name: &str) -> &str {
+    match name {
+        "address" => "location",
+        "annote" => "annotation",
+        "archiveprefix" => "eprinttype",
+        "journal" => "journaltitle",
+        "key" => "sortkey",
+        "pdf" => "file",
+        "primaryclass" => "eprintclass",
+        "school" => "institution",
+        _ => name,
+    }
+}
+
+/// Computes the names that the field `name` of a `crossref` parent has within the child.
+fn inherited_names(parent_type: &str, child_type: &str, name: &str) -> Vec<String> {
+    let prefix = TITLE_MAPPINGS
+        .iter()
+        .find(|(parents, children, _)| {
+            parents.contains(&parent_type) && children.contains(&child_type)
+        })
+        .map(|(_, _, prefix)| *prefix);
+
+    let Some(prefix) = prefix else {
+        return vec![name.to_string()];
+    };
+
+    match name {
+        "title" | "subtitle" | "titleaddon" => vec![format!("{prefix}{name}")],
+        "shorttitle" | "sorttitle" | "indextitle" | "indexsorttitle" => Vec::new(),
+        "author" if prefix == "book" => vec![String::from("author"), String::from("bookauthor")],
+        _ => vec![name.to_string()],
+    }
+}
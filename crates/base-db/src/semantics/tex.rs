@@ -1,6 +1,6 @@
 use rowan::{TextRange, ast::AstNode};
 use rustc_hash::FxHashSet;
-use syntax::latex::{self, HasBrack, HasCurly};
+use syntax::latex::{self, HasBrack, HasCurly, HasKeyValueBody};
 use titlecase::titlecase;
 
 use parser::SyntaxConfig;
@@ -40,6 +40,10 @@ pub struct Semantics {
     pub diagnostic_suppressions: Vec<TextRange>,
     pub warning_suppression_ranges: Vec<(TextRange, TextRange)>,
     pub bibitems: FxHashSet<Span>,
+    /// The main language selected with `babel` or `polyglossia`.
+    pub language: Option<String>,
+    /// The bibliography style selected with `biblatex` or `\bibliographystyle`.
+    pub citation_style: Option<String>,
 }
 
 impl Semantics {
@@ -103,6 +107,8 @@ impl Semantics {
             self.process_graphics_path(graphics_path);
         } else if let Some(bibitem) = latex::BibItem::cast(node.clone()) {
             self.process_bibitem(bibitem);
+        } else if let Some(command) = latex::GenericCommand::cast(node.clone()) {
+            self.process_generic_command(command);
         }
     }
 
@@ -111,6 +117,18 @@ impl Semantics {
             return;
         };
 
+        if include.syntax().kind() == latex::PACKAGE_INCLUDE
+            && list.keys().any(|key| key.to_string() == "babel")
+        {
+            self.process_babel_options(&include);
+        }
+
+        if include.syntax().kind() == latex::PACKAGE_INCLUDE
+            && list.keys().any(|key| key.to_string() == "biblatex")
+        {
+            self.process_biblatex_options(&include);
+        }
+
         for path in list.keys() {
             let kind = match include.syntax().kind() {
                 latex::PACKAGE_INCLUDE => LinkKind::Sty,
@@ -353,6 +371,92 @@ impl Semantics {
         }
     }
 
+    /// Finds the main language of `babel`, which is either given with `main=<language>`
+    /// or is the last language in the package options.
+    fn process_babel_options(&mut self, include: &latex::Include) {
+        let Some(body) = include.options().and_then(|options| options.body()) else {
+            return;
+        };
+
+        let mut language = None;
+        for pair in body.pairs() {
+            let Some(key) = pair.key().map(|key| key.to_string()) else {
+                continue;
+            };
+
+            match pair.value().and_then(|value| value.text()) {
+                Some(value) if key == "main" => {
+                    language = Some(value);
+                    break;
+                }
+                Some(_) => {}
+                None => language = Some(key),
+            }
+        }
+
+        if language.is_some() {
+            self.language = language;
+        }
+    }
+
+    /// Finds the bibliography style of `biblatex`, which is given with `bibstyle=<style>`
+    /// or `style=<style>`.
+    fn process_biblatex_options(&mut self, include: &latex::Include) {
+        let Some(body) = include.options().and_then(|options| options.body()) else {
+            return;
+        };
+
+        let mut style = None;
+        for pair in body.pairs() {
+            let Some(key) = pair.key().map(|key| key.to_string()) else {
+                continue;
+            };
+
+            match pair.value().and_then(|value| value.text()) {
+                Some(value) if key == "bibstyle" => {
+                    style = Some(value);
+                    break;
+                }
+                Some(value) if key == "style" => style = Some(value),
+                _ => {}
+            }
+        }
+
+        if style.is_some() {
+            self.citation_style = style;
+        }
+    }
+
+    fn process_generic_command(&mut self, command: latex::GenericCommand) {
+        let Some(name) = command.name() else {
+            return;
+        };
+
+        let argument = || {
+            command
+                .syntax()
+                .children()
+                .filter_map(latex::CurlyGroup::cast)
+                .last()
+                .and_then(|group| group.content_text())
+                .filter(|text| !text.is_empty())
+        };
+
+        match name.text() {
+            "\\setdefaultlanguage" | "\\setmainlanguage" => {
+                if let Some(language) = argument() {
+                    self.language = Some(language);
+                }
+            }
+            "\\bibliographystyle" => {
+                if let Some(style) = argument() {
+                    self.citation_style = Some(style);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn process_bibitem(&mut self, bibitem: latex::BibItem) {
         if let Some(name) = bibitem.name() {
             if let Some(key) = name.key() {
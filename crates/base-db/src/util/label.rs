@@ -61,6 +61,24 @@ pub enum RenderedObject<'a> {
     EnumItem,
 }
 
+impl RenderedObject<'_> {
+    /// Checks whether the object is of the given kind, for example `figure` or `equation`.
+    /// Theorem-like objects also match their own heading, for example `lemma`.
+    pub fn matches_kind(&self, kind: &str) -> bool {
+        match self {
+            Section { .. } => kind.eq_ignore_ascii_case("section"),
+            Float {
+                kind: float_kind, ..
+            } => kind.eq_ignore_ascii_case(float_kind.as_str()),
+            Theorem {
+                kind: theorem_kind, ..
+            } => kind.eq_ignore_ascii_case("theorem") || kind.eq_ignore_ascii_case(theorem_kind),
+            Equation => kind.eq_ignore_ascii_case("equation"),
+            EnumItem => kind.eq_ignore_ascii_case("item"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RenderedLabel<'a> {
     pub range: TextRange,
@@ -116,7 +116,7 @@ impl Object for bib::Entry {
     }
 
     fn find<'db>(document: &'db Document) -> Box<dyn Iterator<Item = &'db Self> + 'db> {
-        let data = document.data.as_bib();
+        let data = document.data.as_bibliography();
         let iter = data
             .into_iter()
             .flat_map(|data| data.semantics.entries.iter());
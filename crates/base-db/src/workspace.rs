@@ -18,6 +18,7 @@ pub struct Workspace {
     distro: Distro,
     folders: Vec<PathBuf>,
     graphs: FxHashMap<Url, deps::Graph>,
+    build_profiles: FxHashMap<Url, String>,
 }
 
 impl Workspace {
@@ -84,6 +85,10 @@ impl Workspace {
             config: &self.config,
         }));
 
+        self.update_graphs();
+    }
+
+    fn update_graphs(&mut self) {
         self.graphs = self
             .iter()
             .map(|start| (start.uri.clone(), deps::Graph::new(self, start)))
@@ -159,6 +164,24 @@ impl Workspace {
         self.folders = folders;
     }
 
+    /// The profile that was explicitly requested for the last build of a root document.
+    pub fn build_profile(&self, uri: &Url) -> Option<&str> {
+        self.build_profiles.get(uri).map(String::as_str)
+    }
+
+    /// Remembers the profile of the last build of a root document,
+    /// so that other commands find the files in the output directories of that profile.
+    pub fn set_build_profile(&mut self, uri: Url, profile: Option<String>) {
+        let changed = match profile {
+            Some(profile) => self.build_profiles.insert(uri, profile.clone()) != Some(profile),
+            None => self.build_profiles.remove(&uri).is_some(),
+        };
+
+        if changed {
+            self.update_graphs();
+        }
+    }
+
     pub fn set_cursor(&mut self, uri: &Url, cursor: LineCol) -> Option<()> {
         let mut document = self.lookup(uri)?.clone();
         document.cursor = cursor;
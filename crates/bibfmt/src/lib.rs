@@ -6,6 +6,50 @@ pub struct Options {
     pub insert_spaces: bool,
     pub tab_size: usize,
     pub line_length: usize,
+    /// The fields that are moved to the start of an entry in this order.
+    /// The remaining fields keep their relative order.
+    pub field_order: Vec<String>,
+    pub entry_type_case: Case,
+    pub field_name_case: Case,
+    /// The delimiter that replaces braces or quotes around field values.
+    pub delimiter: Option<Delimiter>,
+    /// Pads the field names of an entry so that their `=` signs line up.
+    pub align_equals: bool,
+    pub trailing_comma: bool,
+    pub remove_empty_fields: bool,
+    /// The fields that are removed from every entry.
+    pub remove_fields: Vec<String>,
+    pub sort_entries: Option<SortKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Case {
+    Preserve,
+    Lower,
+    Upper,
+}
+
+impl Case {
+    fn apply(self, text: &str) -> String {
+        match self {
+            Self::Preserve => text.to_string(),
+            Self::Lower => text.to_lowercase(),
+            Self::Upper => text.to_uppercase(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Delimiter {
+    Braces,
+    Quotes,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortKey {
+    Key,
+    Year,
+    Author,
 }
 
 impl Options {
@@ -24,6 +68,15 @@ impl Default for Options {
             insert_spaces: true,
             tab_size: 4,
             line_length: 80,
+            field_order: Vec::new(),
+            entry_type_case: Case::Lower,
+            field_name_case: Case::Preserve,
+            delimiter: None,
+            align_equals: false,
+            trailing_comma: true,
+            remove_empty_fields: false,
+            remove_fields: Vec::new(),
+            sort_entries: None,
         }
     }
 }
@@ -31,7 +84,12 @@ impl Default for Options {
 pub fn format(root: &bibtex::SyntaxNode, line_index: &LineIndex, options: &Options) -> String {
     let indent = options.indent();
     let mut output = String::new();
-    for elem in root.children_with_tokens() {
+    let mut elements: Vec<_> = root.children_with_tokens().collect();
+    if let Some(key) = options.sort_entries {
+        sort_entries(&mut elements, key);
+    }
+
+    for elem in elements {
         match elem {
             NodeOrToken::Token(token) => {
                 output.push_str(token.text());
@@ -53,6 +111,23 @@ pub fn format(root: &bibtex::SyntaxNode, line_index: &LineIndex, options: &Optio
     output
 }
 
+/// Formats a single entry, `@string` or `@preamble` without looking at the rest of the file.
+/// Sorting the entries does not apply here.
+pub fn format_node(node: &bibtex::SyntaxNode, line_index: &LineIndex, options: &Options) -> String {
+    let indent = options.indent();
+    let mut output = String::new();
+    let mut fmt = Formatter {
+        indent: &indent,
+        output: &mut output,
+        options,
+        align: Vec::new(),
+        line_index,
+    };
+
+    fmt.visit_node(node.clone());
+    output
+}
+
 struct Formatter<'a> {
     output: &'a mut String,
     indent: &'a str,
@@ -62,8 +137,9 @@ struct Formatter<'a> {
 }
 
 impl<'a> Formatter<'a> {
-    fn visit_token_lowercase(&mut self, token: &bibtex::SyntaxToken) {
-        self.output.push_str(&token.text().to_lowercase());
+    fn visit_type(&mut self, token: &bibtex::SyntaxToken) {
+        let text = self.options.entry_type_case.apply(token.text());
+        self.output.push_str(&text);
     }
 
     fn should_insert_space(
@@ -86,7 +162,7 @@ impl<'a> Formatter<'a> {
         match parent.kind() {
             bibtex::PREAMBLE => {
                 let preamble = bibtex::Preamble::cast(parent).unwrap();
-                self.visit_token_lowercase(&preamble.type_token().unwrap());
+                self.visit_type(&preamble.type_token().unwrap());
                 self.output.push('{');
                 if preamble.syntax().children().next().is_some() {
                     self.align.push(self.base_align());
@@ -98,7 +174,7 @@ impl<'a> Formatter<'a> {
             }
             bibtex::STRING => {
                 let string = bibtex::StringDef::cast(parent).unwrap();
-                self.visit_token_lowercase(&string.type_token().unwrap());
+                self.visit_type(&string.type_token().unwrap());
                 self.output.push('{');
                 if let Some(name) = string.name_token() {
                     self.output.push_str(name.text());
@@ -112,31 +188,33 @@ impl<'a> Formatter<'a> {
             }
             bibtex::ENTRY => {
                 let entry = bibtex::Entry::cast(parent).unwrap();
-                self.visit_token_lowercase(&entry.type_token().unwrap());
+                self.visit_type(&entry.type_token().unwrap());
                 self.output.push('{');
                 if let Some(key) = entry.name_token() {
                     self.output.push_str(&key.to_string());
                     self.output.push(',');
                     self.output.push('\n');
-                    for field in entry.fields() {
-                        self.visit_node(field.syntax().clone());
+
+                    let fields = self.arrange_fields(&entry);
+                    let name_width = fields
+                        .iter()
+                        .filter(|_| self.options.align_equals)
+                        .filter_map(|field| field.name_token())
+                        .map(|name| name.text().chars().count())
+                        .max()
+                        .unwrap_or(0);
+
+                    for (i, field) in fields.iter().enumerate() {
+                        let is_last = i == fields.len() - 1;
+                        self.visit_field(field, name_width, is_last);
                     }
+
                     self.output.push('}');
                 }
             }
             bibtex::FIELD => {
                 let field = bibtex::Field::cast(parent).unwrap();
-                self.output.push_str(self.indent);
-                let name = field.name_token().unwrap();
-                self.output.push_str(name.text());
-                self.output.push_str(" = ");
-                if let Some(value) = field.value() {
-                    let count = name.text().chars().count();
-                    self.align.push(self.options.tab_size + count + 3);
-                    self.visit_node(value.syntax().clone());
-                    self.output.push(',');
-                    self.output.push('\n');
-                }
+                self.visit_field(&field, 0, false);
             }
             kind if bibtex::Value::can_cast(kind) => {
                 let tokens: Vec<_> = parent
@@ -145,14 +223,22 @@ impl<'a> Formatter<'a> {
                     .filter(|token| token.kind() != bibtex::WHITESPACE)
                     .collect();
 
-                self.output.push_str(tokens[0].text());
+                let delimiters = self.delimiters(&parent, &tokens);
+                let token_text = |i: usize| match delimiters {
+                    Some((left, _)) if i == 0 => left,
+                    Some((_, right)) if i == tokens.len() - 1 => right,
+                    _ => tokens[i].text(),
+                };
+
+                self.output.push_str(token_text(0));
 
                 let align = self.align.pop().unwrap_or_default();
                 let mut length = align + tokens[0].text().chars().count();
                 for i in 1..tokens.len() {
                     let previous = &tokens[i - 1];
                     let current = &tokens[i];
-                    let current_length = current.text().chars().count();
+                    let current_text = token_text(i);
+                    let current_length = current_text.chars().count();
 
                     let insert_space = self.should_insert_space(previous, current);
                     let space_length = if insert_space { 1 } else { 0 };
@@ -168,7 +254,7 @@ impl<'a> Formatter<'a> {
                         self.output.push(' ');
                         length += 1;
                     }
-                    self.output.push_str(current.text());
+                    self.output.push_str(current_text);
                     length += current_length;
                 }
             }
@@ -187,6 +273,191 @@ impl<'a> Formatter<'a> {
             _ => unreachable!(),
         }
     }
+
+    fn visit_field(&mut self, field: &bibtex::Field, name_width: usize, is_last: bool) {
+        self.output.push_str(self.indent);
+        let name = field.name_token().unwrap();
+        let name_text = self.options.field_name_case.apply(name.text());
+        let count = name_width.max(name_text.chars().count());
+        self.output.push_str(&name_text);
+        for _ in name_text.chars().count()..count {
+            self.output.push(' ');
+        }
+
+        self.output.push_str(" = ");
+        if let Some(value) = field.value() {
+            self.align.push(self.options.tab_size + count + 3);
+            self.visit_node(value.syntax().clone());
+            if !is_last || self.options.trailing_comma {
+                self.output.push(',');
+            }
+
+            self.output.push('\n');
+        }
+    }
+
+    /// Removes the unwanted fields of an entry and moves the configured ones to the front.
+    fn arrange_fields(&self, entry: &bibtex::Entry) -> Vec<bibtex::Field> {
+        let mut fields: Vec<_> = entry
+            .fields()
+            .filter(|field| {
+                let Some(name) = field.name_token() else {
+                    return false;
+                };
+
+                let is_removed = self
+                    .options
+                    .remove_fields
+                    .iter()
+                    .any(|removed| removed.eq_ignore_ascii_case(name.text()));
+
+                let is_removed_empty = self.options.remove_empty_fields && is_empty(field);
+                !is_removed && !is_removed_empty
+            })
+            .collect();
+
+        let order = &self.options.field_order;
+        fields.sort_by_key(|field| {
+            let name = field.name_token().unwrap();
+            order
+                .iter()
+                .position(|ordered| ordered.eq_ignore_ascii_case(name.text()))
+                .unwrap_or(order.len())
+        });
+
+        fields
+    }
+
+    /// Returns the delimiters that replace the first and last token of a value.
+    fn delimiters(
+        &self,
+        value: &bibtex::SyntaxNode,
+        tokens: &[bibtex::SyntaxToken],
+    ) -> Option<(&'static str, &'static str)> {
+        let delimiter = self.options.delimiter?;
+        let last = tokens.last()?;
+        match (value.kind(), delimiter) {
+            (bibtex::CURLY_GROUP, Delimiter::Quotes) if last.kind() == bibtex::R_CURLY => {
+                // Quotes cannot be nested, so they are only valid if the content has no quotes outside of braces.
+                let mut depth = 0;
+                for token in &tokens[1..tokens.len() - 1] {
+                    match token.kind() {
+                        bibtex::L_CURLY => depth += 1,
+                        bibtex::R_CURLY => depth -= 1,
+                        bibtex::QUOTE if depth == 0 => return None,
+                        _ => {}
+                    }
+                }
+
+                Some(("\"", "\""))
+            }
+            (bibtex::QUOTE_GROUP, Delimiter::Braces)
+                if tokens.len() > 1 && last.kind() == bibtex::QUOTE =>
+            {
+                Some(("{", "}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_empty(field: &bibtex::Field) -> bool {
+    let Some(value) = field.value() else {
+        return true;
+    };
+
+    if !matches!(
+        value.syntax().kind(),
+        bibtex::CURLY_GROUP | bibtex::QUOTE_GROUP
+    ) {
+        return false;
+    }
+
+    value
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .all(|token| {
+            matches!(
+                token.kind(),
+                bibtex::WHITESPACE | bibtex::L_CURLY | bibtex::R_CURLY | bibtex::QUOTE
+            )
+        })
+}
+
+/// Sorts the entries among themselves. Other elements like comments and `@string` definitions keep their position.
+fn sort_entries(elements: &mut [bibtex::SyntaxElement], key: SortKey) {
+    let slots: Vec<_> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, element)| element.kind() == bibtex::ENTRY)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut entries: Vec<_> = slots.iter().map(|&i| elements[i].clone()).collect();
+    entries.sort_by_cached_key(|element| {
+        let entry = element.as_node().cloned().and_then(bibtex::Entry::cast);
+        let sort_key = entry.as_ref().and_then(|entry| match key {
+            SortKey::Key => entry.name_token().map(|name| name.text().to_lowercase()),
+            SortKey::Year => find_year(entry),
+            SortKey::Author => find_first_author(entry),
+        });
+
+        // Entries without a value for the key are moved to the end.
+        (sort_key.is_none(), sort_key)
+    });
+
+    for (slot, entry) in slots.into_iter().zip(entries) {
+        elements[slot] = entry;
+    }
+}
+
+fn field_text(entry: &bibtex::Entry, name: &str) -> Option<String> {
+    let field = entry.fields().find(|field| {
+        field
+            .name_token()
+            .is_some_and(|token| token.text().eq_ignore_ascii_case(name))
+    })?;
+
+    let text: String = field
+        .value()?
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                bibtex::L_CURLY | bibtex::R_CURLY | bibtex::QUOTE
+            )
+        })
+        .map(|token| token.text().to_string())
+        .collect();
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(text).filter(|text| !text.is_empty())
+}
+
+fn find_year(entry: &bibtex::Entry) -> Option<String> {
+    let text = field_text(entry, "year").or_else(|| field_text(entry, "date"))?;
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let year: String = text[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+
+    Some(format!("{year:0>4}"))
+}
+
+/// Extracts the last name of the first author in either `Last, First` or `First Last` form.
+fn find_first_author(entry: &bibtex::Entry) -> Option<String> {
+    let text = field_text(entry, "author").or_else(|| field_text(entry, "editor"))?;
+    let author = text.split(" and ").next()?.trim();
+    let last_name = match author.split_once(',') {
+        Some((last_name, _)) => last_name.trim(),
+        None => author.rsplit(' ').next()?,
+    };
+
+    Some(last_name.to_lowercase())
 }
 
 #[cfg(test)]
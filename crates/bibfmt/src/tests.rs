@@ -2,16 +2,20 @@ use expect_test::{Expect, expect};
 use line_index::LineIndex;
 use syntax::bibtex;
 
-use crate::Options;
+use crate::{Case, Delimiter, Options, SortKey};
 
-fn check(input: &str, expect: Expect) {
+fn check_with_options(input: &str, options: &Options, expect: Expect) {
     let green = parser::parse_bibtex(input);
     let root = bibtex::SyntaxNode::new_root(green);
     let line_index = LineIndex::new(input);
-    let output = crate::format(&root, &line_index, &Options::default());
+    let output = crate::format(&root, &line_index, options);
     expect.assert_eq(&output);
 }
 
+fn check(input: &str, expect: Expect) {
+    check_with_options(input, &Options::default(), expect);
+}
+
 #[test]
 fn test_wrap_long_lines() {
     check(
@@ -142,3 +146,186 @@ fn test_preamble() {
         expect![[r#"@preamble{"foo bar baz"}"#]],
     );
 }
+
+#[test]
+fn test_field_order() {
+    check_with_options(
+        r#"@article{foo, year = 2020, title = {Foo}, note = {Bar}, author = {Baz}}"#,
+        &Options {
+            field_order: vec!["author".into(), "Title".into()],
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{foo,
+                author = {Baz},
+                title = {Foo},
+                year = 2020,
+                note = {Bar},
+            }"#]],
+    );
+}
+
+#[test]
+fn test_case() {
+    check_with_options(
+        r#"@Article{foo, Title = {Foo}, YEAR = 2020}"#,
+        &Options {
+            entry_type_case: Case::Upper,
+            field_name_case: Case::Lower,
+            ..Options::default()
+        },
+        expect![[r#"
+            @ARTICLE{foo,
+                title = {Foo},
+                year = 2020,
+            }"#]],
+    );
+}
+
+#[test]
+fn test_delimiter_braces() {
+    check_with_options(
+        r#"@article{foo, title = "Foo {"}Bar", note = "baz" # "qux"}"#,
+        &Options {
+            delimiter: Some(Delimiter::Braces),
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{foo,
+                title = {Foo {"}Bar},
+                note = "baz" # "qux",
+            }"#]],
+    );
+}
+
+#[test]
+fn test_delimiter_quotes() {
+    check_with_options(
+        r#"@article{foo, title = {Foo {Bar}}, note = {Say "Baz"}}"#,
+        &Options {
+            delimiter: Some(Delimiter::Quotes),
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{foo,
+                title = "Foo {Bar}",
+                note = {Say "Baz"},
+            }"#]],
+    );
+}
+
+#[test]
+fn test_align_equals() {
+    check_with_options(
+        r#"@article{foo, title = {Foo}, publisher = {Bar}}"#,
+        &Options {
+            align_equals: true,
+            trailing_comma: false,
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{foo,
+                title     = {Foo},
+                publisher = {Bar}
+            }"#]],
+    );
+}
+
+#[test]
+fn test_remove_fields() {
+    check_with_options(
+        r#"@article{foo, title = {Foo}, abstract = {Bar}, note = {}, file = "", year = 2020}"#,
+        &Options {
+            remove_empty_fields: true,
+            remove_fields: vec!["Abstract".into()],
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{foo,
+                title = {Foo},
+                year = 2020,
+            }"#]],
+    );
+}
+
+#[test]
+fn test_sort_by_key() {
+    check_with_options(
+        r#"@string{s = "x"}
+@article{b,}
+% comment
+@article{A,}
+@article{c,}"#,
+        &Options {
+            sort_entries: Some(SortKey::Key),
+            ..Options::default()
+        },
+        expect![[r#"
+            @string{s = "x"}
+            @article{A,
+            }
+            % comment
+            @article{b,
+            }
+            @article{c,
+            }"#]],
+    );
+}
+
+#[test]
+fn test_sort_by_year() {
+    check_with_options(
+        r#"@article{a, year = 2021}
+@article{b}
+@article{c, date = {1999-05}}"#,
+        &Options {
+            sort_entries: Some(SortKey::Year),
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{c,
+                date = {1999-05},
+            }
+            @article{a,
+                year = 2021,
+            }
+            @article{b,
+            }"#]],
+    );
+}
+
+#[test]
+fn test_sort_by_author() {
+    check_with_options(
+        r#"@article{a, author = {John Smith}}
+@article{b, author = {Doe, Jane and Smith, John}}"#,
+        &Options {
+            sort_entries: Some(SortKey::Author),
+            ..Options::default()
+        },
+        expect![[r#"
+            @article{b,
+                author = {Doe, Jane and Smith, John},
+            }
+            @article{a,
+                author = {John Smith},
+            }"#]],
+    );
+}
+
+#[test]
+fn test_format_node() {
+    let input = r#"@article{foo, title = {Foo}}
+
+@article{bar, title={Bar}}"#;
+    let green = parser::parse_bibtex(input);
+    let root = bibtex::SyntaxNode::new_root(green);
+    let line_index = LineIndex::new(input);
+    let entry = root.children().last().unwrap();
+    let output = crate::format_node(&entry, &line_index, &Options::default());
+    expect![[r#"
+        @article{bar,
+            title = {Bar},
+        }"#]]
+    .assert_eq(&output);
+}
@@ -77,4 +77,23 @@ impl AuthorFieldData {
 
         Some(Self { authors })
     }
+
+    /// Formats the names in BibTeX order, for example `Doe, John`.
+    pub fn names_last_first(&self) -> impl Iterator<Item = String> + '_ {
+        self.authors.iter().map(|name| {
+            let first = match name.given_name() {
+                Some(given_name) => [Some(given_name.into()), name.middle_name()]
+                    .into_iter()
+                    .flatten()
+                    .join(" "),
+                None => name.initials().chars().map(|c| format!("{c}.")).join(" "),
+            };
+
+            match name.generational_suffix() {
+                Some(suffix) => format!("{}, {}, {}", name.surname(), suffix, first),
+                None if first.is_empty() => name.surname().into(),
+                None => format!("{}, {}", name.surname(), first),
+            }
+        })
+    }
 }
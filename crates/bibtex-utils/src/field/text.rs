@@ -11,6 +11,7 @@ use super::FieldParseCache;
 pub enum TextField {
     Abstract,
     Addendum,
+    Address,
     BookSubtitle,
     BookTitle,
     BookTitleAddon,
@@ -28,6 +29,7 @@ pub enum TextField {
     EventTitleAddon,
     Holder,
     HowPublished,
+    Institution,
     Isbn,
     Issn,
     Issue,
@@ -38,6 +40,7 @@ pub enum TextField {
     JournalSubtitle,
     JournalTitle,
     JournalTitleAddon,
+    LangId,
     Language,
     Location,
     MainTitle,
@@ -45,8 +48,10 @@ pub enum TextField {
     MainTitleAddon,
     Note,
     OrigLanguage,
+    Organization,
     Publisher,
     Pubstate,
+    School,
     Series,
     Subtitle,
     Title,
@@ -63,6 +68,7 @@ impl TextField {
         Some(match input.to_ascii_lowercase().as_str() {
             "abstract" => Self::Abstract,
             "addendum" => Self::Addendum,
+            "address" => Self::Address,
             "booksubtitle" => Self::BookSubtitle,
             "booktitle" => Self::BookTitle,
             "booktitleaddon" => Self::BookTitleAddon,
@@ -80,6 +86,7 @@ impl TextField {
             "eventtitleaddon" => Self::EventTitleAddon,
             "holder" => Self::Holder,
             "howpublished" => Self::HowPublished,
+            "institution" => Self::Institution,
             "isbn" => Self::Isbn,
             "issn" => Self::Issn,
             "issue" => Self::Issue,
@@ -90,6 +97,7 @@ impl TextField {
             "journalsubtitle" => Self::JournalSubtitle,
             "journaltitle" => Self::JournalTitle,
             "journaltitleaddon" => Self::JournalTitleAddon,
+            "langid" | "hyphenation" => Self::LangId,
             "language" => Self::Language,
             "location" => Self::Location,
             "maintitle" => Self::MainTitle,
@@ -97,8 +105,10 @@ impl TextField {
             "maintitleaddon" => Self::MainTitleAddon,
             "note" => Self::Note,
             "origlanguage" => Self::OrigLanguage,
+            "organization" => Self::Organization,
             "publisher" => Self::Publisher,
             "pubstate" => Self::Pubstate,
+            "school" => Self::School,
             "series" => Self::Series,
             "subtitle" => Self::Subtitle,
             "title" => Self::Title,
@@ -1,29 +1,37 @@
-use base_db::semantics::bib::Semantics;
 use bibtex_utils::field::{
     author::AuthorField,
-    date::DateField,
+    date::{DateField, DateFieldData},
     number::{NumberField, NumberFieldData},
     text::TextField,
 };
+use chrono::Datelike;
 use isocountry::CountryCode;
 use itertools::Itertools;
-use syntax::bibtex;
 use titlecase::titlecase;
 use url::Url;
 
 use super::{
     entry::{EntryData, EntryKind},
+    locale::{Locale, TermForm},
     output::{Inline, InlineBuilder, Punct},
+    processor::capitalize,
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Driver {
     builder: InlineBuilder,
+    locale: Locale,
 }
 
 impl Driver {
-    pub fn process(&mut self, entry: &bibtex::Entry, semantics: &Semantics) {
-        let entry = EntryData::from_entry(entry, semantics);
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            builder: InlineBuilder::default(),
+            locale,
+        }
+    }
+
+    pub fn process(&mut self, entry: EntryData) {
         match entry.kind {
             EntryKind::Article
             | EntryKind::DataSet
@@ -161,7 +169,10 @@ impl Driver {
     fn commentator(&mut self, entry: &mut EntryData) -> Option<()> {
         let commentator = entry.author.remove(&AuthorField::Commentator)?;
         self.builder.push(
-            Inline::Regular(format!("With a comment. by {}", commentator)),
+            Inline::Regular(format!(
+                "{} {commentator}",
+                self.term("with-commentator", TermForm::Long)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -172,7 +183,10 @@ impl Driver {
     fn translator(&mut self, entry: &mut EntryData) -> Option<()> {
         let translator = entry.author.remove(&AuthorField::Translator)?;
         self.builder.push(
-            Inline::Regular(format!("Trans. by {}", translator)),
+            Inline::Regular(format!(
+                "{} {translator}",
+                self.term("translator", TermForm::VerbShort)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -183,7 +197,10 @@ impl Driver {
     fn annotator(&mut self, entry: &mut EntryData) -> Option<()> {
         let annotator = entry.author.remove(&AuthorField::Annotator)?;
         self.builder.push(
-            Inline::Regular(format!("With annots. by {}", annotator)),
+            Inline::Regular(format!(
+                "{} {annotator}",
+                self.term("with-annotator", TermForm::Long)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -205,10 +222,10 @@ impl Driver {
         type_field: TextField,
     ) -> Option<()> {
         let editor = entry.author.remove(&name_field)?;
-        let editor_type = entry
-            .text
-            .remove(&type_field)
-            .map_or_else(|| "Ed. by".to_string(), |data| data.text);
+        let editor_type = entry.text.remove(&type_field).map_or_else(
+            || self.term("editor", TermForm::VerbShort),
+            |data| data.text,
+        );
 
         self.builder.push(
             Inline::Regular(format!("{editor_type} {editor}")),
@@ -312,7 +329,10 @@ impl Driver {
             .map_or(String::new(), |addon| format!(". {}", addon.text));
 
         let venue = entry.text.remove(&TextField::Venue);
-        let date = entry.date.remove(&DateField::EventDate);
+        let date = entry
+            .date
+            .remove(&DateField::EventDate)
+            .map(|date| self.format_date(date));
         let venue_and_date = match (venue, date) {
             (None, None) => String::new(),
             (None, Some(date)) => format!(" ({date})"),
@@ -373,7 +393,11 @@ impl Driver {
     fn version(&mut self, entry: &mut EntryData) -> Option<()> {
         let version = entry.text.remove(&TextField::Version)?;
         self.builder.push(
-            Inline::Regular(format!("Version {}", version.text)),
+            Inline::Regular(format!(
+                "{} {}",
+                self.term("version", TermForm::Long),
+                version.text
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -393,7 +417,8 @@ impl Driver {
         let date = [DateField::Date, DateField::Year, DateField::Month]
             .iter()
             .filter_map(|field| entry.date.remove(field))
-            .reduce(|a, b| a + b)?;
+            .reduce(|a, b| a + b)
+            .map(|date| self.format_date(date))?;
 
         let issue = entry
             .text
@@ -413,7 +438,11 @@ impl Driver {
     fn chapter(&mut self, entry: &mut EntryData) -> Option<()> {
         let chapter = entry.text.remove(&TextField::Chapter)?;
         self.builder.push(
-            Inline::Regular(format!("Chap. {}", chapter.text)),
+            Inline::Regular(format!(
+                "{} {}",
+                self.term("chapter", TermForm::Short),
+                chapter.text
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -425,10 +454,11 @@ impl Driver {
         let date = [DateField::Date, DateField::Year, DateField::Month]
             .iter()
             .filter_map(|field| entry.date.remove(field))
-            .reduce(|a, b| a + b)?;
+            .reduce(|a, b| a + b)
+            .map(|date| self.format_date(date))?;
 
         self.builder
-            .push(Inline::Regular(format!("{date}")), Punct::Dot, Punct::Dot);
+            .push(Inline::Regular(date), Punct::Dot, Punct::Dot);
 
         Some(())
     }
@@ -476,7 +506,7 @@ impl Driver {
     fn volumes(&mut self, entry: &mut EntryData) -> Option<()> {
         let volumes = entry.number.remove(&NumberField::Volume)?;
         self.builder.push(
-            Inline::Regular(format!("{volumes} vols")),
+            Inline::Regular(format!("{volumes} {}", self.short_term("volume", true))),
             Punct::Dot,
             Punct::Dot,
         );
@@ -492,7 +522,10 @@ impl Driver {
             .map_or(String::new(), |part| format!(".{part}"));
 
         self.builder.push(
-            Inline::Regular(format!("Vol. {}{}", volume, part)),
+            Inline::Regular(format!(
+                "{} {volume}{part}",
+                self.term("volume", TermForm::Short)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -518,10 +551,10 @@ impl Driver {
 
     fn edition(&mut self, entry: &mut EntryData) -> Option<()> {
         let text = match entry.number.remove(&NumberField::Edition)? {
-            NumberFieldData::Scalar(1) => "1st".to_string(),
-            NumberFieldData::Scalar(2) => "2nd".to_string(),
-            NumberFieldData::Scalar(3) => "3rd".to_string(),
-            NumberFieldData::Scalar(number) => format!("{}th", number),
+            NumberFieldData::Scalar(number) => {
+                let edition = self.short_term("edition", false);
+                format!("{} {edition}", self.locale.ordinal(number))
+            }
             NumberFieldData::Range(_, _) => return None,
             NumberFieldData::Other(text) => text,
         };
@@ -534,7 +567,10 @@ impl Driver {
     fn introduction(&mut self, entry: &mut EntryData) -> Option<()> {
         let author = entry.author.remove(&AuthorField::Introduction)?;
         self.builder.push(
-            Inline::Regular(format!("With an intro. by {}", author)),
+            Inline::Regular(format!(
+                "{} {author}",
+                self.term("with-introduction", TermForm::Long)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -545,7 +581,10 @@ impl Driver {
     fn foreword(&mut self, entry: &mut EntryData) -> Option<()> {
         let author = entry.author.remove(&AuthorField::Commentator)?;
         self.builder.push(
-            Inline::Regular(format!("With a forew. by {}", author)),
+            Inline::Regular(format!(
+                "{} {author}",
+                self.term("with-foreword", TermForm::Long)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -556,7 +595,10 @@ impl Driver {
     fn afterword(&mut self, entry: &mut EntryData) -> Option<()> {
         let author = entry.author.remove(&AuthorField::Commentator)?;
         self.builder.push(
-            Inline::Regular(format!("With an afterw. by {}", author)),
+            Inline::Regular(format!(
+                "{} {author}",
+                self.term("with-afterword", TermForm::Long)
+            )),
             Punct::Dot,
             Punct::Dot,
         );
@@ -586,7 +628,7 @@ impl Driver {
     fn page_total(&mut self, entry: &mut EntryData) -> Option<()> {
         let page_total = entry.number.remove(&NumberField::PageTotal)?;
         self.builder.push(
-            Inline::Regular(format!("{} pp", page_total)),
+            Inline::Regular(format!("{page_total} {}", self.short_term("page", true))),
             Punct::Dot,
             Punct::Dot,
         );
@@ -662,7 +704,7 @@ impl Driver {
         self.builder
             .push(Inline::Link { url, alt }, Punct::Space, Punct::Space);
 
-        let date = entry.date.remove(&DateField::UrlDate)?;
+        let date = self.format_date(entry.date.remove(&DateField::UrlDate)?);
         self.builder.push(
             Inline::Regular(format!("({date})")),
             Punct::Space,
@@ -723,6 +765,36 @@ impl Driver {
         Some(())
     }
 
+    /// Looks up a term of the locale and capitalizes it since it starts a sentence.
+    fn term(&self, name: &str, form: TermForm) -> String {
+        capitalize(self.locale.term(name, form, false).unwrap_or_default())
+    }
+
+    /// Looks up the short form of a term without the abbreviation dot, which is added by the builder.
+    fn short_term(&self, name: &str, plural: bool) -> String {
+        let term = self
+            .locale
+            .term(name, TermForm::Short, plural)
+            .unwrap_or_default();
+        term.strip_suffix('.').unwrap_or(term).to_string()
+    }
+
+    fn format_date(&self, date: DateFieldData) -> String {
+        let month = match &date {
+            DateFieldData::Date(date) => self.locale.month(date.month(), TermForm::Short),
+            DateFieldData::Month(month) => {
+                self.locale.month(month.number_from_month(), TermForm::Long)
+            }
+            DateFieldData::Year(_) | DateFieldData::Other(_) => None,
+        };
+
+        match (date, month) {
+            (DateFieldData::Date(date), Some(month)) => format!("{month} {}", date.year()),
+            (DateFieldData::Month(_), Some(month)) => month.to_string(),
+            (date, _) => date.to_string(),
+        }
+    }
+
     pub fn finish(self) -> impl Iterator<Item = (Inline, Punct)> {
         self.builder.finish()
     }
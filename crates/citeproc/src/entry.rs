@@ -1,4 +1,4 @@
-use base_db::semantics::bib::Semantics;
+use base_db::semantics::bib::ResolvedEntry;
 use bibtex_utils::field::{
     FieldParseCache,
     author::{AuthorField, AuthorFieldData},
@@ -7,7 +7,7 @@ use bibtex_utils::field::{
     text::{TextField, TextFieldData},
 };
 use rustc_hash::FxHashMap;
-use syntax::bibtex::{Entry, Field, HasName, HasType, HasValue, Value};
+use syntax::bibtex::{Field, HasType, HasValue, Value};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum EntryKind {
@@ -106,16 +106,19 @@ pub struct EntryData {
 }
 
 impl EntryData {
-    pub fn from_entry(entry: &Entry, semantics: &Semantics) -> Self {
+    pub fn from_entry(entry: &ResolvedEntry) -> Self {
         let mut data = EntryData {
-            kind: entry.type_token().map_or(EntryKind::Unknown, |token| {
-                EntryKind::parse(&token.text()[1..])
-            }),
+            kind: entry
+                .entry
+                .type_token()
+                .map_or(EntryKind::Unknown, |token| {
+                    EntryKind::parse(&token.text()[1..])
+                }),
             ..EntryData::default()
         };
 
-        for field in entry.fields() {
-            let _ = data.parse_field(&field, &semantics.expanded_defs);
+        for field in &entry.fields {
+            let _ = data.parse_field(&field.name, &field.field, &field.semantics.expanded_defs);
         }
 
         data
@@ -123,9 +126,12 @@ impl EntryData {
 }
 
 impl EntryData {
-    fn parse_field(&mut self, field: &Field, expanded_defs: &FieldParseCache) -> Option<()> {
-        let name = field.name_token()?;
-        let name = name.text();
+    fn parse_field(
+        &mut self,
+        name: &str,
+        field: &Field,
+        expanded_defs: &FieldParseCache,
+    ) -> Option<()> {
         let value = field.value()?;
         self.parse_author_field(name, &value, expanded_defs)
             .or_else(|| self.parse_date_field(name, &value, expanded_defs))
@@ -1,18 +1,61 @@
 mod driver;
 mod entry;
+mod locale;
 mod output;
+mod processor;
+mod style;
+mod xml;
 
-use base_db::semantics::bib::Semantics;
-use syntax::bibtex;
+use base_db::semantics::bib::ResolvedEntry;
+use bibtex_utils::field::text::TextField;
 use unicode_normalization::UnicodeNormalization;
 
-use self::{driver::Driver, output::Inline};
+use self::{
+    driver::Driver,
+    entry::EntryData,
+    locale::{Language, Locale},
+    output::Inline,
+};
 
+pub use self::style::{Style, StyleError};
+
+/// Renders a bibliography entry as Markdown.
+/// Without a `style`, the entry is rendered in a style that resembles the default style of `biblatex`.
+///
+/// The terms are localized using the `langid` field of the entry if present
+/// or the `language` of the document (e.g. `ngerman`) otherwise.
 #[must_use]
-pub fn render(entry: &bibtex::Entry, semantics: &Semantics) -> Option<String> {
+pub fn render(
+    entry: &ResolvedEntry,
+    style: Option<&Style>,
+    language: Option<&str>,
+) -> Option<String> {
+    let data = EntryData::from_entry(entry);
+    let language = data
+        .text
+        .get(&TextField::LangId)
+        .and_then(|langid| Language::parse(&langid.text))
+        .or_else(|| language.and_then(Language::parse));
+
+    let output = match style {
+        Some(style) => {
+            let language = language.or(style.default_language).unwrap_or_default();
+            processor::render(data, style, &style.locale(language))
+        }
+        None => render_default(data, Locale::new(language.unwrap_or_default()))?,
+    };
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(output.nfc().collect())
+    }
+}
+
+fn render_default(entry: EntryData, locale: Locale) -> Option<String> {
     let mut output = String::new();
-    let mut driver = Driver::default();
-    driver.process(entry, semantics);
+    let mut driver = Driver::new(locale);
+    driver.process(entry);
     driver.finish().for_each(|(inline, punct)| {
         let text = match inline {
             Inline::Regular(text) => text,
@@ -28,7 +71,7 @@ pub fn render(entry: &bibtex::Entry, semantics: &Semantics) -> Option<String> {
         None
     } else {
         output.push('.');
-        Some(output.nfc().collect())
+        Some(output)
     }
 }
 
@@ -0,0 +1,668 @@
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+use crate::xml::Element;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum TermForm {
+    #[default]
+    Long,
+    Short,
+    Verb,
+    VerbShort,
+    Symbol,
+}
+
+impl TermForm {
+    pub fn parse(input: &str) -> Option<Self> {
+        Some(match input {
+            "long" => Self::Long,
+            "short" => Self::Short,
+            "verb" => Self::Verb,
+            "verb-short" => Self::VerbShort,
+            "symbol" => Self::Symbol,
+            _ => return None,
+        })
+    }
+
+    /// The form to try next if a term does not define this form.
+    fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Long => None,
+            Self::Short | Self::Verb => Some(Self::Long),
+            Self::VerbShort => Some(Self::Verb),
+            Self::Symbol => Some(Self::Short),
+        }
+    }
+}
+
+/// The languages that have a built-in locale.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    /// Parses a language name of `babel` or `polyglossia` (`ngerman`) or a language tag (`de-DE`).
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().to_lowercase();
+        let code = input.split(['-', '_']).next().unwrap_or_default();
+        Some(match code {
+            "en" | "english" | "american" | "usenglish" | "british" | "ukenglish" | "canadian"
+            | "australian" | "newzealand" => Self::English,
+            "de" | "german" | "ngerman" | "austrian" | "naustrian" | "swissgerman"
+            | "nswissgerman" => Self::German,
+            "fr" | "french" | "francais" | "frenchb" | "acadian" | "canadien" => Self::French,
+            "es" | "spanish" => Self::Spanish,
+            _ => return None,
+        })
+    }
+
+    fn terms(self) -> &'static [(&'static str, &'static str, &'static str, &'static str)] {
+        match self {
+            Self::English => EN_US,
+            Self::German => DE_DE,
+            Self::French => FR_FR,
+            Self::Spanish => ES_ES,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Term {
+    pub single: String,
+    pub multiple: String,
+}
+
+/// The terms of a language that styles refer to with `<text term="..."/>` and `<label/>`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Locale {
+    language: Language,
+    terms: FxHashMap<(String, TermForm), Term>,
+    punctuation_in_quote: Option<bool>,
+}
+
+impl Locale {
+    /// Creates the built-in locale of a language.
+    pub fn new(language: Language) -> Self {
+        let mut locale = Self {
+            language,
+            punctuation_in_quote: Some(language == Language::English),
+            ..Self::default()
+        };
+
+        for (name, form, single, multiple) in language.terms() {
+            locale.insert(name, TermForm::parse(form).unwrap(), single, multiple);
+        }
+
+        locale
+    }
+
+    /// Reads the terms of a `<locale>` element of a style.
+    pub fn parse(element: &Element) -> Self {
+        let mut locale = Self {
+            punctuation_in_quote: element
+                .element("style-options")
+                .and_then(|options| options.attribute("punctuation-in-quote"))
+                .map(|value| value == "true"),
+            ..Self::default()
+        };
+
+        let terms = element
+            .element("terms")
+            .into_iter()
+            .flat_map(|terms| terms.elements())
+            .filter(|term| term.name == "term");
+
+        for term in terms {
+            let Some(name) = term.attribute("name") else {
+                continue;
+            };
+
+            let form = term
+                .attribute("form")
+                .and_then(TermForm::parse)
+                .unwrap_or_default();
+
+            let (single, multiple) = match (term.element("single"), term.element("multiple")) {
+                (None, None) => (term.text(), term.text()),
+                (single, multiple) => (
+                    single.map(Element::text).unwrap_or_default(),
+                    multiple.map(Element::text).unwrap_or_default(),
+                ),
+            };
+
+            locale.insert(name, form, &single, &multiple);
+        }
+
+        locale
+    }
+
+    fn insert(&mut self, name: &str, form: TermForm, single: &str, multiple: &str) {
+        let term = Term {
+            single: single.into(),
+            multiple: multiple.into(),
+        };
+
+        self.terms.insert((name.into(), form), term);
+    }
+
+    /// Overrides the terms of `self` with the terms of `other`.
+    /// The date formats of `self` are kept.
+    pub fn merge(&mut self, other: &Self) {
+        for (key, term) in &other.terms {
+            self.terms.insert(key.clone(), term.clone());
+        }
+
+        self.punctuation_in_quote = other.punctuation_in_quote.or(self.punctuation_in_quote);
+    }
+
+    /// Whether commas and periods that follow a quotation are moved inside of the quotation marks.
+    pub fn punctuation_in_quote(&self) -> bool {
+        self.punctuation_in_quote.unwrap_or_default()
+    }
+
+    pub fn term(&self, name: &str, form: TermForm, plural: bool) -> Option<&str> {
+        let mut form = Some(form);
+        while let Some(current) = form {
+            if let Some(term) = self.terms.get(&(name.to_string(), current)) {
+                let text = if plural { &term.multiple } else { &term.single };
+                return Some(text.as_str());
+            }
+
+            form = current.fallback();
+        }
+
+        None
+    }
+
+    /// Appends the ordinal suffix, where `ordinal-11` takes precedence over `ordinal-01`.
+    pub fn ordinal(&self, number: u32) -> String {
+        let suffix = [number % 100, number % 10]
+            .into_iter()
+            .find_map(|digits| self.term(&format!("ordinal-{digits:02}"), TermForm::Long, false))
+            .or_else(|| self.term("ordinal", TermForm::Long, false))
+            .unwrap_or_default();
+
+        format!("{number}{suffix}")
+    }
+
+    pub fn long_ordinal(&self, number: u32) -> String {
+        self.term(&format!("long-ordinal-{number:02}"), TermForm::Long, false)
+            .map_or_else(|| self.ordinal(number), String::from)
+    }
+
+    pub fn month(&self, month: u32, form: TermForm) -> Option<&str> {
+        self.term(&format!("month-{month:02}"), form, false)
+    }
+
+    /// Formats a date using the default date format of the locale.
+    pub fn date(
+        &self,
+        year: Option<i32>,
+        month: Option<u32>,
+        day: Option<u32>,
+        numeric: bool,
+    ) -> String {
+        let day = day.filter(|_| month.is_some());
+        if numeric {
+            let (first, second, separator) = match self.language {
+                Language::English => (month, day, "/"),
+                Language::German => (day, month, "."),
+                Language::French | Language::Spanish => (day, month, "/"),
+            };
+
+            return [first, second]
+                .into_iter()
+                .flatten()
+                .map(|part| part.to_string())
+                .chain(year.map(|year| year.to_string()))
+                .join(separator);
+        }
+
+        let month = month.and_then(|month| self.month(month, TermForm::Long));
+        let Some(month) = month else {
+            return year.map(|year| year.to_string()).unwrap_or_default();
+        };
+
+        match (self.language, day, year) {
+            (Language::English, Some(day), Some(year)) => format!("{month} {day}, {year}"),
+            (Language::German, Some(day), Some(year)) => format!("{day}. {month} {year}"),
+            (Language::French, Some(day), Some(year)) => format!("{day} {month} {year}"),
+            (Language::Spanish, Some(day), Some(year)) => format!("{day} de {month} de {year}"),
+            (Language::Spanish, None, Some(year)) => format!("{month} de {year}"),
+            (_, _, Some(year)) => format!("{month} {year}"),
+            (_, _, None) => month.to_string(),
+        }
+    }
+}
+
+const EN_US: &[(&str, &str, &str, &str)] = &[
+    ("accessed", "long", "accessed", "accessed"),
+    ("and", "long", "and", "and"),
+    ("and", "symbol", "&", "&"),
+    ("available at", "long", "available at", "available at"),
+    ("chapter", "long", "chapter", "chapters"),
+    ("chapter", "short", "chap.", "chaps."),
+    ("close-inner-quote", "long", "’", "’"),
+    ("close-quote", "long", "”", "”"),
+    ("edition", "long", "edition", "editions"),
+    ("edition", "short", "ed.", "eds."),
+    ("editor", "long", "editor", "editors"),
+    ("editor", "short", "ed.", "eds."),
+    ("editor", "verb", "edited by", "edited by"),
+    ("editor", "verb-short", "ed. by", "ed. by"),
+    ("et-al", "long", "et al.", "et al."),
+    ("from", "long", "from", "from"),
+    ("in", "long", "in", "in"),
+    ("issue", "long", "issue", "issues"),
+    ("issue", "short", "no.", "nos."),
+    ("no date", "long", "no date", "no date"),
+    ("no date", "short", "n.d.", "n.d."),
+    ("number", "long", "number", "numbers"),
+    ("number", "short", "no.", "nos."),
+    ("number-of-pages", "long", "page", "pages"),
+    ("number-of-pages", "short", "p.", "pp."),
+    ("online", "long", "online", "online"),
+    ("open-inner-quote", "long", "‘", "‘"),
+    ("open-quote", "long", "“", "“"),
+    ("page", "long", "page", "pages"),
+    ("page", "short", "p.", "pp."),
+    ("presented at", "long", "presented at", "presented at"),
+    ("retrieved", "long", "retrieved", "retrieved"),
+    ("translator", "long", "translator", "translators"),
+    ("translator", "short", "trans.", "trans."),
+    ("translator", "verb", "translated by", "translated by"),
+    ("translator", "verb-short", "trans. by", "trans. by"),
+    ("version", "long", "version", "versions"),
+    ("volume", "long", "volume", "volumes"),
+    ("volume", "short", "vol.", "vols."),
+    (
+        "with-afterword",
+        "long",
+        "with an afterw. by",
+        "with an afterw. by",
+    ),
+    (
+        "with-annotator",
+        "long",
+        "with annots. by",
+        "with annots. by",
+    ),
+    (
+        "with-commentator",
+        "long",
+        "with a comment. by",
+        "with a comment. by",
+    ),
+    (
+        "with-foreword",
+        "long",
+        "with a forew. by",
+        "with a forew. by",
+    ),
+    (
+        "with-introduction",
+        "long",
+        "with an intro. by",
+        "with an intro. by",
+    ),
+    ("ordinal", "long", "th", "th"),
+    ("ordinal-01", "long", "st", "st"),
+    ("ordinal-02", "long", "nd", "nd"),
+    ("ordinal-03", "long", "rd", "rd"),
+    ("ordinal-11", "long", "th", "th"),
+    ("ordinal-12", "long", "th", "th"),
+    ("ordinal-13", "long", "th", "th"),
+    ("long-ordinal-01", "long", "first", "first"),
+    ("long-ordinal-02", "long", "second", "second"),
+    ("long-ordinal-03", "long", "third", "third"),
+    ("long-ordinal-04", "long", "fourth", "fourth"),
+    ("long-ordinal-05", "long", "fifth", "fifth"),
+    ("long-ordinal-06", "long", "sixth", "sixth"),
+    ("long-ordinal-07", "long", "seventh", "seventh"),
+    ("long-ordinal-08", "long", "eighth", "eighth"),
+    ("long-ordinal-09", "long", "ninth", "ninth"),
+    ("long-ordinal-10", "long", "tenth", "tenth"),
+    ("month-01", "long", "January", "January"),
+    ("month-02", "long", "February", "February"),
+    ("month-03", "long", "March", "March"),
+    ("month-04", "long", "April", "April"),
+    ("month-05", "long", "May", "May"),
+    ("month-06", "long", "June", "June"),
+    ("month-07", "long", "July", "July"),
+    ("month-08", "long", "August", "August"),
+    ("month-09", "long", "September", "September"),
+    ("month-10", "long", "October", "October"),
+    ("month-11", "long", "November", "November"),
+    ("month-12", "long", "December", "December"),
+    ("month-01", "short", "Jan.", "Jan."),
+    ("month-02", "short", "Feb.", "Feb."),
+    ("month-03", "short", "Mar.", "Mar."),
+    ("month-04", "short", "Apr.", "Apr."),
+    ("month-05", "short", "May", "May"),
+    ("month-06", "short", "June", "June"),
+    ("month-07", "short", "July", "July"),
+    ("month-08", "short", "Aug.", "Aug."),
+    ("month-09", "short", "Sept.", "Sept."),
+    ("month-10", "short", "Oct.", "Oct."),
+    ("month-11", "short", "Nov.", "Nov."),
+    ("month-12", "short", "Dec.", "Dec."),
+];
+
+const DE_DE: &[(&str, &str, &str, &str)] = &[
+    ("accessed", "long", "zugegriffen", "zugegriffen"),
+    ("and", "long", "und", "und"),
+    ("and", "symbol", "&", "&"),
+    ("available at", "long", "verfügbar unter", "verfügbar unter"),
+    ("chapter", "long", "Kapitel", "Kapitel"),
+    ("chapter", "short", "Kap.", "Kap."),
+    ("close-inner-quote", "long", "‘", "‘"),
+    ("close-quote", "long", "“", "“"),
+    ("edition", "long", "Auflage", "Auflagen"),
+    ("edition", "short", "Aufl.", "Aufl."),
+    ("editor", "long", "Herausgeber", "Herausgeber"),
+    ("editor", "short", "Hrsg.", "Hrsg."),
+    ("editor", "verb", "herausgegeben von", "herausgegeben von"),
+    ("editor", "verb-short", "hrsg. von", "hrsg. von"),
+    ("et-al", "long", "u. a.", "u. a."),
+    ("from", "long", "von", "von"),
+    ("in", "long", "in", "in"),
+    ("issue", "long", "Ausgabe", "Ausgaben"),
+    ("issue", "short", "Nr.", "Nr."),
+    ("no date", "long", "ohne Datum", "ohne Datum"),
+    ("no date", "short", "o. J.", "o. J."),
+    ("number", "long", "Nummer", "Nummern"),
+    ("number", "short", "Nr.", "Nr."),
+    ("number-of-pages", "long", "Seite", "Seiten"),
+    ("number-of-pages", "short", "S.", "S."),
+    ("online", "long", "online", "online"),
+    ("open-inner-quote", "long", "‚", "‚"),
+    ("open-quote", "long", "„", "„"),
+    ("page", "long", "Seite", "Seiten"),
+    ("page", "short", "S.", "S."),
+    ("presented at", "long", "präsentiert bei", "präsentiert bei"),
+    ("retrieved", "long", "abgerufen", "abgerufen"),
+    ("translator", "long", "Übersetzer", "Übersetzer"),
+    ("translator", "short", "Übers.", "Übers."),
+    ("translator", "verb", "übersetzt von", "übersetzt von"),
+    ("translator", "verb-short", "übers. von", "übers. von"),
+    ("version", "long", "Version", "Versionen"),
+    ("volume", "long", "Band", "Bände"),
+    ("volume", "short", "Bd.", "Bde."),
+    (
+        "with-afterword",
+        "long",
+        "mit einem Nachw. von",
+        "mit einem Nachw. von",
+    ),
+    ("with-annotator", "long", "mit Anm. von", "mit Anm. von"),
+    (
+        "with-commentator",
+        "long",
+        "mit einem Komm. von",
+        "mit einem Komm. von",
+    ),
+    (
+        "with-foreword",
+        "long",
+        "mit einem Vorw. von",
+        "mit einem Vorw. von",
+    ),
+    (
+        "with-introduction",
+        "long",
+        "mit einer Einl. von",
+        "mit einer Einl. von",
+    ),
+    ("ordinal", "long", ".", "."),
+    ("long-ordinal-01", "long", "erste", "erste"),
+    ("long-ordinal-02", "long", "zweite", "zweite"),
+    ("long-ordinal-03", "long", "dritte", "dritte"),
+    ("long-ordinal-04", "long", "vierte", "vierte"),
+    ("long-ordinal-05", "long", "fünfte", "fünfte"),
+    ("long-ordinal-06", "long", "sechste", "sechste"),
+    ("long-ordinal-07", "long", "siebte", "siebte"),
+    ("long-ordinal-08", "long", "achte", "achte"),
+    ("long-ordinal-09", "long", "neunte", "neunte"),
+    ("long-ordinal-10", "long", "zehnte", "zehnte"),
+    ("month-01", "long", "Januar", "Januar"),
+    ("month-02", "long", "Februar", "Februar"),
+    ("month-03", "long", "März", "März"),
+    ("month-04", "long", "April", "April"),
+    ("month-05", "long", "Mai", "Mai"),
+    ("month-06", "long", "Juni", "Juni"),
+    ("month-07", "long", "Juli", "Juli"),
+    ("month-08", "long", "August", "August"),
+    ("month-09", "long", "September", "September"),
+    ("month-10", "long", "Oktober", "Oktober"),
+    ("month-11", "long", "November", "November"),
+    ("month-12", "long", "Dezember", "Dezember"),
+    ("month-01", "short", "Jan.", "Jan."),
+    ("month-02", "short", "Feb.", "Feb."),
+    ("month-03", "short", "März", "März"),
+    ("month-04", "short", "Apr.", "Apr."),
+    ("month-05", "short", "Mai", "Mai"),
+    ("month-06", "short", "Juni", "Juni"),
+    ("month-07", "short", "Juli", "Juli"),
+    ("month-08", "short", "Aug.", "Aug."),
+    ("month-09", "short", "Sep.", "Sep."),
+    ("month-10", "short", "Okt.", "Okt."),
+    ("month-11", "short", "Nov.", "Nov."),
+    ("month-12", "short", "Dez.", "Dez."),
+];
+
+const FR_FR: &[(&str, &str, &str, &str)] = &[
+    ("accessed", "long", "consulté le", "consulté le"),
+    ("and", "long", "et", "et"),
+    ("and", "symbol", "&", "&"),
+    ("available at", "long", "disponible sur", "disponible sur"),
+    ("chapter", "long", "chapitre", "chapitres"),
+    ("chapter", "short", "chap.", "chap."),
+    ("close-inner-quote", "long", "”", "”"),
+    ("close-quote", "long", "\u{a0}»", "\u{a0}»"),
+    ("edition", "long", "édition", "éditions"),
+    ("edition", "short", "éd.", "éd."),
+    ("editor", "long", "éditeur", "éditeurs"),
+    ("editor", "short", "éd.", "éd."),
+    ("editor", "verb", "édité par", "édité par"),
+    ("editor", "verb-short", "éd. par", "éd. par"),
+    ("et-al", "long", "et al.", "et al."),
+    ("from", "long", "à partir de", "à partir de"),
+    ("in", "long", "in", "in"),
+    ("issue", "long", "numéro", "numéros"),
+    ("issue", "short", "n°", "n°"),
+    ("no date", "long", "sans date", "sans date"),
+    ("no date", "short", "s. d.", "s. d."),
+    ("number", "long", "numéro", "numéros"),
+    ("number", "short", "n°", "n°"),
+    ("number-of-pages", "long", "page", "pages"),
+    ("number-of-pages", "short", "p.", "p."),
+    ("online", "long", "en ligne", "en ligne"),
+    ("open-inner-quote", "long", "“", "“"),
+    ("open-quote", "long", "«\u{a0}", "«\u{a0}"),
+    ("page", "long", "page", "pages"),
+    ("page", "short", "p.", "p."),
+    ("presented at", "long", "présenté à", "présenté à"),
+    ("retrieved", "long", "consulté", "consulté"),
+    ("translator", "long", "traducteur", "traducteurs"),
+    ("translator", "short", "trad.", "trad."),
+    ("translator", "verb", "traduit par", "traduit par"),
+    ("translator", "verb-short", "trad. par", "trad. par"),
+    ("version", "long", "version", "versions"),
+    ("volume", "long", "volume", "volumes"),
+    ("volume", "short", "vol.", "vol."),
+    (
+        "with-afterword",
+        "long",
+        "avec une postf. de",
+        "avec une postf. de",
+    ),
+    (
+        "with-annotator",
+        "long",
+        "avec des annot. de",
+        "avec des annot. de",
+    ),
+    (
+        "with-commentator",
+        "long",
+        "avec un comment. de",
+        "avec un comment. de",
+    ),
+    (
+        "with-foreword",
+        "long",
+        "avec une préf. de",
+        "avec une préf. de",
+    ),
+    (
+        "with-introduction",
+        "long",
+        "avec une intro. de",
+        "avec une intro. de",
+    ),
+    ("ordinal", "long", "e", "e"),
+    ("ordinal-01", "long", "er", "er"),
+    ("ordinal-11", "long", "e", "e"),
+    ("long-ordinal-01", "long", "premier", "premier"),
+    ("long-ordinal-02", "long", "deuxième", "deuxième"),
+    ("long-ordinal-03", "long", "troisième", "troisième"),
+    ("long-ordinal-04", "long", "quatrième", "quatrième"),
+    ("long-ordinal-05", "long", "cinquième", "cinquième"),
+    ("long-ordinal-06", "long", "sixième", "sixième"),
+    ("long-ordinal-07", "long", "septième", "septième"),
+    ("long-ordinal-08", "long", "huitième", "huitième"),
+    ("long-ordinal-09", "long", "neuvième", "neuvième"),
+    ("long-ordinal-10", "long", "dixième", "dixième"),
+    ("month-01", "long", "janvier", "janvier"),
+    ("month-02", "long", "février", "février"),
+    ("month-03", "long", "mars", "mars"),
+    ("month-04", "long", "avril", "avril"),
+    ("month-05", "long", "mai", "mai"),
+    ("month-06", "long", "juin", "juin"),
+    ("month-07", "long", "juillet", "juillet"),
+    ("month-08", "long", "août", "août"),
+    ("month-09", "long", "septembre", "septembre"),
+    ("month-10", "long", "octobre", "octobre"),
+    ("month-11", "long", "novembre", "novembre"),
+    ("month-12", "long", "décembre", "décembre"),
+    ("month-01", "short", "janv.", "janv."),
+    ("month-02", "short", "févr.", "févr."),
+    ("month-03", "short", "mars", "mars"),
+    ("month-04", "short", "avr.", "avr."),
+    ("month-05", "short", "mai", "mai"),
+    ("month-06", "short", "juin", "juin"),
+    ("month-07", "short", "juill.", "juill."),
+    ("month-08", "short", "août", "août"),
+    ("month-09", "short", "sept.", "sept."),
+    ("month-10", "short", "oct.", "oct."),
+    ("month-11", "short", "nov.", "nov."),
+    ("month-12", "short", "déc.", "déc."),
+];
+
+const ES_ES: &[(&str, &str, &str, &str)] = &[
+    ("accessed", "long", "accedido", "accedido"),
+    ("and", "long", "y", "y"),
+    ("and", "symbol", "&", "&"),
+    ("available at", "long", "disponible en", "disponible en"),
+    ("chapter", "long", "capítulo", "capítulos"),
+    ("chapter", "short", "cap.", "caps."),
+    ("close-inner-quote", "long", "”", "”"),
+    ("close-quote", "long", "»", "»"),
+    ("edition", "long", "edición", "ediciones"),
+    ("edition", "short", "ed.", "eds."),
+    ("editor", "long", "editor", "editores"),
+    ("editor", "short", "ed.", "eds."),
+    ("editor", "verb", "editado por", "editado por"),
+    ("editor", "verb-short", "ed. por", "ed. por"),
+    ("et-al", "long", "et al.", "et al."),
+    ("from", "long", "a partir de", "a partir de"),
+    ("in", "long", "en", "en"),
+    ("issue", "long", "número", "números"),
+    ("issue", "short", "n.º", "n.os"),
+    ("no date", "long", "sin fecha", "sin fecha"),
+    ("no date", "short", "s. f.", "s. f."),
+    ("number", "long", "número", "números"),
+    ("number", "short", "n.º", "n.os"),
+    ("number-of-pages", "long", "página", "páginas"),
+    ("number-of-pages", "short", "p.", "pp."),
+    ("online", "long", "en línea", "en línea"),
+    ("open-inner-quote", "long", "“", "“"),
+    ("open-quote", "long", "«", "«"),
+    ("page", "long", "página", "páginas"),
+    ("page", "short", "p.", "pp."),
+    ("presented at", "long", "presentado en", "presentado en"),
+    ("retrieved", "long", "recuperado", "recuperado"),
+    ("translator", "long", "traductor", "traductores"),
+    ("translator", "short", "trad.", "trads."),
+    ("translator", "verb", "traducido por", "traducido por"),
+    ("translator", "verb-short", "trad. por", "trad. por"),
+    ("version", "long", "versión", "versiones"),
+    ("volume", "long", "volumen", "volúmenes"),
+    ("volume", "short", "vol.", "vols."),
+    (
+        "with-afterword",
+        "long",
+        "con un epíl. de",
+        "con un epíl. de",
+    ),
+    ("with-annotator", "long", "con notas de", "con notas de"),
+    (
+        "with-commentator",
+        "long",
+        "con un coment. de",
+        "con un coment. de",
+    ),
+    (
+        "with-foreword",
+        "long",
+        "con un pról. de",
+        "con un pról. de",
+    ),
+    (
+        "with-introduction",
+        "long",
+        "con una intr. de",
+        "con una intr. de",
+    ),
+    ("ordinal", "long", ".º", ".º"),
+    ("long-ordinal-01", "long", "primero", "primero"),
+    ("long-ordinal-02", "long", "segundo", "segundo"),
+    ("long-ordinal-03", "long", "tercero", "tercero"),
+    ("long-ordinal-04", "long", "cuarto", "cuarto"),
+    ("long-ordinal-05", "long", "quinto", "quinto"),
+    ("long-ordinal-06", "long", "sexto", "sexto"),
+    ("long-ordinal-07", "long", "séptimo", "séptimo"),
+    ("long-ordinal-08", "long", "octavo", "octavo"),
+    ("long-ordinal-09", "long", "noveno", "noveno"),
+    ("long-ordinal-10", "long", "décimo", "décimo"),
+    ("month-01", "long", "enero", "enero"),
+    ("month-02", "long", "febrero", "febrero"),
+    ("month-03", "long", "marzo", "marzo"),
+    ("month-04", "long", "abril", "abril"),
+    ("month-05", "long", "mayo", "mayo"),
+    ("month-06", "long", "junio", "junio"),
+    ("month-07", "long", "julio", "julio"),
+    ("month-08", "long", "agosto", "agosto"),
+    ("month-09", "long", "septiembre", "septiembre"),
+    ("month-10", "long", "octubre", "octubre"),
+    ("month-11", "long", "noviembre", "noviembre"),
+    ("month-12", "long", "diciembre", "diciembre"),
+    ("month-01", "short", "ene.", "ene."),
+    ("month-02", "short", "feb.", "feb."),
+    ("month-03", "short", "mar.", "mar."),
+    ("month-04", "short", "abr.", "abr."),
+    ("month-05", "short", "may", "may"),
+    ("month-06", "short", "jun.", "jun."),
+    ("month-07", "short", "jul.", "jul."),
+    ("month-08", "short", "ago.", "ago."),
+    ("month-09", "short", "sep.", "sep."),
+    ("month-10", "short", "oct.", "oct."),
+    ("month-11", "short", "nov.", "nov."),
+    ("month-12", "short", "dic.", "dic."),
+];
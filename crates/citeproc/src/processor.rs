@@ -0,0 +1,882 @@
+use std::cell::{Cell, RefCell};
+
+use bibtex_utils::field::{
+    author::AuthorField,
+    date::{DateField, DateFieldData},
+    number::{NumberField, NumberFieldData},
+    text::TextField,
+};
+use human_name::Name;
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use titlecase::titlecase;
+
+use crate::{
+    entry::{EntryData, EntryKind},
+    locale::{Locale, TermForm},
+    style::{
+        Condition, Date, DateForm, DateLimit, DatePartForm, DatePartKind, Formatting, Label,
+        MatchKind, NameOptions, Names, NumberForm, Plural, Precedes, Rendering, SortOrder, Style,
+        Test, TextCase, TextSource,
+    },
+};
+
+/// The maximum nesting depth of macros, which protects against recursive macros.
+const MAX_DEPTH: usize = 32;
+
+/// Renders an entry with the bibliography layout of a CSL style.
+pub fn render(entry: EntryData, style: &Style, locale: &Locale) -> String {
+    let variables = Variables::new(entry);
+    let processor = Processor {
+        style,
+        locale,
+        variables: &variables,
+        called: Cell::new(0),
+        found: RefCell::default(),
+        suppressed: RefCell::default(),
+        depth: Cell::new(0),
+    };
+
+    let layout = &style.layout;
+    let text = processor.render_children(&layout.children, "");
+    processor
+        .format(text, &layout.formatting)
+        .trim()
+        .to_string()
+}
+
+#[derive(Debug, Default)]
+struct DateValue {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    literal: Option<String>,
+}
+
+impl DateValue {
+    fn parse(data: DateFieldData) -> Self {
+        match data {
+            DateFieldData::Date(date) => {
+                use chrono::Datelike;
+                Self {
+                    year: Some(date.year()),
+                    month: Some(date.month()),
+                    day: Some(date.day()),
+                    literal: None,
+                }
+            }
+            DateFieldData::Year(year) => Self {
+                year: Some(year),
+                ..Self::default()
+            },
+            DateFieldData::Month(month) => Self {
+                month: Some(month.number_from_month()),
+                ..Self::default()
+            },
+            DateFieldData::Other(text) => {
+                let mut parts = text.splitn(3, '-').map(str::parse::<u32>);
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(Ok(year)), Some(Ok(month @ 1..=12)), None) => Self {
+                        year: i32::try_from(year).ok(),
+                        month: Some(month),
+                        ..Self::default()
+                    },
+                    _ => Self {
+                        literal: Some(text),
+                        ..Self::default()
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// The fields of an entry, named after the variables of CSL.
+#[derive(Debug, Default)]
+struct Variables {
+    kind: &'static str,
+    text: FxHashMap<&'static str, String>,
+    numbers: FxHashMap<&'static str, NumberFieldData>,
+    names: FxHashMap<&'static str, Vec<Name>>,
+    dates: FxHashMap<&'static str, DateValue>,
+}
+
+impl Variables {
+    fn new(mut entry: EntryData) -> Self {
+        let mut variables = Self {
+            kind: csl_type(entry.kind),
+            ..Self::default()
+        };
+
+        let mut take = |fields: &[TextField]| {
+            fields
+                .iter()
+                .find_map(|field| entry.text.remove(field))
+                .map(|data| data.text)
+        };
+
+        let title = take(&[TextField::Title]);
+        let subtitle = take(&[TextField::Subtitle]);
+        let container = match entry.kind {
+            EntryKind::Article | EntryKind::Periodical => {
+                take(&[TextField::JournalTitle, TextField::Journal])
+            }
+            _ => take(&[TextField::BookTitle, TextField::MainTitle]),
+        };
+
+        let container_subtitle = take(&[TextField::JournalSubtitle, TextField::BookSubtitle]);
+        let genre = take(&[TextField::Type]).or_else(|| {
+            Some(String::from(match entry.kind {
+                EntryKind::PhdThesis => "PhD thesis",
+                EntryKind::MasterThesis => "Master's thesis",
+                EntryKind::TechReport => "Technical report",
+                _ => return None,
+            }))
+        });
+
+        let fields = [
+            (
+                "publisher",
+                take(&[
+                    TextField::Publisher,
+                    TextField::Institution,
+                    TextField::School,
+                    TextField::Organization,
+                    TextField::HowPublished,
+                ]),
+            ),
+            (
+                "publisher-place",
+                take(&[TextField::Location, TextField::Address]),
+            ),
+            ("collection-title", take(&[TextField::Series])),
+            ("event", take(&[TextField::EventTitle])),
+            ("event-place", take(&[TextField::Venue])),
+            ("note", take(&[TextField::Note])),
+            ("abstract", take(&[TextField::Abstract])),
+            ("DOI", take(&[TextField::Doi])),
+            ("URL", take(&[TextField::Url])),
+            ("ISBN", take(&[TextField::Isbn])),
+            ("ISSN", take(&[TextField::Issn])),
+            ("language", take(&[TextField::Language])),
+            ("version", take(&[TextField::Version])),
+            ("status", take(&[TextField::Pubstate])),
+            ("chapter-number", take(&[TextField::Chapter])),
+            ("issue", take(&[TextField::Issue])),
+            ("genre", genre),
+            ("title-short", title.clone()),
+            ("title", join_subtitle(title, subtitle)),
+            ("container-title-short", container.clone()),
+            (
+                "container-title",
+                join_subtitle(container, container_subtitle),
+            ),
+        ];
+
+        for (name, value) in fields {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                variables.text.insert(name, value);
+            }
+        }
+
+        let is_article = matches!(entry.kind, EntryKind::Article | EntryKind::Periodical);
+        let numbers = [
+            (NumberField::Volume, "volume"),
+            (NumberField::Pages, "page"),
+            (NumberField::Edition, "edition"),
+            (NumberField::Volumes, "number-of-volumes"),
+            (NumberField::PageTotal, "number-of-pages"),
+            (
+                NumberField::Number,
+                if is_article { "issue" } else { "number" },
+            ),
+        ];
+
+        for (field, name) in numbers {
+            if let Some(data) = entry.number.remove(&field) {
+                variables.text.remove(name);
+                variables.numbers.insert(name, data);
+            }
+        }
+
+        for (field, name) in [
+            (AuthorField::Author, "author"),
+            (AuthorField::Editor, "editor"),
+            (AuthorField::Translator, "translator"),
+        ] {
+            if let Some(data) = entry.author.remove(&field) {
+                variables.names.insert(name, data.authors);
+            }
+        }
+
+        let mut issued = entry.date.remove(&DateField::Date).map(DateValue::parse);
+        if let Some(DateFieldData::Year(year)) = entry.date.remove(&DateField::Year) {
+            issued
+                .get_or_insert_with(DateValue::default)
+                .year
+                .get_or_insert(year);
+        }
+
+        if let Some(DateFieldData::Month(month)) = entry.date.remove(&DateField::Month) {
+            issued
+                .get_or_insert_with(DateValue::default)
+                .month
+                .get_or_insert(month.number_from_month());
+        }
+
+        let dates = [
+            ("issued", issued),
+            (
+                "accessed",
+                entry.date.remove(&DateField::UrlDate).map(DateValue::parse),
+            ),
+            (
+                "event-date",
+                entry
+                    .date
+                    .remove(&DateField::EventDate)
+                    .map(DateValue::parse),
+            ),
+        ];
+
+        for (name, value) in dates {
+            if let Some(value) = value {
+                variables.dates.insert(name, value);
+            }
+        }
+
+        variables
+    }
+
+    fn text(&self, name: &str, short: bool) -> Option<String> {
+        let short_name = format!("{name}-short");
+        let name = if short && self.text.contains_key(short_name.as_str()) {
+            short_name.as_str()
+        } else {
+            name
+        };
+
+        self.text
+            .get(name)
+            .cloned()
+            .or_else(|| self.numbers.get(name).map(number_text))
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.text.contains_key(name)
+            || self.numbers.contains_key(name)
+            || self.names.get(name).is_some_and(|names| !names.is_empty())
+            || self.dates.contains_key(name)
+    }
+
+    fn is_numeric(&self, name: &str) -> bool {
+        match self.numbers.get(name) {
+            Some(NumberFieldData::Scalar(_) | NumberFieldData::Range(_, _)) => true,
+            Some(NumberFieldData::Other(text)) => is_numeric(text),
+            None => self.text.get(name).is_some_and(|text| is_numeric(text)),
+        }
+    }
+
+    fn is_plural(&self, name: &str) -> bool {
+        match self.numbers.get(name) {
+            Some(NumberFieldData::Range(_, _)) => true,
+            Some(NumberFieldData::Scalar(_)) => false,
+            Some(NumberFieldData::Other(text)) => text.contains(['-', '–', ',', '&']),
+            None => self.names.get(name).is_some_and(|names| names.len() > 1),
+        }
+    }
+}
+
+fn csl_type(kind: EntryKind) -> &'static str {
+    match kind {
+        EntryKind::Article => "article-journal",
+        EntryKind::Book
+        | EntryKind::MVBook
+        | EntryKind::Collection
+        | EntryKind::MVCollection
+        | EntryKind::Proceedings
+        | EntryKind::MVProceedings
+        | EntryKind::Reference
+        | EntryKind::MVReference
+        | EntryKind::Manual => "book",
+        EntryKind::InBook
+        | EntryKind::BookInBook
+        | EntryKind::SuppBook
+        | EntryKind::InCollection
+        | EntryKind::SuppCollection => "chapter",
+        EntryKind::InReference => "entry-encyclopedia",
+        EntryKind::Booklet => "pamphlet",
+        EntryKind::DataSet => "dataset",
+        EntryKind::Online | EntryKind::Electronic | EntryKind::Www => "webpage",
+        EntryKind::Patent => "patent",
+        EntryKind::Periodical | EntryKind::SuppPeriodical => "periodical",
+        EntryKind::InProceedings | EntryKind::Conference => "paper-conference",
+        EntryKind::Report | EntryKind::TechReport => "report",
+        EntryKind::Software => "software",
+        EntryKind::Thesis | EntryKind::MasterThesis | EntryKind::PhdThesis => "thesis",
+        EntryKind::Misc | EntryKind::Set | EntryKind::Unknown => "article",
+    }
+}
+
+fn join_subtitle(title: Option<String>, subtitle: Option<String>) -> Option<String> {
+    match (title, subtitle) {
+        (Some(title), Some(subtitle)) => Some(format!("{title}: {subtitle}")),
+        (title, _) => title,
+    }
+}
+
+fn number_text(data: &NumberFieldData) -> String {
+    match data {
+        NumberFieldData::Scalar(value) => value.to_string(),
+        NumberFieldData::Range(start, end) => format!("{start}–{end}"),
+        NumberFieldData::Other(text) => text.replace("--", "–"),
+    }
+}
+
+fn is_numeric(text: &str) -> bool {
+    text.chars().any(|c| c.is_ascii_digit())
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '–' | ',' | '&' | ' '))
+}
+
+struct Processor<'a> {
+    style: &'a Style,
+    locale: &'a Locale,
+    variables: &'a Variables,
+    /// The number of variables that the style has tried to render so far.
+    called: Cell<usize>,
+    /// The variables that were not empty.
+    found: RefCell<Vec<String>>,
+    /// The variables that have been used as a substitute for names and must not be repeated.
+    suppressed: RefCell<FxHashSet<String>>,
+    depth: Cell<usize>,
+}
+
+impl Processor<'_> {
+    fn render_children(&self, children: &[Rendering], delimiter: &str) -> String {
+        let mut output = Vec::new();
+        self.collect(children, &mut output);
+        self.join(output, delimiter)
+    }
+
+    /// Renders the children of an element. The children of the matching branch of a
+    /// `<choose>` element are delimited as if they were children of the parent.
+    fn collect(&self, children: &[Rendering], output: &mut Vec<String>) {
+        for child in children {
+            match child {
+                Rendering::Choose { branches } => {
+                    if let Some((_, children)) = branches
+                        .iter()
+                        .find(|(condition, _)| self.evaluate(condition))
+                    {
+                        self.collect(children, output);
+                    }
+                }
+                _ => output.push(self.render(child)),
+            }
+        }
+    }
+
+    fn join(&self, items: impl IntoIterator<Item = String>, delimiter: &str) -> String {
+        items
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .fold(String::new(), |output, text| {
+                if output.is_empty() {
+                    text
+                } else {
+                    self.concat(self.concat(output, delimiter), &text)
+                }
+            })
+    }
+
+    fn render(&self, element: &Rendering) -> String {
+        match element {
+            Rendering::Text { source, formatting } => {
+                let text = match source {
+                    TextSource::Variable { name, short } => {
+                        self.lookup(name, |variables| variables.text(name, *short))
+                    }
+                    TextSource::Macro(name) => self.render_macro(name),
+                    TextSource::Term { name, form, plural } => self
+                        .locale
+                        .term(name, *form, *plural)
+                        .unwrap_or_default()
+                        .to_string(),
+                    TextSource::Value(value) => value.clone(),
+                };
+
+                self.format(text, formatting)
+            }
+            Rendering::Number {
+                variable,
+                form,
+                formatting,
+            } => {
+                let text = self.lookup(variable, |variables| {
+                    match (variables.numbers.get(variable.as_str())?, form) {
+                        (NumberFieldData::Scalar(value), NumberForm::Ordinal) => {
+                            Some(self.locale.ordinal(*value))
+                        }
+                        (NumberFieldData::Scalar(value), NumberForm::LongOrdinal) => {
+                            Some(self.locale.long_ordinal(*value))
+                        }
+                        (NumberFieldData::Scalar(value), NumberForm::Roman) => Some(roman(*value)),
+                        (data, _) => Some(number_text(data)),
+                    }
+                    .or_else(|| variables.text(variable, false))
+                });
+
+                self.format(text, formatting)
+            }
+            Rendering::Label(label) => self.render_label(label, &label.variable),
+            Rendering::Date(date) => self.render_date(date),
+            Rendering::Names(names) => self.render_names(names, None),
+            Rendering::Group {
+                children,
+                delimiter,
+                formatting,
+            } => {
+                let called = self.called.get();
+                let found = self.found.borrow().len();
+                let text = self.render_children(children, delimiter);
+                if self.called.get() > called && self.found.borrow().len() == found {
+                    return String::new();
+                }
+
+                self.format(text, formatting)
+            }
+            Rendering::Choose { .. } => self.render_children(std::slice::from_ref(element), ""),
+        }
+    }
+
+    fn render_macro(&self, name: &str) -> String {
+        let Some(children) = self.style.macros.get(name) else {
+            return String::new();
+        };
+
+        if self.depth.get() >= MAX_DEPTH {
+            return String::new();
+        }
+
+        self.depth.set(self.depth.get() + 1);
+        let text = self.render_children(children, "");
+        self.depth.set(self.depth.get() - 1);
+        text
+    }
+
+    /// Looks up a variable and keeps track of the result for the group suppression.
+    fn lookup<T: Default>(&self, name: &str, f: impl FnOnce(&Variables) -> Option<T>) -> T {
+        self.called.set(self.called.get() + 1);
+        if self.suppressed.borrow().contains(name) {
+            return T::default();
+        }
+
+        match f(self.variables) {
+            Some(value) => {
+                self.found.borrow_mut().push(name.to_string());
+                value
+            }
+            None => T::default(),
+        }
+    }
+
+    fn evaluate(&self, condition: &Condition) -> bool {
+        let mut results = condition.tests.iter().map(|test| match test {
+            Test::Type(kind) => self.variables.kind == kind,
+            Test::Variable(name) => {
+                self.variables.has(name) && !self.suppressed.borrow().contains(name)
+            }
+            Test::IsNumeric(name) => self.variables.is_numeric(name),
+            Test::Unsupported => false,
+        });
+
+        match condition.kind {
+            MatchKind::All => results.all(|result| result),
+            MatchKind::Any => results.any(|result| result),
+            MatchKind::None => !results.any(|result| result),
+        }
+    }
+
+    fn render_label(&self, label: &Label, variable: &str) -> String {
+        if !self.variables.has(variable) || self.suppressed.borrow().contains(variable) {
+            return String::new();
+        }
+
+        let plural = match label.plural {
+            Plural::Contextual => self.variables.is_plural(variable),
+            Plural::Always => true,
+            Plural::Never => false,
+        };
+
+        let name = match variable {
+            "page" | "number-of-pages" => variable,
+            _ => variable.trim_end_matches("-number"),
+        };
+
+        let term = self
+            .locale
+            .term(name, label.form, plural)
+            .unwrap_or_default();
+        self.format(term.to_string(), &label.formatting)
+    }
+
+    fn render_date(&self, date: &Date) -> String {
+        let text = self.lookup(&date.variable, |variables| {
+            let value = variables.dates.get(date.variable.as_str())?;
+            if let Some(literal) = &value.literal {
+                return Some(literal.clone());
+            }
+
+            let month = value.month.filter(|_| date.limit >= DateLimit::YearMonth);
+            let day = value
+                .day
+                .filter(|_| date.limit >= DateLimit::YearMonthDay && month.is_some());
+
+            let text = match date.localized {
+                Some(form) => self
+                    .locale
+                    .date(value.year, month, day, form == DateForm::Numeric),
+                None => {
+                    let parts = date.parts.iter().filter_map(|part| {
+                        let text = match part.kind {
+                            DatePartKind::Year => value.year.map(|year| match part.form {
+                                DatePartForm::Short => format!("{:02}", year % 100),
+                                _ => year.to_string(),
+                            }),
+                            DatePartKind::Month => month.and_then(|month| {
+                                Some(match part.form {
+                                    DatePartForm::Long => {
+                                        self.locale.month(month, TermForm::Long)?.to_string()
+                                    }
+                                    DatePartForm::Short => {
+                                        self.locale.month(month, TermForm::Short)?.to_string()
+                                    }
+                                    DatePartForm::NumericLeadingZeros => format!("{month:02}"),
+                                    DatePartForm::Numeric | DatePartForm::Ordinal => {
+                                        month.to_string()
+                                    }
+                                })
+                            }),
+                            DatePartKind::Day => day.map(|day| match part.form {
+                                DatePartForm::Ordinal => self.locale.ordinal(day),
+                                DatePartForm::NumericLeadingZeros => format!("{day:02}"),
+                                _ => day.to_string(),
+                            }),
+                        }?;
+
+                        Some(self.format(text, &part.formatting))
+                    });
+
+                    self.join(parts, &date.delimiter)
+                }
+            };
+
+            Some(text).filter(|text| !text.is_empty())
+        });
+
+        self.format(text, &date.formatting)
+    }
+
+    /// Renders a `<names>` element. A `<names>` element within `<substitute>` uses the
+    /// name options and label of its parent if it does not have its own.
+    fn render_names(
+        &self,
+        names: &Names,
+        parent: Option<(&NameOptions, Option<&Label>)>,
+    ) -> String {
+        let inherited = &self.style.layout.names;
+        let options = match (&names.name, parent) {
+            (Some(name), _) => name.inherit(inherited),
+            (None, Some((options, _))) => options.clone(),
+            (None, None) => NameOptions::default().inherit(inherited),
+        };
+
+        let label = names.label.as_ref().or(parent.and_then(|(_, label)| label));
+        let text = names
+            .variables
+            .iter()
+            .map(|variable| {
+                let list = self.lookup(variable, |variables| {
+                    variables
+                        .names
+                        .get(variable.as_str())
+                        .filter(|list| !list.is_empty())
+                        .map(|list| self.render_name_list(list, &options))
+                });
+
+                let Some(label) = label.filter(|_| !list.is_empty()) else {
+                    return list;
+                };
+
+                let label = self.render_label(label, variable);
+                if names.label_first {
+                    self.concat(label, &list)
+                } else {
+                    self.concat(list, &label)
+                }
+            })
+            .filter(|text| !text.is_empty())
+            .join(&names.delimiter);
+
+        if !text.is_empty() {
+            return self.format(text, &names.formatting);
+        }
+
+        for child in &names.substitute {
+            let found = self.found.borrow().len();
+            let text = match child {
+                Rendering::Names(child) => self.render_names(child, Some((&options, label))),
+                _ => self.render(child),
+            };
+
+            if !text.is_empty() {
+                let found = self.found.borrow()[found..].to_vec();
+                self.suppressed.borrow_mut().extend(found);
+                return self.format(text, &names.formatting);
+            }
+        }
+
+        String::new()
+    }
+
+    fn render_name_list(&self, names: &[Name], options: &NameOptions) -> String {
+        let rendered: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let inverted = match options.name_as_sort_order {
+                    Some(SortOrder::All) => true,
+                    Some(SortOrder::First) => index == 0,
+                    None => false,
+                };
+
+                (self.render_name(name, inverted, options), inverted)
+            })
+            .collect();
+
+        let count = rendered.len();
+        let delimiter = options.delimiter.as_deref().unwrap_or(", ");
+        let precedes = |precedes: Option<Precedes>, shown: usize, inverted: bool| match precedes
+            .unwrap_or(Precedes::Contextual)
+        {
+            Precedes::Contextual => shown >= 3,
+            Precedes::AfterInvertedName => inverted,
+            Precedes::Always => true,
+            Precedes::Never => false,
+        };
+
+        let shown = options
+            .et_al_min
+            .zip(options.et_al_use_first)
+            .filter(|(min, first)| count >= *min && *first < count)
+            .map(|(_, first)| first.max(1));
+
+        let text = if let Some(shown) = shown {
+            let mut text = rendered[..shown]
+                .iter()
+                .map(|(name, _)| name)
+                .join(delimiter);
+            if options.et_al_use_last == Some(true) && shown + 2 <= count {
+                text.push_str(delimiter);
+                text.push_str("… ");
+                text.push_str(&rendered[count - 1].0);
+            } else {
+                let inverted = rendered[shown - 1].1;
+                let before = options.delimiter_precedes_et_al;
+                text.push_str(if precedes(before, shown + 1, inverted) {
+                    delimiter
+                } else {
+                    " "
+                });
+
+                let et_al = self.locale.term("et-al", TermForm::Long, false);
+                text.push_str(et_al.unwrap_or_default());
+            }
+
+            text
+        } else {
+            let and = options.and.as_deref().and_then(|and| match and {
+                "symbol" => self.locale.term("and", TermForm::Symbol, false),
+                _ => self.locale.term("and", TermForm::Long, false),
+            });
+
+            let mut text = String::new();
+            for (index, (name, _)) in rendered.iter().enumerate() {
+                if index > 0 {
+                    match and.filter(|_| index == count - 1) {
+                        Some(and) => {
+                            let inverted = rendered[index - 1].1;
+                            if precedes(options.delimiter_precedes_last, count, inverted) {
+                                text.push_str(delimiter);
+                            } else {
+                                text.push(' ');
+                            }
+
+                            text.push_str(and);
+                            text.push(' ');
+                        }
+                        None => text.push_str(delimiter),
+                    }
+                }
+
+                text.push_str(name);
+            }
+
+            text
+        };
+
+        self.format(text, &options.formatting)
+    }
+
+    fn render_name(&self, name: &Name, inverted: bool, options: &NameOptions) -> String {
+        let family = name.surname();
+        if options.short == Some(true) {
+            return family.to_string();
+        }
+
+        let initials = |separator: &str| {
+            name.initials()
+                .chars()
+                .map(|c| format!("{c}{separator}"))
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        };
+
+        let given = match (&options.initialize_with, name.given_name()) {
+            (Some(separator), _) => initials(separator),
+            (None, Some(given)) => [Some(given.into()), name.middle_name()]
+                .into_iter()
+                .flatten()
+                .join(" "),
+            (None, None) => initials(". "),
+        };
+
+        let suffix = name.generational_suffix();
+        let separator = options.sort_separator.as_deref().unwrap_or(", ");
+        match (given.is_empty(), inverted) {
+            (true, _) => family.to_string(),
+            (false, true) => [Some(family), Some(&given), suffix]
+                .into_iter()
+                .flatten()
+                .join(separator),
+            (false, false) => match suffix {
+                Some(suffix) => format!("{given} {family}, {suffix}"),
+                None => format!("{given} {family}"),
+            },
+        }
+    }
+
+    fn format(&self, text: String, formatting: &Formatting) -> String {
+        if text.is_empty() {
+            return text;
+        }
+
+        let mut text = if formatting.strip_periods {
+            text.replace('.', "")
+        } else {
+            text
+        };
+
+        text = match formatting.text_case {
+            Some(TextCase::Lowercase) => text.to_lowercase(),
+            Some(TextCase::Uppercase) => text.to_uppercase(),
+            Some(TextCase::CapitalizeFirst | TextCase::Sentence) => capitalize(&text),
+            Some(TextCase::CapitalizeAll) => text.split(' ').map(capitalize).join(" "),
+            Some(TextCase::Title) => titlecase(&text),
+            None => text,
+        };
+
+        if formatting.quotes {
+            let open = self.locale.term("open-quote", TermForm::Long, false);
+            let close = self.locale.term("close-quote", TermForm::Long, false);
+            text = format!("{}{text}{}", open.unwrap_or("\""), close.unwrap_or("\""));
+        }
+
+        if formatting.italic {
+            text = format!("*{text}*");
+        }
+
+        if formatting.bold {
+            text = format!("**{text}**");
+        }
+
+        self.concat(
+            self.concat(formatting.prefix.clone(), &text),
+            &formatting.suffix,
+        )
+    }
+
+    /// Appends `right` to `left` without repeating punctuation or spaces at the boundary.
+    fn concat(&self, mut left: String, right: &str) -> String {
+        let mut right = if left.ends_with(' ') {
+            right.trim_start_matches(' ')
+        } else {
+            right
+        };
+
+        let last = left
+            .trim_end_matches(['*', '"', '”', '’'])
+            .chars()
+            .next_back();
+
+        match (last, right.chars().next()) {
+            (Some('.' | '?' | '!'), Some('.')) | (Some(','), Some(',')) => {
+                right = &right[1..];
+            }
+            (Some('.' | '?' | '!'), Some(',')) if left.ends_with(['”', '’']) => {
+                right = &right[1..];
+            }
+            (_, Some(punct @ ('.' | ',')))
+                if self.locale.punctuation_in_quote() && left.ends_with(['”', '’']) =>
+            {
+                let quote = left.pop().unwrap();
+                left.push(punct);
+                left.push(quote);
+                right = &right[1..];
+            }
+            _ => {}
+        }
+
+        left.push_str(right);
+        left
+    }
+}
+
+pub(crate) fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn roman(mut value: u32) -> String {
+    const NUMERALS: &[(u32, &str)] = &[
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+
+    let mut output = String::new();
+    for (number, numeral) in NUMERALS {
+        while value >= *number {
+            output.push_str(numeral);
+            value -= number;
+        }
+    }
+
+    output
+}
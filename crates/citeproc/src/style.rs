@@ -0,0 +1,722 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use base_db::{Workspace, deps::Project};
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use crate::{
+    locale::{Language, Locale, TermForm},
+    xml::{self, Element, XmlError},
+};
+
+/// The styles that ship with the server and can be referred to by name.
+const BUNDLED_STYLES: &[(&str, &str)] = &[
+    ("apa", include_str!("../styles/apa.csl")),
+    (
+        "chicago-author-date",
+        include_str!("../styles/chicago-author-date.csl"),
+    ),
+    ("ieee", include_str!("../styles/ieee.csl")),
+];
+
+/// Maps the names of common `biblatex` and BibTeX styles to the bundled styles.
+const DOCUMENT_STYLES: &[(&str, &str)] = &[
+    ("apa", "apa"),
+    ("apa6", "apa"),
+    ("apacite", "apa"),
+    ("apalike", "apa"),
+    ("chicago", "chicago-author-date"),
+    ("chicago-authordate", "chicago-author-date"),
+    ("ieee", "ieee"),
+    ("ieeetr", "ieee"),
+    ("ieeetran", "ieee"),
+];
+
+#[derive(Debug, Error)]
+pub enum StyleError {
+    #[error("invalid XML: {0}")]
+    Xml(#[from] XmlError),
+
+    #[error("expected a `<style>` element but found `<{0}>`")]
+    NotAStyle(String),
+
+    #[error("the style has neither a `<bibliography>` nor a `<citation>` layout")]
+    MissingLayout,
+
+    #[error("unknown macro `{0}`")]
+    UnknownMacro(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// A citation style in the [CSL 1.0](https://docs.citationstyles.org/en/stable/specification.html) format.
+///
+/// Only the bibliography layout is used to render entries.
+/// Sorting, disambiguation and citation-specific features are not supported.
+#[derive(Debug, Clone, Default)]
+pub struct Style {
+    pub(crate) title: Option<String>,
+    pub(crate) macros: FxHashMap<String, Vec<Rendering>>,
+    pub(crate) layout: Layout,
+    pub(crate) default_language: Option<Language>,
+    /// The `<locale>` elements of the style together with their `xml:lang` attribute.
+    pub(crate) locales: Vec<(Option<Language>, Locale)>,
+}
+
+impl Style {
+    pub fn parse(input: &str) -> Result<Self, StyleError> {
+        let root = xml::parse(input)?;
+        if root.name != "style" {
+            return Err(StyleError::NotAStyle(root.name));
+        }
+
+        let locales = root
+            .elements()
+            .filter(|element| element.name == "locale")
+            .filter_map(|element| match element.attribute("xml:lang") {
+                Some(lang) => Some((Some(Language::parse(lang)?), Locale::parse(element))),
+                None => Some((None, Locale::parse(element))),
+            })
+            .collect();
+
+        let style_names = NameOptions::parse(&root);
+        let (layout_parent, names) = match root.element("bibliography") {
+            Some(bibliography) => (bibliography, NameOptions::parse(bibliography)),
+            None => {
+                let citation = root.element("citation").ok_or(StyleError::MissingLayout)?;
+                (citation, NameOptions::parse(citation))
+            }
+        };
+
+        let layout = layout_parent
+            .element("layout")
+            .ok_or(StyleError::MissingLayout)?;
+
+        let macros = root
+            .elements()
+            .filter(|element| element.name == "macro")
+            .filter_map(|element| Some((element.attribute("name")?.to_string(), element)))
+            .map(|(name, element)| (name, parse_children(element)))
+            .collect();
+
+        let style = Self {
+            title: root
+                .element("info")
+                .and_then(|info| info.element("title"))
+                .map(Element::text),
+            macros,
+            layout: Layout {
+                children: parse_children(layout),
+                formatting: Formatting::parse(layout),
+                names: names.inherit(&style_names),
+            },
+            default_language: root.attribute("default-locale").and_then(Language::parse),
+            locales,
+        };
+
+        style.check_macros(&style.layout.children)?;
+        for children in style.macros.values() {
+            style.check_macros(children)?;
+        }
+
+        Ok(style)
+    }
+
+    /// Looks up one of the styles that are bundled with the server.
+    pub fn bundled(name: &str) -> Option<Arc<Self>> {
+        static STYLES: Lazy<FxHashMap<&str, Arc<Style>>> = Lazy::new(|| {
+            BUNDLED_STYLES
+                .iter()
+                .map(|(name, text)| (*name, Arc::new(Style::parse(text).unwrap())))
+                .collect()
+        });
+
+        STYLES.get(name.to_lowercase().as_str()).cloned()
+    }
+
+    /// Reads a style from a `.csl` file.
+    /// Styles are cached until the file is modified.
+    pub fn load(path: &Path) -> Result<Arc<Self>, StyleError> {
+        type Cache = FxHashMap<PathBuf, (SystemTime, Arc<Style>)>;
+        static CACHE: Lazy<Mutex<Cache>> = Lazy::new(Mutex::default);
+
+        let modified = std::fs::metadata(path)?.modified()?;
+        let mut cache = CACHE.lock().unwrap();
+        match cache.get(path) {
+            Some((time, style)) if *time == modified => Ok(Arc::clone(style)),
+            _ => {
+                let style = Arc::new(Self::parse(&std::fs::read_to_string(path)?)?);
+                cache.insert(path.to_path_buf(), (modified, Arc::clone(&style)));
+                Ok(style)
+            }
+        }
+    }
+
+    /// Finds the style that is used to render the citations of a project.
+    /// The style that the project selects with `biblatex` or `\bibliographystyle` takes precedence
+    /// over the configured style, which is either the name of a bundled style or the path
+    /// to a `.csl` file relative to the workspace folder.
+    pub fn from_project(workspace: &Workspace, project: &Project) -> Option<Arc<Self>> {
+        let document_style = project.citation_style().and_then(|name| {
+            let name = name.to_lowercase();
+            DOCUMENT_STYLES
+                .iter()
+                .find(|(document_name, _)| *document_name == name)
+                .and_then(|(_, bundled_name)| Self::bundled(bundled_name))
+        });
+
+        if document_style.is_some() {
+            return document_style;
+        }
+
+        let style = workspace.config().citation.style.as_deref()?;
+        if let Some(style) = Self::bundled(style) {
+            return Some(style);
+        }
+
+        let path = workspace
+            .folders()
+            .iter()
+            .map(|folder| folder.join(style))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| PathBuf::from(style));
+
+        match Self::load(&path) {
+            Ok(style) => Some(style),
+            Err(why) => {
+                log::warn!("Unable to load citation style {style}: {why}");
+                None
+            }
+        }
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Combines the built-in locale of `language` with the locales that the style defines,
+    /// where locales for a specific language take precedence.
+    pub(crate) fn locale(&self, language: Language) -> Locale {
+        let mut locale = Locale::new(language);
+        let generic = self.locales.iter().filter(|(lang, _)| lang.is_none());
+        let specific = self
+            .locales
+            .iter()
+            .filter(|(lang, _)| *lang == Some(language));
+
+        for (_, other) in generic.chain(specific) {
+            locale.merge(other);
+        }
+
+        locale
+    }
+
+    fn check_macros(&self, children: &[Rendering]) -> Result<(), StyleError> {
+        for child in children {
+            match child {
+                Rendering::Text {
+                    source: TextSource::Macro(name),
+                    ..
+                } if !self.macros.contains_key(name) => {
+                    return Err(StyleError::UnknownMacro(name.clone()));
+                }
+                Rendering::Group { children, .. } => self.check_macros(children)?,
+                Rendering::Names(names) => self.check_macros(&names.substitute)?,
+                Rendering::Choose { branches, .. } => {
+                    for (_, children) in branches {
+                        self.check_macros(children)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    pub children: Vec<Rendering>,
+    pub formatting: Formatting,
+    pub names: NameOptions,
+}
+
+#[derive(Debug, Clone)]
+pub enum Rendering {
+    Text {
+        source: TextSource,
+        formatting: Formatting,
+    },
+    Number {
+        variable: String,
+        form: NumberForm,
+        formatting: Formatting,
+    },
+    Label(Label),
+    Date(Date),
+    Names(Box<Names>),
+    Group {
+        children: Vec<Rendering>,
+        delimiter: String,
+        formatting: Formatting,
+    },
+    Choose {
+        /// The conditions of `if` and `else-if` followed by an `else` branch that always matches.
+        branches: Vec<(Condition, Vec<Rendering>)>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum TextSource {
+    Variable {
+        name: String,
+        short: bool,
+    },
+    Macro(String),
+    Term {
+        name: String,
+        form: TermForm,
+        plural: bool,
+    },
+    Value(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NumberForm {
+    Numeric,
+    Ordinal,
+    LongOrdinal,
+    Roman,
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub variable: String,
+    pub form: TermForm,
+    pub plural: Plural,
+    pub formatting: Formatting,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Plural {
+    Contextual,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone)]
+pub struct Date {
+    pub variable: String,
+    /// Set if the date uses the default format of the locale.
+    pub localized: Option<DateForm>,
+    pub limit: DateLimit,
+    pub parts: Vec<DatePart>,
+    pub delimiter: String,
+    pub formatting: Formatting,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DateForm {
+    Text,
+    Numeric,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum DateLimit {
+    Year,
+    YearMonth,
+    YearMonthDay,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatePart {
+    pub kind: DatePartKind,
+    pub form: DatePartForm,
+    pub formatting: Formatting,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DatePartKind {
+    Year,
+    Month,
+    Day,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DatePartForm {
+    Long,
+    Short,
+    Numeric,
+    NumericLeadingZeros,
+    Ordinal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Names {
+    pub variables: Vec<String>,
+    /// The options of the `<name>` child, which are inherited from the parent `<names>` if missing.
+    pub name: Option<NameOptions>,
+    pub label: Option<Label>,
+    pub label_first: bool,
+    pub substitute: Vec<Rendering>,
+    pub delimiter: String,
+    pub formatting: Formatting,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NameOptions {
+    pub and: Option<String>,
+    pub delimiter: Option<String>,
+    pub delimiter_precedes_last: Option<Precedes>,
+    pub delimiter_precedes_et_al: Option<Precedes>,
+    pub et_al_min: Option<usize>,
+    pub et_al_use_first: Option<usize>,
+    pub et_al_use_last: Option<bool>,
+    pub initialize_with: Option<String>,
+    pub name_as_sort_order: Option<SortOrder>,
+    pub sort_separator: Option<String>,
+    pub short: Option<bool>,
+    pub formatting: Formatting,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Precedes {
+    Contextual,
+    AfterInvertedName,
+    Always,
+    Never,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortOrder {
+    First,
+    All,
+}
+
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub kind: MatchKind,
+    pub tests: Vec<Test>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MatchKind {
+    All,
+    Any,
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub enum Test {
+    Type(String),
+    Variable(String),
+    IsNumeric(String),
+    /// A test that depends on the citation context, which never matches in a bibliography.
+    Unsupported,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextCase {
+    Lowercase,
+    Uppercase,
+    CapitalizeFirst,
+    CapitalizeAll,
+    Sentence,
+    Title,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Formatting {
+    pub prefix: String,
+    pub suffix: String,
+    pub italic: bool,
+    pub bold: bool,
+    pub quotes: bool,
+    pub strip_periods: bool,
+    pub text_case: Option<TextCase>,
+}
+
+impl Formatting {
+    fn parse(element: &Element) -> Self {
+        Self {
+            prefix: element.attribute("prefix").unwrap_or_default().into(),
+            suffix: element.attribute("suffix").unwrap_or_default().into(),
+            italic: matches!(element.attribute("font-style"), Some("italic" | "oblique")),
+            bold: element.attribute("font-weight") == Some("bold"),
+            quotes: element.attribute("quotes") == Some("true"),
+            strip_periods: element.attribute("strip-periods") == Some("true"),
+            text_case: element.attribute("text-case").and_then(|case| {
+                Some(match case {
+                    "lowercase" => TextCase::Lowercase,
+                    "uppercase" => TextCase::Uppercase,
+                    "capitalize-first" => TextCase::CapitalizeFirst,
+                    "capitalize-all" => TextCase::CapitalizeAll,
+                    "sentence" => TextCase::Sentence,
+                    "title" => TextCase::Title,
+                    _ => return None,
+                })
+            }),
+        }
+    }
+}
+
+impl NameOptions {
+    fn parse(element: &Element) -> Self {
+        let precedes = |name| {
+            element.attribute(name).and_then(|value| {
+                Some(match value {
+                    "contextual" => Precedes::Contextual,
+                    "after-inverted-name" => Precedes::AfterInvertedName,
+                    "always" => Precedes::Always,
+                    "never" => Precedes::Never,
+                    _ => return None,
+                })
+            })
+        };
+
+        Self {
+            and: element.attribute("and").map(String::from),
+            delimiter: element
+                .attribute("name-delimiter")
+                .or_else(|| (element.name == "name").then(|| element.attribute("delimiter"))?)
+                .map(String::from),
+            delimiter_precedes_last: precedes("delimiter-precedes-last"),
+            delimiter_precedes_et_al: precedes("delimiter-precedes-et-al"),
+            et_al_min: element.attribute("et-al-min").and_then(|n| n.parse().ok()),
+            et_al_use_first: element
+                .attribute("et-al-use-first")
+                .and_then(|n| n.parse().ok()),
+            et_al_use_last: element
+                .attribute("et-al-use-last")
+                .map(|value| value == "true"),
+            initialize_with: element.attribute("initialize-with").map(String::from),
+            name_as_sort_order: element.attribute("name-as-sort-order").and_then(|value| {
+                Some(match value {
+                    "first" => SortOrder::First,
+                    "all" => SortOrder::All,
+                    _ => return None,
+                })
+            }),
+            sort_separator: element.attribute("sort-separator").map(String::from),
+            short: element.attribute("form").map(|form| form == "short"),
+            formatting: Formatting::parse(element),
+        }
+    }
+
+    /// Fills in the options that are not set with the options of `parent`.
+    pub fn inherit(&self, parent: &Self) -> Self {
+        Self {
+            and: self.and.clone().or_else(|| parent.and.clone()),
+            delimiter: self.delimiter.clone().or_else(|| parent.delimiter.clone()),
+            delimiter_precedes_last: self
+                .delimiter_precedes_last
+                .or(parent.delimiter_precedes_last),
+            delimiter_precedes_et_al: self
+                .delimiter_precedes_et_al
+                .or(parent.delimiter_precedes_et_al),
+            et_al_min: self.et_al_min.or(parent.et_al_min),
+            et_al_use_first: self.et_al_use_first.or(parent.et_al_use_first),
+            et_al_use_last: self.et_al_use_last.or(parent.et_al_use_last),
+            initialize_with: self
+                .initialize_with
+                .clone()
+                .or_else(|| parent.initialize_with.clone()),
+            name_as_sort_order: self.name_as_sort_order.or(parent.name_as_sort_order),
+            sort_separator: self
+                .sort_separator
+                .clone()
+                .or_else(|| parent.sort_separator.clone()),
+            short: self.short.or(parent.short),
+            formatting: self.formatting.clone(),
+        }
+    }
+}
+
+fn parse_children(element: &Element) -> Vec<Rendering> {
+    element.elements().filter_map(parse_rendering).collect()
+}
+
+fn parse_rendering(element: &Element) -> Option<Rendering> {
+    let formatting = Formatting::parse(element);
+    Some(match element.name.as_str() {
+        "text" => {
+            let source = if let Some(name) = element.attribute("variable") {
+                TextSource::Variable {
+                    name: name.into(),
+                    short: element.attribute("form") == Some("short"),
+                }
+            } else if let Some(name) = element.attribute("macro") {
+                TextSource::Macro(name.into())
+            } else if let Some(name) = element.attribute("term") {
+                TextSource::Term {
+                    name: name.into(),
+                    form: element
+                        .attribute("form")
+                        .and_then(TermForm::parse)
+                        .unwrap_or_default(),
+                    plural: element.attribute("plural") == Some("true"),
+                }
+            } else {
+                TextSource::Value(element.attribute("value")?.into())
+            };
+
+            Rendering::Text { source, formatting }
+        }
+        "number" => Rendering::Number {
+            variable: element.attribute("variable")?.into(),
+            form: match element.attribute("form") {
+                Some("ordinal") => NumberForm::Ordinal,
+                Some("long-ordinal") => NumberForm::LongOrdinal,
+                Some("roman") => NumberForm::Roman,
+                _ => NumberForm::Numeric,
+            },
+            formatting,
+        },
+        "label" => Rendering::Label(parse_label(element, element.attribute("variable")?)),
+        "date" => Rendering::Date(parse_date(element)?),
+        "names" => Rendering::Names(Box::new(parse_names(element)?)),
+        "group" => Rendering::Group {
+            children: parse_children(element),
+            delimiter: element.attribute("delimiter").unwrap_or_default().into(),
+            formatting,
+        },
+        "choose" => Rendering::Choose {
+            branches: element
+                .elements()
+                .map(|branch| {
+                    let condition = match branch.name.as_str() {
+                        "if" | "else-if" => parse_condition(branch),
+                        _ => Condition {
+                            kind: MatchKind::All,
+                            tests: Vec::new(),
+                        },
+                    };
+
+                    (condition, parse_children(branch))
+                })
+                .collect(),
+        },
+        _ => return None,
+    })
+}
+
+fn parse_label(element: &Element, variable: &str) -> Label {
+    Label {
+        variable: variable.into(),
+        form: element
+            .attribute("form")
+            .and_then(TermForm::parse)
+            .unwrap_or_default(),
+        plural: match element.attribute("plural") {
+            Some("always") => Plural::Always,
+            Some("never") => Plural::Never,
+            _ => Plural::Contextual,
+        },
+        formatting: Formatting::parse(element),
+    }
+}
+
+fn parse_date(element: &Element) -> Option<Date> {
+    let localized = element.attribute("form").map(|form| match form {
+        "numeric" => DateForm::Numeric,
+        _ => DateForm::Text,
+    });
+
+    let parts = element
+        .elements()
+        .filter(|part| part.name == "date-part")
+        .filter_map(|part| {
+            let kind = match part.attribute("name")? {
+                "year" => DatePartKind::Year,
+                "month" => DatePartKind::Month,
+                "day" => DatePartKind::Day,
+                _ => return None,
+            };
+
+            let form = match (kind, part.attribute("form")) {
+                (_, Some("short")) => DatePartForm::Short,
+                (_, Some("numeric")) => DatePartForm::Numeric,
+                (_, Some("numeric-leading-zeros")) => DatePartForm::NumericLeadingZeros,
+                (_, Some("ordinal")) => DatePartForm::Ordinal,
+                (DatePartKind::Day, _) => DatePartForm::Numeric,
+                (_, _) => DatePartForm::Long,
+            };
+
+            Some(DatePart {
+                kind,
+                form,
+                formatting: Formatting::parse(part),
+            })
+        })
+        .collect();
+
+    Some(Date {
+        variable: element.attribute("variable")?.into(),
+        localized,
+        limit: match element.attribute("date-parts") {
+            Some("year") => DateLimit::Year,
+            Some("year-month") => DateLimit::YearMonth,
+            _ => DateLimit::YearMonthDay,
+        },
+        parts,
+        delimiter: element.attribute("delimiter").unwrap_or_default().into(),
+        formatting: Formatting::parse(element),
+    })
+}
+
+fn parse_names(element: &Element) -> Option<Names> {
+    let variables = element
+        .attribute("variable")?
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let children: Vec<_> = element.elements().collect();
+    let name_index = children.iter().position(|child| child.name == "name");
+    let label_index = children.iter().position(|child| child.name == "label");
+    let first = variables.first()?;
+    let label = label_index.map(|index| parse_label(children[index], first));
+
+    Some(Names {
+        name: name_index.map(|index| NameOptions::parse(children[index])),
+        label,
+        label_first: matches!((label_index, name_index), (Some(label), Some(name)) if label < name),
+        substitute: element
+            .element("substitute")
+            .map(parse_children)
+            .unwrap_or_default(),
+        delimiter: element.attribute("delimiter").unwrap_or_default().into(),
+        formatting: Formatting::parse(element),
+        variables,
+    })
+}
+
+fn parse_condition(element: &Element) -> Condition {
+    let kind = match element.attribute("match") {
+        Some("any") => MatchKind::Any,
+        Some("none") => MatchKind::None,
+        _ => MatchKind::All,
+    };
+
+    let mut tests = Vec::new();
+    for (name, value) in &element.attributes {
+        let test: fn(String) -> Test = match name.as_str() {
+            "type" => Test::Type,
+            "variable" => Test::Variable,
+            "is-numeric" => Test::IsNumeric,
+            "match" => continue,
+            _ => |_| Test::Unsupported,
+        };
+
+        tests.extend(value.split_whitespace().map(String::from).map(test));
+    }
+
+    Condition { kind, tests }
+}
@@ -1,9 +1,11 @@
-use base_db::semantics::bib::Semantics;
+use base_db::semantics::bib::{ResolvedEntry, Semantics};
 use expect_test::{Expect, expect};
 use parser::parse_bibtex;
 use rowan::ast::AstNode;
 use syntax::bibtex;
 
+use super::Style;
+
 fn check(input: &str, expect: Expect) {
     let green = parse_bibtex(input);
     let root = bibtex::SyntaxNode::new_root(green);
@@ -11,7 +13,7 @@ fn check(input: &str, expect: Expect) {
     semantics.process_root(&root);
     let root = bibtex::Root::cast(root).unwrap();
     let entry = root.entries().next().unwrap();
-    let output = super::render(&entry, &semantics).unwrap();
+    let output = super::render(&ResolvedEntry::new(entry, &semantics), None, None).unwrap();
     expect.assert_eq(&output);
 }
 
@@ -65,7 +67,7 @@ fn test_article_jain_1999() {
     keywords = {incremental clustering, clustering applications, exploratory data analysis, cluster analysis, similarity indices, unsupervised learning}
 }"#,
         expect![[
-            r#"A. Jain, M. Murty, P. Flynn: "Data Clustering: A Review". *ACM Comput. Surv.* 31.3 (Sep. 1999): 264-323. ISSN: 0360-0300. DOI: [10.1145/331499.331504](https://doi.org/10.1145/331499.331504). URL: [https://doi.org/10.1145/331499.331504](https://doi.org/10.1145/331499.331504)."#
+            r#"A. Jain, M. Murty, P. Flynn: "Data Clustering: A Review". *ACM Comput. Surv.* 31.3 (Sept. 1999): 264-323. ISSN: 0360-0300. DOI: [10.1145/331499.331504](https://doi.org/10.1145/331499.331504). URL: [https://doi.org/10.1145/331499.331504](https://doi.org/10.1145/331499.331504)."#
         ]],
     );
 }
@@ -294,7 +296,7 @@ fn test_mvbook_nietzsche_1988() {
                     first in the bibliography},
 }"#,
         expect![[
-            r#"F. Nietzsche: "Sämtliche Werke. Kritische Studienausgabe". Ed. by G. Colli, M. Montinari. 2nd. München and Berlin and New York: Deutscher Taschenbuch-Verlag and Walter de Gruyter, 1988."#
+            r#"F. Nietzsche: "Sämtliche Werke. Kritische Studienausgabe". Hrsg. von G. Colli, M. Montinari. 2. Aufl. München and Berlin and New York: Deutscher Taschenbuch-Verlag and Walter de Gruyter, 1988."#
         ]],
     );
 }
@@ -396,3 +398,296 @@ fn test_patent_almendro_1998() {
         ]],
     );
 }
+
+fn check_style(style: &Style, input: &str, expect: Expect) {
+    let green = parse_bibtex(input);
+    let root = bibtex::SyntaxNode::new_root(green);
+    let mut semantics = Semantics::default();
+    semantics.process_root(&root);
+    let root = bibtex::Root::cast(root).unwrap();
+    let entry = root.entries().next().unwrap();
+    let output = super::render(&ResolvedEntry::new(entry, &semantics), Some(style), None).unwrap();
+    expect.assert_eq(&output);
+}
+
+const RIVEST_1978: &str = r#"
+@article{10.1145/359340.359342,
+    author = {Rivest, R. L. and Shamir, A. and Adleman, L.},
+    title = {A Method for Obtaining Digital Signatures and Public-Key Cryptosystems},
+    year = {1978},
+    publisher = {Association for Computing Machinery},
+    address = {New York, NY, USA},
+    volume = {21},
+    number = {2},
+    doi = {10.1145/359340.359342},
+    journal = {Commun. ACM},
+    month = {feb},
+    pages = {120-126},
+}"#;
+
+const ERWIN_2007: &str = r#"
+@incollection{erwin,
+    author = {Erwin, Sarah and Doe, John},
+    editor = {Smith, Jane and Miller, Max},
+    title = {Chapter Title},
+    booktitle = {The Big Book},
+    publisher = {Springer},
+    address = {Berlin},
+    year = {2007},
+    pages = {10--20},
+}"#;
+
+#[test]
+fn test_style_apa_article() {
+    check_style(
+        &Style::bundled("apa").unwrap(),
+        RIVEST_1978,
+        expect![[
+            r#"Rivest, R. L., Shamir, A., & Adleman, L. (1978). A Method for Obtaining Digital Signatures and Public-Key Cryptosystems. *Commun. ACM*, *21*(2), 120–126. https://doi.org/10.1145/359340.359342"#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_apa_incollection() {
+    check_style(
+        &Style::bundled("apa").unwrap(),
+        ERWIN_2007,
+        expect![[
+            r#"Erwin, S., & Doe, J. (2007). Chapter Title. In J. Smith & M. Miller (Eds.), *The Big Book* (pp. 10–20). Springer."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_ieee_article() {
+    check_style(
+        &Style::bundled("ieee").unwrap(),
+        RIVEST_1978,
+        expect![[
+            r#"R. L. Rivest, A. Shamir, and L. Adleman, “A Method for Obtaining Digital Signatures and Public-Key Cryptosystems,” *Commun. ACM*, vol. 21, no. 2, pp. 120–126, Feb. 1978, doi: 10.1145/359340.359342."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_ieee_incollection() {
+    check_style(
+        &Style::bundled("ieee").unwrap(),
+        ERWIN_2007,
+        expect![[
+            r#"S. Erwin and J. Doe, “Chapter Title,” in *The Big Book*, J. Smith and M. Miller, Eds., Berlin: Springer, 2007, pp. 10–20."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_chicago_article() {
+    check_style(
+        &Style::bundled("chicago-author-date").unwrap(),
+        RIVEST_1978,
+        expect![[
+            r#"Rivest, R. L., A. Shamir, and L. Adleman. 1978. “A Method for Obtaining Digital Signatures and Public-Key Cryptosystems.” *Commun. ACM* 21 (2): 120–126. https://doi.org/10.1145/359340.359342."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_chicago_book_without_author() {
+    check_style(
+        &Style::bundled("chicago-author-date").unwrap(),
+        r#"
+@book{matuz,
+    editor = {Matuz, Roger},
+    title = {Contemporary Literary Criticism},
+    edition = {2},
+    publisher = {Gale},
+    location = {Detroit},
+    date = {1990-05},
+}"#,
+        expect![[
+            r#"Matuz, Roger, ed. 1990. *Contemporary Literary Criticism*. 2nd ed. Detroit: Gale."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_custom() {
+    let style = Style::parse(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<style xmlns="http://purl.org/net/xbiblio/csl" version="1.0">
+  <!-- A comment -->
+  <macro name="year">
+    <date variable="issued" form="text" date-parts="year-month"/>
+  </macro>
+  <bibliography>
+    <layout suffix="!">
+      <group delimiter=" &amp; ">
+        <text variable="title" text-case="uppercase"/>
+        <group prefix="[" suffix="]">
+          <text value="missing: "/>
+          <text variable="ISBN"/>
+        </group>
+        <text macro="year" font-weight="bold"/>
+        <number variable="volume" form="roman"/>
+      </group>
+    </layout>
+  </bibliography>
+</style>"#,
+    )
+    .unwrap();
+
+    check_style(
+        &style,
+        RIVEST_1978,
+        expect![[
+            r#"A METHOD FOR OBTAINING DIGITAL SIGNATURES AND PUBLIC-KEY CRYPTOSYSTEMS & **February 1978** & xxi!"#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_zotero() {
+    let style = Style::parse(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE style [
+  <!ENTITY separator ", ">
+]>
+<style xmlns="http://purl.org/net/xbiblio/csl" class="in-text" version="1.0" default-locale="en-US">
+  <info>
+    <title><![CDATA[Zotero & Co]]> &#8211; Style</title>
+    <link href="https://www.zotero.org/styles/example" rel="self"/>
+  </info>
+  <locale xml:lang="en">
+    <terms>
+      <term name="in">in</term>
+    </terms>
+  </locale>
+  <bibliography>
+    <layout suffix=".">
+      <group delimiter="&separator;">
+        <text variable="title" quotes="true"/>
+        <text variable="container-title" prefix="&lt;" suffix="&gt;"/>
+        <text value="&#x2116;"/>
+      </group>
+    </layout>
+  </bibliography>
+</style>"#,
+    )
+    .unwrap();
+
+    expect!["Zotero & Co – Style"].assert_eq(style.title().unwrap());
+    check_style(
+        &style,
+        RIVEST_1978,
+        expect![
+            "“A Method for Obtaining Digital Signatures and Public-Key Cryptosystems,” <Commun. ACM>, №."
+        ],
+    );
+}
+
+#[test]
+fn test_style_errors() {
+    let errors = [
+        "<style><bibliography/></style>",
+        "<style><bibliography><layout><text macro=\"foo\"/></layout></bibliography></style>",
+        "<style><bibliography><layout></bibliography></style>",
+        "<citation/>",
+    ]
+    .map(|input| Style::parse(input).unwrap_err().to_string());
+
+    expect![[r#"
+        [
+            "the style has neither a `<bibliography>` nor a `<citation>` layout",
+            "unknown macro `foo`",
+            "invalid XML: expected 'layout' tag, not 'bibliography' at 1:30",
+            "expected a `<style>` element but found `<citation>`",
+        ]
+    "#]]
+    .assert_debug_eq(&errors);
+}
+
+fn check_language(style: Option<&Style>, language: &str, input: &str, expect: Expect) {
+    let green = parse_bibtex(input);
+    let root = bibtex::SyntaxNode::new_root(green);
+    let mut semantics = Semantics::default();
+    semantics.process_root(&root);
+    let root = bibtex::Root::cast(root).unwrap();
+    let entry = root.entries().next().unwrap();
+    let entry = ResolvedEntry::new(entry, &semantics);
+    let output = super::render(&entry, style, Some(language)).unwrap();
+    expect.assert_eq(&output);
+}
+
+#[test]
+fn test_language_german() {
+    check_language(
+        None,
+        "ngerman",
+        r#"
+@book{foo,
+    author = {Foo Bar},
+    editor = {Baz Qux},
+    title = {Titel},
+    edition = {3},
+    year = {2020},
+    month = {mar},
+}"#,
+        expect![[r#"F. Bar: "Titel". Hrsg. von B. Qux. 3. Aufl. März 2020."#]],
+    );
+}
+
+#[test]
+fn test_language_langid() {
+    check_language(
+        None,
+        "ngerman",
+        r#"
+@book{foo,
+    author = {Foo Bar},
+    translator = {Baz Qux},
+    title = {Titre},
+    edition = {1},
+    langid = {french},
+    year = {2020},
+    month = {feb},
+}"#,
+        expect![[r#"F. Bar: "Titre". Trad. par B. Qux. 1er éd. févr. 2020."#]],
+    );
+}
+
+#[test]
+fn test_language_style_apa_spanish() {
+    check_language(
+        Some(&Style::bundled("apa").unwrap()),
+        "spanish",
+        ERWIN_2007,
+        expect![[
+            r#"Erwin, S., & Doe, J. (2007). Chapter Title. En J. Smith & M. Miller (eds.), *The Big Book* (pp. 10–20). Springer."#
+        ]],
+    );
+}
+
+#[test]
+fn test_language_style_ieee_french() {
+    check_language(
+        Some(&Style::bundled("ieee").unwrap()),
+        "french",
+        RIVEST_1978,
+        expect![[
+            r#"R. L. Rivest, A. Shamir, et L. Adleman, « A Method for Obtaining Digital Signatures and Public-Key Cryptosystems », *Commun. ACM*, vol. 21, n° 2, p. 120–126, févr. 1978, doi: 10.1145/359340.359342."#
+        ]],
+    );
+}
+
+#[test]
+fn test_language_unknown() {
+    check_language(
+        Some(&Style::bundled("ieee").unwrap()),
+        "klingon",
+        RIVEST_1978,
+        expect![[
+            r#"R. L. Rivest, A. Shamir, and L. Adleman, “A Method for Obtaining Digital Signatures and Public-Key Cryptosystems,” *Commun. ACM*, vol. 21, no. 2, pp. 120–126, Feb. 1978, doi: 10.1145/359340.359342."#
+        ]],
+    );
+}
@@ -0,0 +1,94 @@
+//! An owned element tree for CSL style files, built on top of `roxmltree`.
+//! Namespaces, comments and processing instructions are dropped.
+
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn element(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+#[error(transparent)]
+pub struct XmlError(#[from] roxmltree::Error);
+
+/// Parses the root element of an XML document.
+pub fn parse(input: &str) -> Result<Element, XmlError> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..roxmltree::ParsingOptions::default()
+    };
+
+    let document = roxmltree::Document::parse_with_options(input, options)?;
+    Ok(convert(document.root_element()))
+}
+
+fn convert(node: roxmltree::Node) -> Element {
+    let attributes = node
+        .attributes()
+        .map(|attribute| {
+            let name = match attribute.namespace() {
+                Some(roxmltree::NS_XML_URI) => format!("xml:{}", attribute.name()),
+                _ => attribute.name().to_string(),
+            };
+
+            (name, attribute.value().to_string())
+        })
+        .collect();
+
+    let children = node
+        .children()
+        .filter_map(|child| {
+            if child.is_element() {
+                Some(Node::Element(convert(child)))
+            } else {
+                let text = child.text()?;
+                (child.is_text() && !text.trim().is_empty()).then(|| Node::Text(text.to_string()))
+            }
+        })
+        .collect();
+
+    Element {
+        name: node.tag_name().name().to_string(),
+        attributes,
+        children,
+    }
+}
@@ -0,0 +1,133 @@
+use base_db::{
+    Document, Workspace,
+    deps::{self, EdgeData, ProjectRoot},
+};
+use rowan::{TextRange, ast::AstNode};
+use rustc_hash::FxHashSet;
+use syntax::latex::{self, HasCurly};
+use url::Url;
+
+use crate::{
+    CodeAction, CodeActionKind, CodeActionParams, DocumentChange,
+    util::{is_balanced, trim_range},
+};
+
+/// Moves the selected text into a new file and replaces it with an `\input` command.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    if !params.create_files {
+        return None;
+    }
+
+    let document = params.feature.document;
+    let workspace = params.feature.workspace;
+    let data = document.data.as_tex()?;
+    let document_dir = document.dir.as_ref()?;
+    let range = trim_range(&document.text, params.range)?;
+    let root_node = data.root_node();
+    if !is_balanced(&root_node, range) {
+        return None;
+    }
+
+    let root = project_root(workspace, document)?;
+    let dir = match &workspace.config().code_actions.extract_dir {
+        Some(dir) => root
+            .src_dir
+            .join(&format!("{}/", dir.trim_end_matches('/')))
+            .ok()?,
+        None => document_dir.clone(),
+    };
+
+    let stem = find_section_name(&root_node, range)
+        .map(|name| slugify(&name))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| String::from("extracted"));
+
+    let uri = find_unused_uri(workspace, &dir, &stem)?;
+    let relative_path = root.src_dir.make_relative(&uri)?;
+    let input_path = relative_path.strip_suffix(".tex").unwrap_or(&relative_path);
+
+    actions.push(CodeAction {
+        title: format!("Extract to {relative_path}"),
+        kind: CodeActionKind::RefactorExtract,
+        changes: vec![
+            DocumentChange::CreateFile {
+                uri,
+                text: format!("{}\n", &document.text[range]),
+            },
+            DocumentChange::Edit {
+                uri: document.uri.clone(),
+                range,
+                text: format!("\\input{{{input_path}}}"),
+            },
+        ],
+    });
+
+    Some(())
+}
+
+/// Finds the directory that `\input` paths of `document` are relative to.
+/// Documents that are included with `\import` have their own root.
+fn project_root(workspace: &Workspace, document: &Document) -> Option<ProjectRoot> {
+    let parent = deps::parents(workspace, document)
+        .into_iter()
+        .next()
+        .unwrap_or(document);
+
+    if let Some(graph) = workspace.graphs().get(&parent.uri) {
+        let mut visited = FxHashSet::default();
+        let mut uri = &document.uri;
+        while visited.insert(uri) {
+            let Some(edge) = graph.edges.iter().find(|edge| {
+                &edge.target == uri
+                    && !matches!(edge.data, EdgeData::Artifact | EdgeData::AdditionalFiles)
+            }) else {
+                break;
+            };
+
+            match &edge.data {
+                EdgeData::DirectLink(data) if data.new_root.is_some() => {
+                    return data.new_root.clone();
+                }
+                EdgeData::FileList(root) => return Some(root.as_ref().clone()),
+                _ => uri = &edge.source,
+            }
+        }
+    }
+
+    Some(ProjectRoot::walk_and_find(workspace, parent.dir.as_ref()?))
+}
+
+fn find_section_name(root: &latex::SyntaxNode, range: TextRange) -> Option<String> {
+    let token = root.token_at_offset(range.start()).right_biased()?;
+    token
+        .parent_ancestors()
+        .filter_map(latex::Section::cast)
+        .find(|section| section.syntax().text_range().start() == range.start())?
+        .name()?
+        .content_text()
+}
+
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+fn find_unused_uri(workspace: &Workspace, dir: &Url, stem: &str) -> Option<Url> {
+    (1..100)
+        .map(|index| match index {
+            1 => format!("{stem}.tex"),
+            _ => format!("{stem}-{index}.tex"),
+        })
+        .filter_map(|name| dir.join(&name).ok())
+        .find(|uri| {
+            workspace.lookup(uri).is_none() && !uri.to_file_path().is_ok_and(|path| path.exists())
+        })
+}
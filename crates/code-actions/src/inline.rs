@@ -0,0 +1,62 @@
+use base_db::{deps::EdgeData, semantics::tex::LinkKind};
+use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
+use syntax::latex;
+
+use crate::{CodeAction, CodeActionKind, CodeActionParams, DocumentChange};
+
+/// Replaces an `\input` or `\include` command with the contents of the included file.
+/// The included file itself is kept.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    let document = params.feature.document;
+    let workspace = params.feature.workspace;
+    let data = document.data.as_tex()?;
+    let root = data.root_node();
+
+    let include = root
+        .token_at_offset(params.range.start())
+        .right_biased()?
+        .parent_ancestors()
+        .find_map(latex::Include::cast)
+        .filter(|include| include.syntax().kind() == latex::LATEX_INCLUDE)?;
+
+    let command = include.command()?;
+    if !matches!(command.text(), "\\input" | "\\include") {
+        return None;
+    }
+
+    let range = latex::small_range(&include);
+    let targets: FxHashSet<_> = workspace
+        .graphs()
+        .values()
+        .flat_map(|graph| &graph.edges)
+        .filter(|edge| edge.source == document.uri)
+        .filter_map(|edge| match &edge.data {
+            EdgeData::DirectLink(data)
+                if data.link.kind == LinkKind::Tex
+                    && range.contains_range(data.link.path.range) =>
+            {
+                Some(&edge.target)
+            }
+            _ => None,
+        })
+        .collect();
+
+    let [target] = targets.into_iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+
+    let target = workspace.lookup(target)?;
+    let name = target.uri.path_segments()?.next_back()?;
+    actions.push(CodeAction {
+        title: format!("Inline {name}"),
+        kind: CodeActionKind::RefactorInline,
+        changes: vec![DocumentChange::Edit {
+            uri: document.uri.clone(),
+            range,
+            text: target.text.trim_end().to_string(),
+        }],
+    });
+
+    Some(())
+}
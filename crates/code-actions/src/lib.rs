@@ -0,0 +1,70 @@
+mod extract;
+mod inline;
+mod sentences;
+mod star;
+mod unwrap;
+mod util;
+mod wrap;
+
+pub use self::sentences::break_sentences;
+
+use base_db::FeatureParams;
+use rowan::TextRange;
+use url::Url;
+
+#[derive(Debug)]
+pub struct CodeActionParams<'a> {
+    pub feature: FeatureParams<'a>,
+    pub range: TextRange,
+
+    /// If `true`, actions may create new files.
+    pub create_files: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodeActionKind {
+    RefactorExtract,
+    RefactorInline,
+    RefactorRewrite,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodeAction {
+    pub title: String,
+    pub kind: CodeActionKind,
+    pub changes: Vec<DocumentChange>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DocumentChange {
+    CreateFile {
+        uri: Url,
+        text: String,
+    },
+    Edit {
+        uri: Url,
+        range: TextRange,
+        text: String,
+    },
+    /// An edit that uses snippet syntax if the client supports it and `text` otherwise.
+    Snippet {
+        uri: Url,
+        range: TextRange,
+        snippet: String,
+        text: String,
+    },
+}
+
+pub fn find_all(params: &CodeActionParams) -> Vec<CodeAction> {
+    let mut actions = Vec::new();
+    extract::find_actions(params, &mut actions);
+    inline::find_actions(params, &mut actions);
+    wrap::find_actions(params, &mut actions);
+    unwrap::find_actions(params, &mut actions);
+    star::find_actions(params, &mut actions);
+    sentences::find_actions(params, &mut actions);
+    actions
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,297 @@
+use base_db::{Config, Document, Workspace};
+use rowan::{TextRange, TextSize, ast::AstNode};
+use syntax::latex;
+
+use crate::{CodeAction, CodeActionKind, CodeActionParams, DocumentChange};
+
+/// Abbreviations that end with a period but usually do not end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "al.", "app.", "approx.", "cf.", "ch.", "chap.", "cor.", "def.", "dr.", "e.g.", "ed.", "eds.",
+    "eq.", "eqs.", "et.", "etc.", "fig.", "figs.", "i.e.", "lem.", "mr.", "mrs.", "ms.", "no.",
+    "p.", "pp.", "prof.", "prop.", "ref.", "refs.", "resp.", "sec.", "secs.", "tab.", "thm.",
+    "viz.", "vol.", "vs.",
+];
+
+/// The commands that usually stand on a line of their own.
+const BLOCK_COMMANDS: &[&str] = &[
+    "\\\\",
+    "\\bigskip",
+    "\\centering",
+    "\\clearpage",
+    "\\linebreak",
+    "\\maketitle",
+    "\\medskip",
+    "\\newline",
+    "\\newpage",
+    "\\noindent",
+    "\\pagebreak",
+    "\\par",
+    "\\smallskip",
+    "\\tableofcontents",
+    "\\vspace",
+];
+
+/// Reflows the paragraphs that intersect with `range` so that every sentence starts on a new line.
+/// The line breaks within a sentence are replaced with spaces.
+/// Math, verbatim content and comments are left untouched.
+pub fn break_sentences(
+    workspace: &Workspace,
+    document: &Document,
+    range: TextRange,
+) -> Vec<(TextRange, String)> {
+    let Some(data) = document.data.as_tex() else {
+        return Vec::new();
+    };
+
+    let config = workspace.config();
+    let mut edits = Vec::new();
+    for token in data
+        .root_node()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        // A line break and the indentation of the next line are separate tokens.
+        if !is_whitespace(Some(&token)) || is_whitespace(token.prev_token().as_ref()) {
+            continue;
+        }
+
+        let mut last = token.clone();
+        while let Some(next) = last.next_token().filter(|next| is_whitespace(Some(next))) {
+            last = next;
+        }
+
+        let whitespace = TextRange::new(token.text_range().start(), last.text_range().end());
+        if !range.contains_range(whitespace) {
+            continue;
+        }
+
+        let (Some(previous), Some(next)) = (token.prev_token(), last.next_token()) else {
+            continue;
+        };
+
+        // Trailing whitespace is part of the preceding node, so the context is the node that contains both neighbors.
+        let Some(container) = previous
+            .parent_ancestors()
+            .find(|node| node.text_range().contains_range(next.text_range()))
+            .filter(|node| is_prose(node, config))
+        else {
+            continue;
+        };
+
+        let is_end = ends_sentence(&previous);
+        let line_breaks = document.text[whitespace].matches('\n').count();
+        if line_breaks == 0 && is_end && previous.kind() == latex::WORD && starts_sentence(&next) {
+            let indent = paragraph_indentation(&document.text, &token);
+            edits.push((whitespace, format!("\n{indent}")));
+        } else if line_breaks == 1
+            && !is_end
+            && is_inline(&previous, &container, true)
+            && is_inline(&next, &container, false)
+            && [&previous, &next]
+                .iter()
+                .any(|token| matches!(token.kind(), latex::WORD | latex::COMMA))
+        {
+            edits.push((whitespace, String::from(" ")));
+        }
+    }
+
+    edits
+}
+
+/// Offers to put each sentence of the paragraphs in the selection on its own line.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    let document = params.feature.document;
+    let text = &document.text;
+    let range = paragraph_range(text, params.range.start())
+        .cover(paragraph_range(text, params.range.end()));
+
+    let edits = break_sentences(params.feature.workspace, document, range);
+    if edits.is_empty() {
+        return None;
+    }
+
+    let changes = edits
+        .into_iter()
+        .map(|(range, text)| DocumentChange::Edit {
+            uri: document.uri.clone(),
+            range,
+            text,
+        })
+        .collect();
+
+    actions.push(CodeAction {
+        title: String::from("Put each sentence on its own line"),
+        kind: CodeActionKind::RefactorRewrite,
+        changes,
+    });
+
+    Some(())
+}
+
+fn is_prose(node: &latex::SyntaxNode, config: &Config) -> bool {
+    node.ancestors().all(|ancestor| match ancestor.kind() {
+        latex::ENVIRONMENT => latex::Environment::cast(ancestor)
+            .and_then(|environment| environment.begin()?.name()?.key())
+            .map(|name| name.to_string())
+            .is_some_and(|name| {
+                !config.syntax.math_environments.contains(&name)
+                    && !config.syntax.verbatim_environments.contains(&name)
+                    && !commands::TABLE_ENVIRONMENTS.contains(&name.as_str())
+            }),
+        latex::ROOT
+        | latex::PREAMBLE
+        | latex::TEXT
+        | latex::PART
+        | latex::CHAPTER
+        | latex::SECTION
+        | latex::SUBSECTION
+        | latex::SUBSUBSECTION
+        | latex::PARAGRAPH
+        | latex::SUBPARAGRAPH
+        | latex::ENUM_ITEM
+        | latex::CAPTION
+        | latex::GENERIC_COMMAND
+        | latex::CURLY_GROUP => true,
+        _ => false,
+    })
+}
+
+fn is_sentence_end(word: &str) -> bool {
+    let word = word.trim_end_matches(['\'', '"']);
+    if !word.ends_with(['.', '!', '?']) {
+        return false;
+    }
+
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    if ABBREVIATIONS.contains(&word.to_lowercase().as_str()) {
+        return false;
+    }
+
+    // Initials like `J.` in `J. Doe` are no sentences of their own.
+    let mut chars = word.chars();
+    !matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (Some(c), Some('.'), None) if c.is_uppercase()
+    )
+}
+
+fn is_whitespace(token: Option<&latex::SyntaxToken>) -> bool {
+    token.is_some_and(|token| token.kind() == latex::WHITESPACE)
+}
+
+/// Checks whether the text before a token ends a sentence, looking into groups like `\emph{...}`.
+fn ends_sentence(token: &latex::SyntaxToken) -> bool {
+    let mut token = token.clone();
+    while token.kind() == latex::R_CURLY {
+        let Some(previous) = token.prev_token() else {
+            return false;
+        };
+
+        token = previous;
+    }
+
+    token.kind() == latex::WORD && is_sentence_end(token.text())
+}
+
+fn starts_sentence(token: &latex::SyntaxToken) -> bool {
+    match token.kind() {
+        latex::WORD => !token.text().starts_with(|c: char| c.is_lowercase()),
+        latex::COMMENT | latex::WHITESPACE | latex::R_CURLY | latex::R_BRACK => false,
+        _ => true,
+    }
+}
+
+/// Checks whether a token next to a line break belongs to running text,
+/// like words, inline math and commands such as `\emph{...}`.
+/// Environments, sectioning commands and the like keep their own lines.
+fn is_inline(token: &latex::SyntaxToken, container: &latex::SyntaxNode, is_before: bool) -> bool {
+    let is_inline_token = if is_before {
+        matches!(
+            token.kind(),
+            latex::WORD
+                | latex::COMMA
+                | latex::R_CURLY
+                | latex::R_BRACK
+                | latex::R_PAREN
+                | latex::DOLLAR
+                | latex::COMMAND_NAME
+        )
+    } else {
+        matches!(
+            token.kind(),
+            latex::WORD | latex::COMMA | latex::DOLLAR | latex::COMMAND_NAME
+        )
+    };
+
+    if !is_inline_token || (token.kind() == latex::DOLLAR && token.text() != "$") {
+        return false;
+    }
+
+    token
+        .parent_ancestors()
+        .take_while(|node| node != container)
+        .all(|node| match node.kind() {
+            latex::GENERIC_COMMAND => latex::GenericCommand::cast(node)
+                .and_then(|command| command.name())
+                .is_some_and(|name| !BLOCK_COMMANDS.contains(&name.text())),
+            latex::TEXT
+            | latex::FORMULA
+            | latex::CURLY_GROUP
+            | latex::CURLY_GROUP_WORD
+            | latex::CURLY_GROUP_WORD_LIST
+            | latex::BRACK_GROUP
+            | latex::BRACK_GROUP_WORD
+            | latex::MIXED_GROUP
+            | latex::CITATION
+            | latex::LABEL_REFERENCE
+            | latex::LABEL_REFERENCE_RANGE
+            | latex::ACRONYM_REFERENCE
+            | latex::GLOSSARY_ENTRY_REFERENCE => true,
+            _ => false,
+        })
+}
+
+/// Finds the indentation of the first line of the paragraph that contains `token`.
+/// Items and the bodies of environments start new paragraphs.
+fn paragraph_indentation<'a>(text: &'a str, token: &latex::SyntaxToken) -> &'a str {
+    let mut start = paragraph_range(text, token.text_range().start()).start();
+    let block_start = token.parent_ancestors().find_map(|node| match node.kind() {
+        latex::ENUM_ITEM => Some(node.text_range().start()),
+        latex::ENVIRONMENT => latex::Environment::cast(node)
+            .and_then(|environment| environment.begin())
+            .map(|begin| begin.syntax().text_range().end()),
+        _ => None,
+    });
+
+    if let Some(block_start) = block_start {
+        start = start.max(block_start);
+    }
+
+    let rest = &text[usize::from(start)..];
+    let start = start + TextSize::of(&rest[..rest.len() - rest.trim_start().len()]);
+    line_indentation(text, start)
+}
+
+fn line_indentation(text: &str, offset: TextSize) -> &str {
+    let line_start = text[..usize::from(offset)]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+
+    let line = &text[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Finds the lines around `offset` that are not separated by a blank line.
+fn paragraph_range(text: &str, offset: TextSize) -> TextRange {
+    let offset = usize::from(offset);
+    let start = text[..offset].rfind("\n\n").map_or(0, |index| index + 2);
+
+    let end = text[offset..]
+        .find("\n\n")
+        .map_or(text.len(), |index| offset + index);
+
+    TextRange::new(
+        TextSize::try_from(start).unwrap(),
+        TextSize::try_from(end).unwrap(),
+    )
+}
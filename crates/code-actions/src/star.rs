@@ -0,0 +1,141 @@
+use base_db::semantics::tex::LabelKind;
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{
+    CodeAction, CodeActionKind, CodeActionParams, DocumentChange,
+    util::{environment_name, expand_to_line, find_environment, trim_trivia},
+    wrap::find_environment_names,
+};
+
+/// Switches between the numbered and the starred variant of an environment or a section.
+/// Switching to the starred variant removes the labels that nothing refers to.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    let document = params.feature.document;
+    let data = document.data.as_tex()?;
+    let root = data.root_node();
+    let offset = params.range.start();
+
+    let section = root
+        .token_at_offset(offset)
+        .right_biased()
+        .and_then(|token| token.parent_ancestors().find_map(latex::Section::cast))
+        .filter(|section| {
+            section
+                .name()
+                .is_some_and(|name| offset <= name.syntax().text_range().end())
+        });
+
+    if let Some(section) = section {
+        let command = section.command()?;
+        let (name, starred) = toggle_star(command.text());
+        let labels = section
+            .syntax()
+            .children()
+            .filter_map(latex::LabelDefinition::cast);
+
+        let mut changes = vec![DocumentChange::Edit {
+            uri: document.uri.clone(),
+            range: command.text_range(),
+            text: name.clone(),
+        }];
+
+        if starred {
+            remove_unused_labels(params, labels, &mut changes);
+        }
+
+        actions.push(CodeAction {
+            title: format!("Change to {name}"),
+            kind: CodeActionKind::RefactorRewrite,
+            changes,
+        });
+    }
+
+    if let Some(environment) = find_environment(&root, offset) {
+        let begin = environment_name(&environment)?;
+        let end = environment.end()?.name()?.key()?;
+        let (name, starred) = toggle_star(&begin.to_string());
+        if starred && !find_environment_names(&params.feature).contains(&name.as_str()) {
+            let config = &params.feature.workspace.config().syntax;
+            if !config.math_environments.contains(&name) {
+                return Some(());
+            }
+        }
+
+        let labels = environment
+            .syntax()
+            .descendants()
+            .filter(|node| {
+                node.ancestors()
+                    .filter_map(latex::Environment::cast)
+                    .next()
+                    .is_some_and(|parent| parent.syntax() == environment.syntax())
+            })
+            .filter_map(latex::LabelDefinition::cast);
+
+        let mut changes = vec![
+            DocumentChange::Edit {
+                uri: document.uri.clone(),
+                range: latex::small_range(&begin),
+                text: name.clone(),
+            },
+            DocumentChange::Edit {
+                uri: document.uri.clone(),
+                range: latex::small_range(&end),
+                text: name.clone(),
+            },
+        ];
+
+        if starred {
+            remove_unused_labels(params, labels, &mut changes);
+        }
+
+        actions.push(CodeAction {
+            title: format!("Change to {name}"),
+            kind: CodeActionKind::RefactorRewrite,
+            changes,
+        });
+    }
+
+    Some(())
+}
+
+/// Returns the name with the star added or removed and whether it is now starred.
+fn toggle_star(name: &str) -> (String, bool) {
+    match name.strip_suffix('*') {
+        Some(name) => (name.to_string(), false),
+        None => (format!("{name}*"), true),
+    }
+}
+
+fn remove_unused_labels(
+    params: &CodeActionParams,
+    labels: impl Iterator<Item = latex::LabelDefinition>,
+    changes: &mut Vec<DocumentChange>,
+) {
+    let document = params.feature.document;
+    for label in labels {
+        let Some(name) = label.name().and_then(|name| name.key()) else {
+            continue;
+        };
+
+        let name = name.to_string();
+        let is_referenced = params
+            .feature
+            .project
+            .documents
+            .iter()
+            .filter_map(|document| document.data.as_tex())
+            .flat_map(|data| data.semantics.labels.iter())
+            .any(|label| label.kind != LabelKind::Definition && label.name.text == name);
+
+        if !is_referenced {
+            let range = trim_trivia(label.syntax());
+            changes.push(DocumentChange::Edit {
+                uri: document.uri.clone(),
+                range: expand_to_line(&document.text, range),
+                text: String::new(),
+            });
+        }
+    }
+}
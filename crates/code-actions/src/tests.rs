@@ -0,0 +1,515 @@
+use base_db::{CodeActionConfig, Config};
+use expect_test::{Expect, expect};
+use rowan::{TextRange, TextSize};
+
+use crate::{CodeAction, CodeActionKind, CodeActionParams, DocumentChange};
+
+fn find_actions(config: Config, input: &str, selection: &str) -> (String, Vec<CodeAction>) {
+    let mut fixture = test_utils::fixture::Fixture::parse(input);
+    fixture.workspace.set_config(config);
+    let fixture = fixture;
+
+    let document = fixture.workspace.lookup(&fixture.documents[0].uri).unwrap();
+    let start = document.text.find(selection).unwrap();
+    let range = TextRange::at(TextSize::try_from(start).unwrap(), TextSize::of(selection));
+
+    let params = CodeActionParams {
+        feature: base_db::FeatureParams::new(&fixture.workspace, document),
+        range,
+        create_files: true,
+    };
+
+    (document.text.clone(), crate::find_all(&params))
+}
+
+fn check_with_config(
+    config: Config,
+    input: &str,
+    selection: &str,
+    kind: CodeActionKind,
+    expect: Expect,
+) {
+    let (_, actions) = find_actions(config, input, selection);
+    let actions: Vec<_> = actions
+        .into_iter()
+        .filter(|action| action.kind == kind)
+        .collect();
+
+    expect.assert_debug_eq(&actions);
+}
+
+fn check(input: &str, selection: &str, kind: CodeActionKind, expect: Expect) {
+    check_with_config(Config::default(), input, selection, kind, expect)
+}
+
+/// Applies the action with the given title to the first document and compares the result.
+fn check_edit(input: &str, selection: &str, title: &str, expected: Option<&str>) {
+    let (mut text, actions) = find_actions(Config::default(), input, selection);
+    let Some(action) = actions.into_iter().find(|action| action.title == title) else {
+        assert_eq!(expected, None);
+        return;
+    };
+
+    let mut edits: Vec<_> = action
+        .changes
+        .into_iter()
+        .filter_map(|change| match change {
+            DocumentChange::Edit { range, text, .. } => Some((range, text)),
+            DocumentChange::Snippet { range, text, .. } => Some((range, text)),
+            DocumentChange::CreateFile { .. } => None,
+        })
+        .collect();
+
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    for (range, new_text) in edits {
+        text.replace_range(std::ops::Range::<usize>::from(range), &new_text);
+    }
+
+    assert_eq!(Some(text.as_str()), expected);
+}
+
+#[test]
+fn test_extract_section() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\section{Related Work}
+Foo
+\end{document}"#,
+        "\\section{Related Work}\nFoo\n",
+        CodeActionKind::RefactorExtract,
+        expect![[r#"
+            [
+                CodeAction {
+                    title: "Extract to related-work.tex",
+                    kind: RefactorExtract,
+                    changes: [
+                        CreateFile {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/related-work.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            text: "\\section{Related Work}\nFoo\n",
+                        },
+                        Edit {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/main.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            range: 41..67,
+                            text: "\\input{related-work}",
+                        },
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_extract_directory() {
+    let config = Config {
+        code_actions: CodeActionConfig {
+            extract_dir: Some("chapters".into()),
+        },
+        ..Config::default()
+    };
+
+    check_with_config(
+        config,
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+Foo
+\end{document}
+
+%! chapters/extracted.tex
+Bar"#,
+        "Foo",
+        CodeActionKind::RefactorExtract,
+        expect![[r#"
+            [
+                CodeAction {
+                    title: "Extract to chapters/extracted-2.tex",
+                    kind: RefactorExtract,
+                    changes: [
+                        CreateFile {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/chapters/extracted-2.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            text: "Foo\n",
+                        },
+                        Edit {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/main.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            range: 41..44,
+                            text: "\\input{chapters/extracted-2}",
+                        },
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_extract_import() {
+    check(
+        r#"
+%! parts/part.tex
+\section{Foo}
+Bar
+
+%! main.tex
+\documentclass{article}
+\usepackage{import}
+\begin{document}
+\import{parts/}{part}
+\end{document}"#,
+        "Bar",
+        CodeActionKind::RefactorExtract,
+        expect![[r#"
+            [
+                CodeAction {
+                    title: "Extract to extracted.tex",
+                    kind: RefactorExtract,
+                    changes: [
+                        CreateFile {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/parts/extracted.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            text: "Bar\n",
+                        },
+                        Edit {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/parts/part.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            range: 14..17,
+                            text: "\\input{extracted}",
+                        },
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_inline() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\input{chapter}
+\end{document}
+
+%! chapter.tex
+Foo
+"#,
+        "\\input{chapter}",
+        CodeActionKind::RefactorInline,
+        expect![[r#"
+            [
+                CodeAction {
+                    title: "Inline chapter.tex",
+                    kind: RefactorInline,
+                    changes: [
+                        Edit {
+                            uri: Url {
+                                scheme: "file",
+                                cannot_be_a_base: false,
+                                username: "",
+                                password: None,
+                                host: None,
+                                port: None,
+                                path: "/texlab/main.tex",
+                                query: None,
+                                fragment: None,
+                            },
+                            range: 41..56,
+                            text: "Foo",
+                        },
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_extract_partial_command() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\textbf{Foo} Bar
+\end{document}"#,
+        "Foo} Bar",
+        CodeActionKind::RefactorExtract,
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_wrap_environment() {
+    let (_, actions) = find_actions(
+        Config::default(),
+        r#"
+%! main.tex
+\documentclass{article}
+\newtheorem{lemma}{Lemma}
+\begin{document}
+  Foo $x$
+\begin{proof}\end{proof}
+\end{document}"#,
+        "Foo $x$",
+    );
+
+    let action = actions
+        .into_iter()
+        .find(|action| action.title == "Wrap in environment")
+        .unwrap();
+
+    let [DocumentChange::Snippet { snippet, text, .. }] = &action.changes[..] else {
+        panic!("unexpected changes: {:?}", action.changes);
+    };
+
+    assert!(snippet.starts_with("\\\\begin{${1|proof,lemma,"));
+    assert!(snippet.ends_with("|}}\nFoo \\$x\\$\n  \\\\end{$1}"));
+    assert_eq!(text, "\\begin{proof}\nFoo $x$\n  \\end{proof}");
+}
+
+#[test]
+fn test_wrap_command() {
+    check_edit(
+        r#"
+%! main.tex
+Foo Bar Baz"#,
+        "Bar",
+        "Wrap in command",
+        Some("Foo \\emph{Bar} Baz\n"),
+    );
+}
+
+#[test]
+fn test_unwrap_command() {
+    check_edit(
+        r#"
+%! main.tex
+Foo \textbf{Bar \emph{Baz}}"#,
+        "Bar",
+        "Unwrap \\textbf",
+        Some("Foo Bar \\emph{Baz}\n"),
+    );
+}
+
+#[test]
+fn test_unwrap_environment() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{document}
+\begin{center}
+Foo
+\end{center}
+\end{document}"#,
+        "Foo",
+        "Unwrap center environment",
+        Some("\\begin{document}\nFoo\n\\end{document}\n"),
+    );
+}
+
+#[test]
+fn test_star_environment() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{equation}
+  x = y
+  \label{eq:foo}
+  \label{eq:bar}
+\end{equation}
+\eqref{eq:bar}"#,
+        "x = y",
+        "Change to equation*",
+        Some("\\begin{equation*}\n  x = y\n  \\label{eq:bar}\n\\end{equation*}\n\\eqref{eq:bar}\n"),
+    );
+}
+
+#[test]
+fn test_unstar_environment() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{align*}
+  x = y
+\end{align*}"#,
+        "x = y",
+        "Change to align",
+        Some("\\begin{align}\n  x = y\n\\end{align}\n"),
+    );
+}
+
+#[test]
+fn test_star_unknown_environment() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{foo}
+  x = y
+\end{foo}"#,
+        "x = y",
+        "Change to foo*",
+        None,
+    );
+}
+
+#[test]
+fn test_star_section() {
+    check_edit(
+        r#"
+%! main.tex
+\section{Foo}
+\label{sec:foo}
+Bar"#,
+        "Foo",
+        "Change to \\section*",
+        Some("\\section*{Foo}\nBar\n"),
+    );
+}
+
+#[test]
+fn test_break_sentences() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{document}
+First sentence, e.g. with an abbreviation. Second
+  sentence with $x = 1. Y$ math and Fig.~\ref{foo}! Third \emph{one}? % A. B.
+\begin{equation}
+  a. B
+\end{equation}
+See J. Doe. \textbf{Last}.
+\end{document}"#,
+        "abbreviation",
+        "Put each sentence on its own line",
+        Some(
+            r#"\begin{document}
+First sentence, e.g. with an abbreviation.
+Second sentence with $x = 1. Y$ math and Fig.~\ref{foo}!
+Third \emph{one}? % A. B.
+\begin{equation}
+  a. B
+\end{equation}
+See J. Doe.
+\textbf{Last}.
+\end{document}
+"#,
+        ),
+    );
+}
+
+#[test]
+fn test_break_sentences_commands() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{itemize}
+  \item Some \emph{emphasized}
+  text with $x$
+  math. And
+  \textbf{bold} words.
+\end{itemize}"#,
+        "emphasized",
+        "Put each sentence on its own line",
+        Some(
+            r#"\begin{itemize}
+  \item Some \emph{emphasized} text with $x$ math.
+  And \textbf{bold} words.
+\end{itemize}
+"#,
+        ),
+    );
+}
+
+#[test]
+fn test_break_sentences_verbatim() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{verbatim}
+Foo. Bar
+\end{verbatim}"#,
+        "Foo",
+        "Put each sentence on its own line",
+        None,
+    );
+}
+
+#[test]
+fn test_break_sentences_table() {
+    check_edit(
+        r#"
+%! main.tex
+\begin{longtable*}{ll}
+Foo. Bar & Baz
+\end{longtable*}"#,
+        "Foo",
+        "Put each sentence on its own line",
+        None,
+    );
+}
@@ -0,0 +1,74 @@
+use rowan::ast::AstNode;
+use syntax::latex::{self, HasCurly};
+
+use crate::{
+    CodeAction, CodeActionKind, CodeActionParams, DocumentChange,
+    util::{environment_name, expand_to_line, find_environment, trim_trivia},
+};
+
+/// Removes the environment or the command around the cursor but keeps its content.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    let document = params.feature.document;
+    let data = document.data.as_tex()?;
+    let root = data.root_node();
+    let offset = params.range.start();
+
+    let command = root
+        .token_at_offset(offset)
+        .right_biased()
+        .into_iter()
+        .flat_map(|token| token.parent_ancestors())
+        .filter_map(latex::GenericCommand::cast)
+        .find_map(|command| {
+            let mut groups = command.syntax().children();
+            let group = groups.next().and_then(latex::CurlyGroup::cast)?;
+            groups.next().is_none().then_some((command, group))
+        });
+
+    if let Some((command, group)) = command {
+        let name = command.name()?;
+        let start = trim_trivia(command.syntax()).start();
+        let left_curly = group.left_curly()?.text_range();
+        let right_curly = group.right_curly()?.text_range();
+        actions.push(CodeAction {
+            title: format!("Unwrap {}", name.text()),
+            kind: CodeActionKind::RefactorRewrite,
+            changes: vec![
+                DocumentChange::Edit {
+                    uri: document.uri.clone(),
+                    range: left_curly.cover_offset(start),
+                    text: String::new(),
+                },
+                DocumentChange::Edit {
+                    uri: document.uri.clone(),
+                    range: right_curly,
+                    text: String::new(),
+                },
+            ],
+        });
+    }
+
+    if let Some(environment) = find_environment(&root, offset) {
+        let name = environment_name(&environment)?.to_string();
+        let begin = trim_trivia(environment.begin()?.syntax());
+        let end = trim_trivia(environment.end()?.syntax());
+        actions.push(CodeAction {
+            title: format!("Unwrap {name} environment"),
+            kind: CodeActionKind::RefactorRewrite,
+            changes: vec![
+                DocumentChange::Edit {
+                    uri: document.uri.clone(),
+                    range: expand_to_line(&document.text, begin),
+                    text: String::new(),
+                },
+                DocumentChange::Edit {
+                    uri: document.uri.clone(),
+                    range: expand_to_line(&document.text, end),
+                    text: String::new(),
+                },
+            ],
+        });
+    }
+
+    Some(())
+}
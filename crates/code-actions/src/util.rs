@@ -0,0 +1,110 @@
+use rowan::{NodeOrToken, TextRange, TextSize, ast::AstNode};
+use syntax::latex;
+
+/// Removes the whitespace around the selection and ignores empty selections.
+pub fn trim_range(text: &str, range: TextRange) -> Option<TextRange> {
+    let selection = &text[range];
+    let trimmed = selection.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let leading = selection.len() - selection.trim_start().len();
+    let start = range.start() + TextSize::try_from(leading).ok()?;
+    Some(TextRange::at(start, TextSize::of(trimmed)))
+}
+
+/// Checks that the selection does not cut through a command or a group.
+pub fn is_balanced(root: &latex::SyntaxNode, range: TextRange) -> bool {
+    let element = root.covering_element(range);
+    if element
+        .ancestors()
+        .any(|node| matches!(node.kind(), latex::KEY | latex::CURLY_GROUP_WORD_LIST))
+    {
+        return false;
+    }
+
+    match element {
+        NodeOrToken::Token(token) => token.text_range() == range,
+        NodeOrToken::Node(node) => node
+            .children_with_tokens()
+            .map(|child| match child {
+                NodeOrToken::Node(node) => trim_trivia(&node),
+                NodeOrToken::Token(token) => token.text_range(),
+            })
+            .filter(|child| {
+                child
+                    .intersect(range)
+                    .is_some_and(|common| !common.is_empty())
+            })
+            .all(|child| range.contains_range(child)),
+    }
+}
+
+/// Removes the trailing whitespace and comments that the parser attaches to a node.
+pub fn trim_trivia(node: &latex::SyntaxNode) -> TextRange {
+    let start = node.text_range().start();
+    let end = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !matches!(token.kind(), latex::WHITESPACE | latex::COMMENT))
+        .last()
+        .map_or(start, |token| token.text_range().end());
+
+    TextRange::new(start, end)
+}
+
+/// Extends the range to the whole line if nothing else is on it, including the line break.
+pub fn expand_to_line(text: &str, range: TextRange) -> TextRange {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = text[end..]
+        .find('\n')
+        .map_or(text.len(), |index| end + index + 1);
+
+    if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty() {
+        TextRange::new(
+            TextSize::try_from(line_start).unwrap(),
+            TextSize::try_from(line_end).unwrap(),
+        )
+    } else {
+        range
+    }
+}
+
+/// Returns the whitespace in front of `offset` if it is the first character of its line.
+pub fn indentation(text: &str, offset: TextSize) -> Option<&str> {
+    let offset = usize::from(offset);
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let indent = &text[line_start..offset];
+    indent.trim().is_empty().then_some(indent)
+}
+
+pub fn escape_snippet(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '$' | '}') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Finds the innermost environment around `offset` that is not the `document` environment.
+pub fn find_environment(root: &latex::SyntaxNode, offset: TextSize) -> Option<latex::Environment> {
+    root.token_at_offset(offset)
+        .right_biased()?
+        .parent_ancestors()
+        .filter_map(latex::Environment::cast)
+        .find(|environment| {
+            environment_name(environment).is_some_and(|name| name.to_string() != "document")
+        })
+}
+
+pub fn environment_name(environment: &latex::Environment) -> Option<latex::Key> {
+    environment.begin()?.name()?.key()
+}
@@ -0,0 +1,92 @@
+use base_db::FeatureParams;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    CodeAction, CodeActionKind, CodeActionParams, DocumentChange,
+    util::{escape_snippet, indentation, is_balanced, trim_range},
+};
+
+/// The commands that are offered for wrapping a selection.
+static COMMANDS: &[&str] = &["emph", "textbf", "textit", "texttt", "textsc", "underline"];
+
+/// Wraps the selection in an environment or a command, letting the user pick its name.
+pub fn find_actions(params: &CodeActionParams, actions: &mut Vec<CodeAction>) -> Option<()> {
+    let document = params.feature.document;
+    let data = document.data.as_tex()?;
+    let range = trim_range(&document.text, params.range)?;
+    if !is_balanced(&data.root_node(), range) {
+        return None;
+    }
+
+    let selection = &document.text[range];
+    let environments = find_environment_names(&params.feature);
+    if let Some(default) = environments.first() {
+        let (separator, indent) = match indentation(&document.text, range.start()) {
+            Some(indent) => ("\n", indent),
+            None => ("", ""),
+        };
+
+        let choices = environments.join(",");
+        let snippet = format!(
+            "\\\\begin{{${{1|{choices}|}}}}{separator}{}{separator}{indent}\\\\end{{$1}}",
+            escape_snippet(selection),
+        );
+
+        let text = format!(
+            "\\begin{{{default}}}{separator}{selection}{separator}{indent}\\end{{{default}}}"
+        );
+
+        actions.push(CodeAction {
+            title: String::from("Wrap in environment"),
+            kind: CodeActionKind::RefactorRewrite,
+            changes: vec![DocumentChange::Snippet {
+                uri: document.uri.clone(),
+                range,
+                snippet,
+                text,
+            }],
+        });
+    }
+
+    let choices = COMMANDS.join(",");
+    actions.push(CodeAction {
+        title: String::from("Wrap in command"),
+        kind: CodeActionKind::RefactorRewrite,
+        changes: vec![DocumentChange::Snippet {
+            uri: document.uri.clone(),
+            range,
+            snippet: format!("\\\\${{1|{choices}|}}{{{}}}", escape_snippet(selection)),
+            text: format!("\\{}{{{selection}}}", COMMANDS[0]),
+        }],
+    });
+
+    Some(())
+}
+
+/// Collects the environments of the project followed by the ones of the included packages.
+pub fn find_environment_names<'a>(params: &'a FeatureParams<'a>) -> Vec<&'a str> {
+    let documents = params.project.documents.iter();
+    let project = documents
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| {
+            let used = data.semantics.environments.iter().map(|name| &name.text);
+            let theorems = data
+                .semantics
+                .theorem_definitions
+                .iter()
+                .map(|theorem| &theorem.name.text);
+
+            used.chain(theorems).map(String::as_str)
+        });
+
+    let packages = completion_data::included_packages(params)
+        .flat_map(|package| package.environments.iter().copied());
+
+    let mut visited = FxHashSet::default();
+    project
+        .chain(packages)
+        .filter(|name| *name != "document")
+        .filter(|name| !name.contains([',', '|', '$', '}', '\\']))
+        .filter(|name| visited.insert(*name))
+        .collect()
+}
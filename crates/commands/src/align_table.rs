@@ -0,0 +1,557 @@
+use base_db::Document;
+use rowan::{TextRange, TextSize, ast::AstNode};
+use syntax::latex;
+
+/// The environments whose columns are separated by `&` and whose rows end with `\\`.
+pub const TABLE_ENVIRONMENTS: &[&str] = &[
+    "tabular",
+    "tabular*",
+    "tabularx",
+    "tabulary",
+    "longtable",
+    "longtable*",
+    "array",
+    "align",
+    "align*",
+    "alignat",
+    "alignat*",
+    "flalign",
+    "flalign*",
+    "matrix",
+    "pmatrix",
+    "bmatrix",
+    "Bmatrix",
+    "vmatrix",
+    "Vmatrix",
+    "smallmatrix",
+];
+
+/// Replaces the body of a table with its aligned version.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AlignTableEdit {
+    pub range: TextRange,
+    pub text: String,
+}
+
+/// Aligns the innermost table that contains `position`.
+pub fn align_table(document: &Document, position: TextSize) -> Option<AlignTableEdit> {
+    let root = document.data.as_tex()?.root_node();
+    root.token_at_offset(position)
+        .right_biased()?
+        .parent_ancestors()
+        .filter_map(latex::Environment::cast)
+        .find(is_table)
+        .and_then(|environment| align_environment(&document.text, &environment))
+}
+
+/// Aligns all tables that intersect with `range`.
+/// Nested tables are only aligned if the outer table is left untouched.
+pub fn align_tables(document: &Document, range: TextRange) -> Vec<AlignTableEdit> {
+    let mut edits = Vec::new();
+    if let Some(data) = document.data.as_tex() {
+        collect_edits(&document.text, &data.root_node(), range, &mut edits);
+    }
+
+    edits
+}
+
+fn collect_edits(
+    text: &str,
+    node: &latex::SyntaxNode,
+    range: TextRange,
+    edits: &mut Vec<AlignTableEdit>,
+) {
+    for child in node.children() {
+        if child.text_range().intersect(range).is_none() {
+            continue;
+        }
+
+        let edit = latex::Environment::cast(child.clone())
+            .filter(is_table)
+            .filter(|environment| latex::small_range(environment).intersect(range).is_some())
+            .and_then(|environment| align_environment(text, &environment));
+
+        match edit {
+            Some(edit) => edits.push(edit),
+            None => collect_edits(text, &child, range, edits),
+        }
+    }
+}
+
+fn is_table(environment: &latex::Environment) -> bool {
+    environment
+        .begin()
+        .and_then(|begin| begin.name())
+        .and_then(|name| name.key())
+        .is_some_and(|name| TABLE_ENVIRONMENTS.contains(&name.to_string().as_str()))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Separator {
+    Column,
+    Row,
+    Comment,
+}
+
+#[derive(Debug)]
+struct Row<'a> {
+    indent: &'a str,
+    /// The cells after the first one start with a space unless they start with `=` to keep `&=` together.
+    cells: Vec<String>,
+    suffix: &'a str,
+}
+
+#[derive(Debug)]
+enum Line<'a> {
+    Verbatim(&'a str),
+    Row(Row<'a>),
+}
+
+/// Computes the aligned body of a table. The lines that contain `\begin` and `\end` are kept as-is.
+/// Returns `None` if a row spans multiple lines or the table is already aligned.
+fn align_environment(text: &str, environment: &latex::Environment) -> Option<AlignTableEdit> {
+    let begin_end = latex::small_range(&environment.begin()?).end();
+    let end_start = latex::small_range(&environment.end()?).start();
+    let start =
+        begin_end + TextSize::try_from(text[usize::from(begin_end)..].find('\n')? + 1).ok()?;
+    let line_start = text[..usize::from(end_start)].rfind('\n')? + 1;
+    let end = TextSize::try_from(line_start).ok()?;
+    if start > end || !text[line_start..usize::from(end_start)].trim().is_empty() {
+        return None;
+    }
+
+    let body = TextRange::new(start, end);
+    let separators = find_separators(environment.syntax(), body)?;
+    let lines = parse_lines(text, body, &separators)?;
+    let new_text = render_lines(&lines);
+    if new_text == text[body] {
+        return None;
+    }
+
+    Some(AlignTableEdit {
+        range: body,
+        text: new_text,
+    })
+}
+
+/// Finds the column and row separators that belong to the table itself.
+/// Separators inside of groups or nested environments are ignored.
+/// Returns `None` if a nested group spans multiple lines.
+fn find_separators(
+    environment: &latex::SyntaxNode,
+    body: TextRange,
+) -> Option<Vec<(TextSize, Separator)>> {
+    let mut separators = Vec::new();
+    let mut preorder = environment.preorder_with_tokens();
+    while let Some(event) = preorder.next() {
+        let rowan::WalkEvent::Enter(element) = event else {
+            continue;
+        };
+
+        let overlaps = body
+            .intersect(element.text_range())
+            .is_some_and(|range| !range.is_empty());
+
+        if !overlaps {
+            continue;
+        }
+
+        match element {
+            rowan::NodeOrToken::Node(node) => {
+                if &node != environment && is_nested(&node) {
+                    if spans_lines(&node) {
+                        return None;
+                    }
+
+                    preorder.skip_subtree();
+                }
+            }
+            rowan::NodeOrToken::Token(token) => match token.kind() {
+                latex::WORD => {
+                    let start = token.text_range().start();
+                    for (index, _) in token.text().match_indices('&') {
+                        let offset = start + TextSize::try_from(index).ok()?;
+                        separators.push((offset, Separator::Column));
+                    }
+                }
+                latex::COMMAND_NAME if token.text() == "\\\\" => {
+                    separators.push((token.text_range().start(), Separator::Row));
+                }
+                latex::COMMENT => {
+                    separators.push((token.text_range().start(), Separator::Comment));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    Some(separators)
+}
+
+/// Checks whether a node contains a line break, ignoring its trailing whitespace and comments.
+fn spans_lines(node: &latex::SyntaxNode) -> bool {
+    let end = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !matches!(token.kind(), latex::WHITESPACE | latex::COMMENT))
+        .last()
+        .map_or(node.text_range().start(), |token| token.text_range().end());
+
+    let range = TextRange::new(node.text_range().start(), end);
+    node.text()
+        .slice(range - node.text_range().start())
+        .contains_char('\n')
+}
+
+fn is_nested(node: &latex::SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        latex::CURLY_GROUP
+            | latex::CURLY_GROUP_WORD
+            | latex::CURLY_GROUP_WORD_LIST
+            | latex::CURLY_GROUP_COMMAND
+            | latex::CURLY_GROUP_KEY_VALUE
+            | latex::BRACK_GROUP
+            | latex::BRACK_GROUP_WORD
+            | latex::BRACK_GROUP_KEY_VALUE
+            | latex::PAREN_GROUP
+            | latex::MIXED_GROUP
+            | latex::ENVIRONMENT
+            | latex::FORMULA
+            | latex::EQUATION
+    )
+}
+
+/// Splits the body into lines and the lines into cells.
+/// Lines without separators like `\hline` are kept as-is.
+fn parse_lines<'a>(
+    text: &'a str,
+    body: TextRange,
+    separators: &[(TextSize, Separator)],
+) -> Option<Vec<Line<'a>>> {
+    let mut lines = Vec::new();
+    let mut has_open_row = false;
+    let mut line_start = usize::from(body.start());
+    for line in text[body].split_inclusive('\n') {
+        let line_end = line_start + line.trim_end_matches(['\r', '\n']).len();
+        let next_line_start = line_start + line.len();
+        let line_separators: Vec<_> = separators
+            .iter()
+            .map(|&(offset, kind)| (usize::from(offset), kind))
+            .filter(|&(offset, _)| offset >= line_start && offset < line_end)
+            .collect();
+
+        let columns: Vec<_> = line_separators
+            .iter()
+            .filter(|(_, kind)| *kind == Separator::Column)
+            .map(|(offset, _)| *offset)
+            .collect();
+
+        let rows: Vec<_> = line_separators
+            .iter()
+            .filter(|(_, kind)| *kind == Separator::Row)
+            .map(|(offset, _)| *offset)
+            .collect();
+
+        let comment = line_separators
+            .iter()
+            .find(|(_, kind)| *kind == Separator::Comment)
+            .map(|(offset, _)| *offset);
+
+        if columns.is_empty() && rows.is_empty() {
+            lines.push(Line::Verbatim(&text[line_start..next_line_start]));
+            line_start = next_line_start;
+            continue;
+        }
+
+        // Rows that span multiple lines or share a line cannot be aligned in a meaningful way.
+        if has_open_row
+            || rows.len() > 1
+            || rows
+                .first()
+                .is_some_and(|row| columns.iter().any(|column| column > row))
+        {
+            return None;
+        }
+
+        has_open_row = rows.is_empty();
+        let content_end = rows.first().copied().or(comment).unwrap_or(line_end);
+        let indent_end = line_start + (line.len() - line.trim_start().len());
+        let mut cell_start = indent_end;
+        let mut cells = Vec::new();
+        for offset in columns.into_iter().chain(std::iter::once(content_end)) {
+            let cell = text[cell_start..offset].trim();
+            if cell_start == indent_end || cell.starts_with('=') {
+                cells.push(cell.to_string());
+            } else {
+                cells.push(format!(" {cell}"));
+            }
+
+            cell_start = offset + 1;
+        }
+
+        lines.push(Line::Row(Row {
+            indent: &text[line_start..indent_end],
+            cells,
+            suffix: text[content_end..line_end].trim_end(),
+        }));
+
+        line_start = next_line_start;
+    }
+
+    Some(lines)
+}
+
+fn render_lines(lines: &[Line]) -> String {
+    let rows: Vec<_> = lines
+        .iter()
+        .filter_map(|line| match line {
+            Line::Row(row) => Some(row),
+            Line::Verbatim(_) => None,
+        })
+        .collect();
+
+    let widths = column_widths(&rows);
+    let indent = rows.first().map_or("", |row| row.indent);
+
+    let mut output = String::new();
+    for line in lines {
+        let row = match line {
+            Line::Verbatim(text) => {
+                output.push_str(text);
+                continue;
+            }
+            Line::Row(row) => row,
+        };
+
+        let mut text = String::from(indent);
+        let mut column = 0;
+        for (index, cell) in row.cells.iter().enumerate() {
+            if index > 0 {
+                text.push_str(" &");
+            }
+
+            let span = column_span(cell);
+            let width = spanned_width(&widths, column, span);
+            text.push_str(cell);
+            text.extend(std::iter::repeat_n(
+                ' ',
+                width.saturating_sub(char_count(cell)),
+            ));
+            column += span;
+        }
+
+        if row.suffix.is_empty() {
+            text.truncate(text.trim_end().len());
+        } else {
+            text.push(' ');
+            text.push_str(row.suffix);
+        }
+
+        output.push_str(&text);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Computes the width of every column.
+/// Cells that span multiple columns widen the last column they cover if necessary.
+fn column_widths(rows: &[&Row]) -> Vec<usize> {
+    let mut widths = Vec::new();
+    let mut spanning_cells = Vec::new();
+    for row in rows {
+        let mut column = 0;
+        for cell in &row.cells {
+            let span = column_span(cell);
+            if widths.len() < column + span {
+                widths.resize(column + span, 0);
+            }
+
+            if span == 1 {
+                widths[column] = widths[column].max(char_count(cell));
+            } else {
+                spanning_cells.push((column, span, char_count(cell)));
+            }
+
+            column += span;
+        }
+    }
+
+    for (column, span, width) in spanning_cells {
+        let available = spanned_width(&widths, column, span);
+        if width > available {
+            widths[column + span - 1] += width - available;
+        }
+    }
+
+    widths
+}
+
+fn spanned_width(widths: &[usize], column: usize, span: usize) -> usize {
+    widths[column..column + span].iter().sum::<usize>() + 2 * (span - 1)
+}
+
+/// Returns the number of columns that a cell covers, which is only greater than one for `\multicolumn`.
+fn column_span(cell: &str) -> usize {
+    cell.trim_start()
+        .strip_prefix("\\multicolumn")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix('{'))
+        .and_then(|rest| rest.split_once('}'))
+        .and_then(|(count, _)| count.trim().parse().ok())
+        .filter(|&count| count > 0)
+        .unwrap_or(1)
+}
+
+fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{Expect, expect};
+    use rowan::TextRange;
+    use test_utils::fixture::Fixture;
+
+    use super::{align_table, align_tables};
+
+    fn check(input: &str, expect: Expect) {
+        let fixture = Fixture::parse(input);
+        let document = fixture.workspace.lookup(&fixture.documents[0].uri).unwrap();
+        let range = TextRange::up_to(document.text.len().try_into().unwrap());
+
+        let mut text = document.text.clone();
+        for edit in align_tables(document, range).into_iter().rev() {
+            text.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.text);
+        }
+
+        expect.assert_eq(&text);
+    }
+
+    #[test]
+    fn test_tabular() {
+        check(
+            r#"
+%! main.tex
+\begin{tabular}{lcr}
+  \hline
+  Name & Value & Unit \\ \hline
+  A \& B & 1 & m \\
+  Longer name&2&kg\\[2pt] % a comment
+  % a comment line
+  C & 3 \\
+\end{tabular}"#,
+            expect![[r#"
+                \begin{tabular}{lcr}
+                  \hline
+                  Name        & Value & Unit \\ \hline
+                  A \& B      & 1     & m    \\
+                  Longer name & 2     & kg   \\[2pt] % a comment
+                  % a comment line
+                  C           & 3     \\
+                \end{tabular}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_multicolumn() {
+        check(
+            r#"
+%! main.tex
+\begin{tabular}{ll}
+\multicolumn{2}{c}{A very long heading} \\
+a & b \\
+ccc & d
+\end{tabular}"#,
+            expect![[r#"
+                \begin{tabular}{ll}
+                \multicolumn{2}{c}{A very long heading} \\
+                a   & b                                 \\
+                ccc & d
+                \end{tabular}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_nested_groups() {
+        check(
+            r#"
+%! main.tex
+\begin{align*}
+  f(x) &= \frac{a & b}{c} \\
+  g &= \begin{pmatrix} 1 & 2 \end{pmatrix}
+\end{align*}"#,
+            expect![[r#"
+                \begin{align*}
+                  f(x) &= \frac{a & b}{c}                     \\
+                  g    &= \begin{pmatrix} 1 & 2 \end{pmatrix}
+                \end{align*}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_multiline_row() {
+        check(
+            r#"
+%! main.tex
+\begin{align}
+  f(x) &= \begin{cases}
+    1 & x > 0 \\
+    0 & x \leq 0
+  \end{cases} \\
+  g(x) &= 1
+\end{align}"#,
+            expect![[r#"
+                \begin{align}
+                  f(x) &= \begin{cases}
+                    1 & x > 0 \\
+                    0 & x \leq 0
+                  \end{cases} \\
+                  g(x) &= 1
+                \end{align}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_cells_after_row_separator() {
+        check(
+            r#"
+%! main.tex
+\begin{tabular}{ll}
+  a & bbb \\
+  a & b \\ c & d
+\end{tabular}"#,
+            expect![[r#"
+                \begin{tabular}{ll}
+                  a & bbb \\
+                  a & b \\ c & d
+                \end{tabular}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_position() {
+        let fixture = Fixture::parse(
+            r#"
+%! main.tex
+\begin{equation}
+  \begin{bmatrix}
+    1 & 22 \\
+    333 & 4
+       |
+  \end{bmatrix}
+\end{equation}"#,
+        );
+
+        let document = fixture.workspace.lookup(&fixture.documents[0].uri).unwrap();
+        let offset = fixture.documents[0].cursor.unwrap();
+        let edit = align_table(document, offset).unwrap();
+        assert_eq!(edit.text, "    1   & 22 \\\\\n    333 & 4\n");
+    }
+}
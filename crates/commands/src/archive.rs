@@ -0,0 +1,499 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use base_db::{
+    Document, Owner, Workspace,
+    deps::{self, EdgeData, ProjectRoot},
+    semantics::tex::Link,
+};
+use distro::Language;
+use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
+use syntax::latex::{self, HasCurly};
+use thiserror::Error;
+use url::Url;
+
+use crate::clean::{ARTIFACT_EXTENSIONS, AUXILIARY_EXTENSIONS, read_file_list};
+
+const GRAPHICS_EXTENSIONS: &[&str] = &["pdf", "png", "jpg", "jpeg", "eps", "ps", "svg"];
+
+/// The extensions of the files in the project directory that are expected to be part of the archive.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "tex", "sty", "cls", "bib", "bst", "bbl", "pdf", "png", "jpg", "jpeg", "eps", "ps", "svg",
+];
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("document '{0}' is not a local file")]
+    NotLocal(Url),
+
+    #[error("document '{0}' is not a LaTeX document")]
+    NotTex(Url),
+
+    #[error("unable to write the archive: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Collects the files that are needed to compile a project elsewhere, for example on arXiv.
+#[derive(Debug)]
+pub struct ArchiveCommand {
+    /// Maps the paths inside of the archive to the files on disk.
+    files: BTreeMap<PathBuf, PathBuf>,
+    missing: Vec<String>,
+    unreferenced: Vec<PathBuf>,
+    output_path: PathBuf,
+}
+
+impl ArchiveCommand {
+    pub fn new(
+        workspace: &Workspace,
+        uri: &Url,
+        output_path: Option<PathBuf>,
+    ) -> Result<Self, ArchiveError> {
+        let Some(document) = workspace.lookup(uri) else {
+            return Err(ArchiveError::NotTex(uri.clone()));
+        };
+
+        let document = deps::parents(workspace, document)
+            .into_iter()
+            .next()
+            .unwrap_or(document);
+
+        let root = ProjectRoot::from_document(workspace, document);
+        let (Some(path), Some(root)) = (&document.path, root) else {
+            return Err(ArchiveError::NotLocal(document.uri.clone()));
+        };
+
+        if document.data.as_tex().is_none() {
+            return Err(ArchiveError::NotTex(document.uri.clone()));
+        }
+
+        let Ok(base_dir) = root.compile_dir.to_file_path() else {
+            return Err(ArchiveError::NotLocal(document.uri.clone()));
+        };
+
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let graph = &workspace.graphs()[&document.uri];
+        let documents: Vec<_> = graph.preorder(workspace).collect();
+        let graphics_paths: Vec<_> = documents
+            .iter()
+            .filter_map(|document| document.data.as_tex())
+            .flat_map(|data| data.semantics.graphics_paths.iter().cloned())
+            .collect();
+
+        let mut collector = Collector {
+            workspace,
+            root: &root,
+            base_dir: &base_dir,
+            files: BTreeMap::default(),
+            missing: Vec::new(),
+        };
+
+        for document in &documents {
+            collector.add_document(document);
+            collector.add_missing_links(graph, document);
+            collector.add_graphics(document, &graphics_paths);
+            collector.add_bibliography_styles(document);
+        }
+
+        collector.add_file_list(&stem);
+
+        // Publishers expect the generated bibliography next to the root document.
+        let bbl_path = root.aux_dir.join(&format!("{stem}.bbl")).ok();
+        if let Some(bbl_path) = bbl_path
+            .and_then(|uri| uri.to_file_path().ok())
+            .filter(|path| path.is_file())
+        {
+            collector
+                .files
+                .insert(PathBuf::from(format!("{stem}.bbl")), bbl_path);
+        }
+
+        let Collector {
+            files, mut missing, ..
+        } = collector;
+
+        missing.sort();
+        missing.dedup();
+
+        let output_path =
+            output_path.unwrap_or_else(|| base_dir.join(format!("{stem}-submission.tar.gz")));
+
+        let unreferenced = find_unreferenced(&root, &base_dir, &stem, &files, &output_path);
+        Ok(Self {
+            files,
+            missing,
+            unreferenced,
+            output_path,
+        })
+    }
+
+    /// The paths of the files inside of the archive.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// The referenced files that could not be found or lie outside of the project directory.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// The files in the project directory that no document references.
+    pub fn unreferenced(&self) -> &[PathBuf] {
+        &self.unreferenced
+    }
+
+    /// Writes a `.tar.gz` archive if the output path has that extension and copies the files into a directory otherwise.
+    pub fn run(self) -> Result<PathBuf, ArchiveError> {
+        let target = self.output_path.as_path();
+        let name = target.to_string_lossy();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            let file = fs::File::create(target)?;
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            let mut builder = tar::Builder::new(encoder);
+            for (name, source) in &self.files {
+                builder.append_path_with_name(source, name)?;
+            }
+
+            builder.into_inner()?.finish()?;
+        } else {
+            for (name, source) in &self.files {
+                let destination = target.join(name);
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+
+                fs::copy(source, destination)?;
+            }
+        }
+
+        Ok(self.output_path)
+    }
+}
+
+struct Collector<'a> {
+    workspace: &'a Workspace,
+    root: &'a ProjectRoot,
+    base_dir: &'a Path,
+    files: BTreeMap<PathBuf, PathBuf>,
+    missing: Vec<String>,
+}
+
+impl Collector<'_> {
+    fn add_path(&mut self, path: PathBuf) {
+        if self.workspace.distro().file_name_db.contains(&path) {
+            return;
+        }
+
+        match path.strip_prefix(self.base_dir) {
+            Ok(name) => {
+                self.files.insert(name.to_path_buf(), path);
+            }
+            Err(_) => {
+                let message = format!("{} (outside of the project directory)", path.display());
+                self.missing.push(message);
+            }
+        }
+    }
+
+    fn add_document(&mut self, document: &Document) {
+        if document.owner == Owner::Distro
+            || !matches!(document.language, Language::Tex | Language::Bib)
+        {
+            return;
+        }
+
+        if let Some(path) = &document.path {
+            self.add_path(path.clone());
+        }
+    }
+
+    /// Reports the links that neither resolve to a document nor to a file of the distribution.
+    fn add_missing_links(&mut self, graph: &deps::Graph, document: &Document) {
+        let Some(data) = document.data.as_tex() else {
+            return;
+        };
+
+        for link in &data.semantics.links {
+            let resolved = graph.edges.iter().any(|edge| {
+                edge.source == document.uri
+                    && matches!(&edge.data, EdgeData::DirectLink(data) if data.link == *link)
+            });
+
+            if !resolved && !self.is_distro_link(link) {
+                self.missing.push(
+                    link.package_name()
+                        .unwrap_or_else(|| link.path.text.clone()),
+                );
+            }
+        }
+    }
+
+    fn is_distro_link(&self, link: &Link) -> bool {
+        let file_name_db = &self.workspace.distro().file_name_db;
+        let stem = &link.path.text;
+        std::iter::once(stem.clone())
+            .chain(
+                link.kind
+                    .extensions()
+                    .iter()
+                    .map(|extension| format!("{stem}.{extension}")),
+            )
+            .any(|name| file_name_db.get(&name).is_some())
+    }
+
+    /// Resolves the figures relative to the source directory and the `\graphicspath` entries.
+    fn add_graphics(&mut self, document: &Document, graphics_paths: &[String]) {
+        let Some(data) = document.data.as_tex() else {
+            return;
+        };
+
+        let Ok(src_dir) = self.root.src_dir.to_file_path() else {
+            return;
+        };
+
+        let includes = data
+            .root_node()
+            .descendants()
+            .filter_map(latex::Include::cast)
+            .filter(|include| {
+                matches!(
+                    include.syntax().kind(),
+                    latex::GRAPHICS_INCLUDE | latex::SVG_INCLUDE | latex::INKSCAPE_INCLUDE
+                )
+            });
+
+        for include in includes {
+            for key in include.path_list().iter().flat_map(|list| list.keys()) {
+                let name = key.to_string();
+                let found = std::iter::once("")
+                    .chain(graphics_paths.iter().map(String::as_str))
+                    .flat_map(|dir| {
+                        let dir = src_dir.join(dir);
+                        let alternatives = GRAPHICS_EXTENSIONS
+                            .iter()
+                            .map(|extension| dir.join(format!("{name}.{extension}")));
+
+                        std::iter::once(dir.join(&name))
+                            .chain(alternatives)
+                            .collect::<Vec<_>>()
+                    })
+                    .find(|path| path.is_file());
+
+                match found {
+                    Some(path) => self.add_path(path),
+                    None => self.missing.push(key.to_string()),
+                }
+            }
+        }
+    }
+
+    fn add_bibliography_styles(&mut self, document: &Document) {
+        let Some(data) = document.data.as_tex() else {
+            return;
+        };
+
+        let Ok(src_dir) = self.root.src_dir.to_file_path() else {
+            return;
+        };
+
+        let styles = data
+            .root_node()
+            .descendants()
+            .filter_map(latex::GenericCommand::cast)
+            .filter(|command| {
+                command
+                    .name()
+                    .is_some_and(|name| name.text() == "\\bibliographystyle")
+            })
+            .filter_map(|command| {
+                command
+                    .syntax()
+                    .children()
+                    .find_map(latex::CurlyGroup::cast)
+            })
+            .filter_map(|group| group.content_text());
+
+        for style in styles {
+            let name = format!("{style}.bst");
+            let path = src_dir.join(&name);
+            if path.is_file() {
+                self.add_path(path);
+            } else if self.workspace.distro().file_name_db.get(&name).is_none() {
+                self.missing.push(name);
+            }
+        }
+    }
+
+    /// Adds the local inputs that the compiler has recorded in the `.fls` file, like data files of plots.
+    fn add_file_list(&mut self, stem: &str) -> Option<()> {
+        let uri = self.root.aux_dir.join(&format!("{stem}.fls")).ok()?;
+        let path = uri.to_file_path().ok()?;
+        let file_list = read_file_list(self.workspace, &path)?;
+        let working_dir = file_list.working_dir.as_deref().or(path.parent())?;
+        let outputs: FxHashSet<_> = file_list
+            .outputs
+            .iter()
+            .map(|output| working_dir.join(output))
+            .collect();
+
+        let inputs: Vec<_> = file_list
+            .inputs
+            .iter()
+            .map(|input| working_dir.join(input))
+            .filter(|input| input.starts_with(self.base_dir) && !outputs.contains(input))
+            .filter(|input| !has_extension(input, AUXILIARY_EXTENSIONS))
+            .filter(|input| input.is_file())
+            .collect();
+
+        for input in inputs {
+            self.add_path(input);
+        }
+
+        Some(())
+    }
+}
+
+fn find_unreferenced(
+    root: &ProjectRoot,
+    base_dir: &Path,
+    stem: &str,
+    files: &BTreeMap<PathBuf, PathBuf>,
+    output_path: &Path,
+) -> Vec<PathBuf> {
+    let sources: FxHashSet<_> = files.values().collect();
+    let output_dirs: Vec<_> = [&root.aux_dir, &root.log_dir, &root.pdf_dir]
+        .into_iter()
+        .filter_map(|dir| dir.to_file_path().ok())
+        .filter(|dir| dir != base_dir)
+        .chain(std::iter::once(output_path.to_path_buf()))
+        .collect();
+
+    let mut unreferenced = Vec::new();
+    let mut stack = vec![base_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                if !is_hidden && !output_dirs.contains(&path) {
+                    stack.push(path);
+                }
+
+                continue;
+            }
+
+            let is_artifact = path.parent() == Some(base_dir)
+                && path.file_stem().is_some_and(|name| name == stem)
+                && has_extension(&path, ARTIFACT_EXTENSIONS);
+
+            if has_extension(&path, SOURCE_EXTENSIONS) && !is_artifact && !sources.contains(&path) {
+                unreferenced.extend(path.strip_prefix(base_dir).ok().map(Path::to_path_buf));
+            }
+        }
+    }
+
+    unreferenced.sort();
+    unreferenced
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extensions
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use base_db::{Owner, Workspace};
+    use distro::{Distro, FileNameDB, Language};
+    use line_index::LineCol;
+    use url::Url;
+
+    use super::ArchiveCommand;
+
+    #[test]
+    fn test_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        let main = r#"\documentclass{article}
+\usepackage{mystyle}
+\usepackage{missing}
+\graphicspath{{figures/}}
+\begin{document}
+\input{chapter}
+\includegraphics{plot}
+\bibliographystyle{plain}
+\bibliography{refs}
+\end{document}"#;
+
+        let files = [
+            ("main.tex", main),
+            ("chapter.tex", "Chapter"),
+            ("mystyle.sty", ""),
+            ("refs.bib", ""),
+            ("main.bbl", ""),
+            ("main.aux", ""),
+            ("main.pdf", ""),
+            ("figures/plot.png", ""),
+            ("unused.png", ""),
+        ];
+
+        std::fs::create_dir(dir.join("figures")).unwrap();
+        let mut workspace = Workspace::default();
+        workspace.set_distro(Distro {
+            file_name_db: FileNameDB::from_iter([PathBuf::from("/texmf/article.cls")]),
+            ..Distro::default()
+        });
+
+        for (name, text) in files {
+            let path = dir.join(name);
+            std::fs::write(&path, text).unwrap();
+            if let Some(language @ (Language::Tex | Language::Bib)) = Language::from_path(&path) {
+                let uri = Url::from_file_path(&path).unwrap();
+                let cursor = LineCol { line: 0, col: 0 };
+                workspace.open(uri, text.into(), language, Owner::Client, cursor);
+            }
+        }
+
+        let uri = Url::from_file_path(dir.join("main.tex")).unwrap();
+        let target = dir.join("submission.tar.gz");
+        let command = ArchiveCommand::new(&workspace, &uri, Some(target.clone())).unwrap();
+        let files: Vec<_> = command.files().map(Path::to_path_buf).collect();
+        assert_eq!(
+            files,
+            [
+                "chapter.tex",
+                "figures/plot.png",
+                "main.bbl",
+                "main.tex",
+                "mystyle.sty",
+                "refs.bib"
+            ]
+            .map(Path::new)
+        );
+
+        assert_eq!(command.missing(), ["missing.sty", "plain.bst"]);
+        assert_eq!(command.unreferenced(), [Path::new("unused.png")]);
+
+        assert_eq!(command.run().unwrap(), target);
+        assert!(target.is_file());
+    }
+}
@@ -12,6 +12,7 @@ use base_db::{
 };
 use bstr::io::BufReadExt;
 use crossbeam_channel::Sender;
+use itertools::Itertools;
 use thiserror::Error;
 use url::Url;
 
@@ -25,6 +26,15 @@ pub enum BuildError {
     #[error("Document \"{0}\" does not exist on the local file system")]
     NotLocal(Url),
 
+    #[error(transparent)]
+    UnknownProfile(#[from] deps::UnknownProfileError),
+
+    #[error("Program \"{0}\" does not support continuous compilation")]
+    NotContinuous(String),
+
+    #[error("Argument \"{0}\" contains whitespace, which \"tectonic -X watch\" does not support")]
+    Whitespace(String),
+
     #[error("Unable to run compiler: {0}")]
     Compile(#[from] std::io::Error),
 }
@@ -33,11 +43,21 @@ pub enum BuildError {
 pub struct BuildCommand {
     program: String,
     args: Vec<String>,
+    env: Vec<(String, String)>,
     working_dir: PathBuf,
+    root_uri: Url,
+    log_uri: Option<Url>,
 }
 
 impl BuildCommand {
-    pub fn new(workspace: &Workspace, uri: &Url) -> Result<Self, BuildError> {
+    /// Prepares the build of the project containing the given document.
+    /// Without an explicit profile, the `% !TEX program` magic comment of the root document
+    /// and then the configured default profile are used.
+    pub fn new(
+        workspace: &Workspace,
+        uri: &Url,
+        profile: Option<&str>,
+    ) -> Result<Self, BuildError> {
         let Some(document) = workspace.lookup(uri) else {
             return Err(BuildError::NotFound(uri.clone()));
         };
@@ -56,22 +76,94 @@ impl BuildCommand {
         };
 
         let config = &workspace.config().build;
-        let program = config.program.clone();
-        let args = replace_placeholders(&config.args, &[('f', path)]);
+        let profile = deps::find_profile(config, document, profile)?;
+        let (program, args, env) = match profile {
+            Some(profile) => (&profile.program, &profile.args, profile.env.clone()),
+            None => (&config.program, &config.args, Vec::new()),
+        };
+
+        let program = program.clone();
+        let args = replace_placeholders(args, &[('f', path)]);
 
-        let root = ProjectRoot::walk_and_find(workspace, document_dir);
+        let mut root = ProjectRoot::walk_and_find(workspace, document_dir);
+        if let Some(profile) = profile {
+            log::debug!("Using build profile \"{}\"", profile.name);
+            root = root.with_profile(workspace, profile);
+        }
 
         let Ok(working_dir) = root.compile_dir.to_file_path() else {
             return Err(BuildError::NotLocal(document.uri.clone()));
         };
 
+        let log_uri = config
+            .output_filename
+            .as_deref()
+            .map(Path::new)
+            .or(document.path.as_deref())
+            .and_then(Path::file_stem)
+            .and_then(|stem| {
+                root.log_dir
+                    .join(&format!("{}.log", stem.to_string_lossy()))
+                    .ok()
+            });
+
         Ok(Self {
             program,
             args,
+            env,
             working_dir,
+            root_uri: document.uri.clone(),
+            log_uri,
         })
     }
 
+    /// Turns the command into a long-running process that recompiles the document whenever its inputs change.
+    pub fn continuous(mut self) -> Result<Self, BuildError> {
+        let name = Path::new(&self.program)
+            .file_stem()
+            .and_then(|stem| stem.to_str());
+
+        match name {
+            Some("latexmk") => {
+                let flags = ["-pvc", "-view=none"].map(String::from);
+                self.args.splice(0..0, flags);
+            }
+            Some("tectonic") => {
+                // `tectonic -X watch` reruns the given command, which keeps the arguments of the profile.
+                // The command is passed as a single string that is split at whitespace again.
+                if let Some(arg) = self
+                    .args
+                    .iter()
+                    .find(|arg| arg.contains(char::is_whitespace))
+                {
+                    return Err(BuildError::Whitespace(arg.clone()));
+                }
+
+                let args = match self.args.first().map(String::as_str) {
+                    Some("-X") => self.args[1..].join(" "),
+                    _ => std::iter::once("compile")
+                        .chain(self.args.iter().map(String::as_str))
+                        .join(" "),
+                };
+
+                self.args = vec!["-X".into(), "watch".into(), "-x".into(), args];
+            }
+            _ => return Err(BuildError::NotContinuous(self.program)),
+        }
+
+        Ok(self)
+    }
+
+    /// The root document of the project that is being compiled.
+    pub fn root_uri(&self) -> &Url {
+        &self.root_uri
+    }
+
+    /// The expected location of the log file, which the errors found while building belong to.
+    pub fn log_uri(&self) -> Option<&Url> {
+        self.log_uri.as_ref()
+    }
+
     pub fn spawn(self, sender: Sender<String>) -> Result<Child, BuildError> {
         log::debug!(
             "Spawning compiler {} {:#?} in directory {}",
@@ -92,6 +184,7 @@ impl BuildCommand {
     fn spawn_internal(&self) -> std::io::Result<Child> {
         std::process::Command::new(&self.program)
             .args(self.args.clone())
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -104,6 +197,7 @@ impl BuildCommand {
         use std::os::unix::process::CommandExt;
         std::process::Command::new(&self.program)
             .args(self.args.clone())
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -146,3 +240,106 @@ fn track_output(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use base_db::{BuildConfig, BuildProfile, Config};
+    use test_utils::fixture::Fixture;
+
+    use super::{BuildCommand, BuildError};
+
+    fn build(fixture: &str, profile: Option<&str>) -> Result<BuildCommand, BuildError> {
+        let mut fixture = Fixture::parse(fixture);
+        let profile_names = ["draft", "lualatex", "camera-ready"];
+        let profiles = profile_names
+            .into_iter()
+            .map(|name| BuildProfile {
+                name: name.into(),
+                program: format!("build-{name}"),
+                args: vec!["%f".into()],
+                ..BuildProfile::default()
+            })
+            .collect();
+
+        fixture.workspace.set_config(Config {
+            build: BuildConfig {
+                profiles,
+                default_profile: Some("draft".into()),
+                ..BuildConfig::default()
+            },
+            ..Config::default()
+        });
+
+        let uri = &fixture.documents[0].uri;
+        BuildCommand::new(&fixture.workspace, uri, profile)
+    }
+
+    #[test]
+    fn test_default_profile() {
+        let command = build("%! main.tex\n\\documentclass{article}", None).unwrap();
+        assert_eq!(command.program, "build-draft");
+    }
+
+    #[test]
+    fn test_magic_comment() {
+        let fixture = "%! main.tex\n% !TEX program = LuaLaTeX\n\\documentclass{article}";
+        let command = build(fixture, None).unwrap();
+        assert_eq!(command.program, "build-lualatex");
+    }
+
+    #[test]
+    fn test_magic_comment_after_header() {
+        let fixture = "%! main.tex\n\\documentclass{article}\n% !TEX program = lualatex";
+        let command = build(fixture, None).unwrap();
+        assert_eq!(command.program, "build-draft");
+    }
+
+    #[test]
+    fn test_explicit_profile() {
+        let fixture = "%! main.tex\n% !TEX program = lualatex\n\\documentclass{article}";
+        let command = build(fixture, Some("camera-ready")).unwrap();
+        assert_eq!(command.program, "build-camera-ready");
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let result = build("%! main.tex\n\\documentclass{article}", Some("final"));
+        assert!(matches!(result, Err(BuildError::UnknownProfile(error)) if error.0 == "final"));
+    }
+
+    #[test]
+    fn test_continuous_unsupported() {
+        let command = build("%! main.tex\n\\documentclass{article}", None).unwrap();
+        let result = command.continuous();
+        assert!(matches!(result, Err(BuildError::NotContinuous(name)) if name == "build-draft"));
+    }
+
+    #[test]
+    fn test_continuous_tectonic() {
+        let mut command = build("%! main.tex\n\\documentclass{article}", None).unwrap();
+        command.program = "tectonic".into();
+        command.args = vec!["--synctex".into(), "main.tex".into()];
+        let command = command.continuous().unwrap();
+        assert_eq!(
+            command.args,
+            ["-X", "watch", "-x", "compile --synctex main.tex"]
+        );
+    }
+
+    #[test]
+    fn test_continuous_tectonic_whitespace() {
+        let mut command = build("%! main.tex\n\\documentclass{article}", None).unwrap();
+        command.program = "tectonic".into();
+        command.args = vec!["--synctex".into(), "my thesis.tex".into()];
+        let result = command.continuous();
+        assert!(matches!(result, Err(BuildError::Whitespace(arg)) if arg == "my thesis.tex"));
+    }
+
+    #[test]
+    fn test_continuous_latexmk() {
+        let mut command = build("%! main.tex\n\\documentclass{article}", None).unwrap();
+        command.program = "latexmk".into();
+        let command = command.continuous().unwrap();
+        assert_eq!(command.args[..2], ["-pvc", "-view=none"]);
+    }
+}
@@ -0,0 +1,515 @@
+use syntax::{BuildError, BuildErrorLevel};
+
+/// The maximum length of a line before TeX wraps it.
+const MAX_LINE_LENGTH: usize = 79;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BuildPass {
+    Latex,
+    Bibtex,
+    Biber,
+    Makeindex,
+}
+
+impl BuildPass {
+    fn detect(line: &str) -> Option<Self> {
+        let index = line.find("This is ")?;
+        let program = &line[index + "This is ".len()..];
+        if program.contains("BibTeX") {
+            Some(Self::Bibtex)
+        } else if program.starts_with("Biber") {
+            Some(Self::Biber)
+        } else if ["makeindex", "upmendex", "mendex", "xindy"]
+            .iter()
+            .any(|name| program.starts_with(name))
+        {
+            Some(Self::Makeindex)
+        } else if index == 0 && program.split([',', ' ']).next()?.ends_with("TeX") {
+            Some(Self::Latex)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuildEvent {
+    /// A new pass has started; `run` counts the passes of the same kind, starting at one.
+    Pass { pass: BuildPass, run: u32 },
+
+    /// The compiler has shipped out the given page.
+    Page(u32),
+
+    /// The errors have changed. Contains the errors of the current LaTeX pass,
+    /// which replace the errors of the previous passes once the pass has finished.
+    /// A continuous build reports the errors of each round when the round has finished.
+    Errors(Vec<BuildError>),
+
+    /// A continuous build has finished a round and waits for changes.
+    Finished,
+}
+
+/// Watches the output of a running build and reports its progress and errors as they appear.
+#[derive(Debug, Default)]
+pub struct BuildMonitor {
+    pass: Option<BuildPass>,
+    runs: Vec<(BuildPass, u32)>,
+    page: u32,
+    output: String,
+    files: Vec<Option<String>>,
+    pending_error: bool,
+    pending_warning: bool,
+    errors: Vec<BuildError>,
+    reported: Vec<BuildError>,
+}
+
+impl BuildMonitor {
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    pub fn count(&self, level: BuildErrorLevel) -> usize {
+        self.errors
+            .iter()
+            .filter(|error| error.level == level)
+            .count()
+    }
+
+    pub fn process_line(&mut self, line: &str) -> Vec<BuildEvent> {
+        let mut events = Vec::new();
+        if is_watching(line) {
+            self.finish_pass(&mut events);
+            self.pass = None;
+            if self.errors.is_empty() && !matches!(events.last(), Some(BuildEvent::Errors(_))) {
+                // Clears the errors of the previous round if this one had none.
+                events.push(BuildEvent::Errors(Vec::new()));
+            }
+
+            self.reported = std::mem::take(&mut self.errors);
+            events.push(BuildEvent::Finished);
+            return events;
+        }
+
+        if let Some(pass) = BuildPass::detect(line) {
+            self.finish_pass(&mut events);
+            if pass == BuildPass::Latex {
+                // Every pass reports all of its messages again, so the previous ones are outdated.
+                self.errors.clear();
+            }
+
+            self.pass = Some(pass);
+            self.page = 0;
+            let run = match self.runs.iter_mut().find(|(kind, _)| *kind == pass) {
+                Some((_, run)) => {
+                    *run += 1;
+                    *run
+                }
+                None => {
+                    self.runs.push((pass, 1));
+                    1
+                }
+            };
+
+            events.push(BuildEvent::Pass { pass, run });
+        }
+
+        if self.pass != Some(BuildPass::Latex) {
+            return events;
+        }
+
+        self.output.push_str(line);
+        self.output.push('\n');
+
+        if let Some(page) = find_last_page(line).filter(|page| *page > self.page) {
+            self.page = page;
+            events.push(BuildEvent::Page(page));
+        }
+
+        if line.starts_with('!') {
+            self.pending_error = true;
+        } else if is_line_reference(line) {
+            self.pending_error = false;
+            self.update_errors(&mut events);
+        }
+
+        if line.contains("Warning:")
+            || line.starts_with("Overfull")
+            || line.starts_with("Underfull")
+            || is_package_continuation(line)
+        {
+            self.pending_warning = true;
+        }
+
+        // Warnings may be wrapped across several lines, so they are only complete after a short line.
+        if self.pending_warning && !self.pending_error && line.chars().count() != MAX_LINE_LENGTH {
+            self.pending_warning = false;
+            self.update_errors(&mut events);
+        }
+
+        events
+    }
+
+    /// Processes the remaining output after the compiler has exited.
+    pub fn finish(&mut self) -> Vec<BuildEvent> {
+        let mut events = Vec::new();
+        self.finish_pass(&mut events);
+        events
+    }
+
+    fn finish_pass(&mut self, events: &mut Vec<BuildEvent>) {
+        if self.pass == Some(BuildPass::Latex) {
+            self.update_errors(events);
+            if self.errors != self.reported {
+                self.report_errors(events);
+            }
+        }
+
+        self.output.clear();
+        self.files.clear();
+        self.pending_error = false;
+        self.pending_warning = false;
+    }
+
+    /// Parses the output since the last update, so that the whole log is not parsed again after every message.
+    fn update_errors(&mut self, events: &mut Vec<BuildEvent>) {
+        // Reopening the enclosing files lets the parser attribute the messages to them.
+        let mut text = String::new();
+        for file in &self.files {
+            text.push('(');
+            text.push_str(file.as_deref().unwrap_or_default());
+            text.push('\n');
+        }
+
+        // The parser drops the final line break, which would hide a warning on the last line.
+        text.push_str(&self.output);
+        text.push('\n');
+        let log = parser::parse_build_log(&text);
+
+        // The last message is kept because lines like `(hyperref) ...` may still continue it.
+        let start = last_message_start(&self.output);
+        let consumed: String = self.output.drain(..start).collect();
+        track_files(&mut self.files, &consumed);
+
+        // Messages that were already reported by a previous pass are only updated after the pass.
+        let mut changed = false;
+        for error in log.errors {
+            if !self.errors.contains(&error) {
+                changed |= !self.reported.contains(&error);
+                self.errors.push(error);
+            }
+        }
+
+        if changed {
+            self.report_errors(events);
+        }
+    }
+
+    fn report_errors(&mut self, events: &mut Vec<BuildEvent>) {
+        self.reported = self.errors.clone();
+        events.push(BuildEvent::Errors(self.errors.clone()));
+    }
+}
+
+/// Finds the start of the last line, including the lines that TeX wrapped because they were too long.
+fn last_message_start(output: &str) -> usize {
+    let mut start = 0;
+    let mut offset = 0;
+    let mut wrapped = false;
+    for line in output.split_inclusive('\n') {
+        if !wrapped {
+            start = offset;
+        }
+
+        offset += line.len();
+        wrapped = line.trim_end_matches(['\r', '\n']).chars().count() == MAX_LINE_LENGTH;
+    }
+
+    start
+}
+
+/// Keeps track of the files that are open at the end of the given output, which TeX
+/// prints like `(./main.tex (./chapter.tex) ...)`. Other parentheses are tracked as well
+/// because the parser counts them when looking for the end of a file.
+fn track_files(files: &mut Vec<Option<String>>, output: &str) {
+    let mut text = String::new();
+    for line in output.lines() {
+        text.push_str(line);
+        if line.chars().count() != MAX_LINE_LENGTH {
+            text.push('\n');
+        }
+    }
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => files.push(find_file_name(&text[index + 1..])),
+            ')' => {
+                files.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn find_file_name(text: &str) -> Option<String> {
+    let text = &text[..text.find(['(', ')', '\r', '\n']).unwrap_or(text.len())];
+    let end = [".tex", ".sty", ".cls"]
+        .into_iter()
+        .filter_map(|extension| text.rfind(extension).map(|index| index + extension.len()))
+        .max()
+        .filter(|end| *end > 4)?;
+
+    Some(text[..end].to_string())
+}
+
+/// Checks for the messages that `latexmk -pvc` and `tectonic -X watch` print after each round.
+fn is_watching(line: &str) -> bool {
+    line.starts_with("=== Watching for updated files") || line.starts_with("[Finished running")
+}
+
+/// Checks for lines like `(hyperref)   removing ...` that continue a package warning.
+fn is_package_continuation(line: &str) -> bool {
+    line.strip_prefix('(')
+        .and_then(|rest| rest.split_once(')'))
+        .is_some_and(|(name, _)| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || c == '_' || c == '-')
+        })
+}
+
+fn is_line_reference(line: &str) -> bool {
+    line.strip_prefix("l.")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Finds the last page number in a line like `[1] [2{/usr/share/pdftex.map}] [3`.
+fn find_last_page(line: &str) -> Option<u32> {
+    let bytes = line.as_bytes();
+    let mut result = None;
+    for (index, _) in line.match_indices('[') {
+        let preceded = index == 0 || matches!(bytes[index - 1], b' ' | b']' | b')');
+        let digits = line[index + 1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(line.len() - index - 1);
+
+        let next = bytes.get(index + 1 + digits).copied();
+        let followed = matches!(next, None | Some(b']' | b'{' | b'<' | b' '));
+        if preceded && digits > 0 && followed {
+            result = line[index + 1..index + 1 + digits].parse().ok().or(result);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use syntax::BuildErrorLevel;
+
+    use super::{BuildEvent, BuildMonitor, BuildPass};
+
+    fn process(monitor: &mut BuildMonitor, output: &str) -> Vec<BuildEvent> {
+        output
+            .lines()
+            .flat_map(|line| monitor.process_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_passes() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "Rc files read:\n\
+             This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)\n\
+             This is BibTeX, Version 0.99d (TeX Live 2023)\n\
+             This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)\n\
+             INFO - This is Biber 2.19\n\
+             This is makeindex, version 2.17 [TeX Live 2023] (kpathsea + Thai support).",
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                BuildEvent::Pass {
+                    pass: BuildPass::Latex,
+                    run: 1
+                },
+                BuildEvent::Pass {
+                    pass: BuildPass::Bibtex,
+                    run: 1
+                },
+                BuildEvent::Pass {
+                    pass: BuildPass::Latex,
+                    run: 2
+                },
+                BuildEvent::Pass {
+                    pass: BuildPass::Biber,
+                    run: 1
+                },
+                BuildEvent::Pass {
+                    pass: BuildPass::Makeindex,
+                    run: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_continuous_rounds() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             === Watching for updated files. Use ctrl/C to stop ...\n\
+             This is pdfTeX, Version 3.141592653-2.6-1.40.25",
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                BuildEvent::Pass {
+                    pass: BuildPass::Latex,
+                    run: 1
+                },
+                BuildEvent::Errors(Vec::new()),
+                BuildEvent::Finished,
+                BuildEvent::Pass {
+                    pass: BuildPass::Latex,
+                    run: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pages() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             [1{/usr/share/texmf/fonts/map/pdftex/updmap/pdftex.map}]\n\
+             [2] [3\n\
+             ]",
+        );
+
+        assert_eq!(&events[1..], &[BuildEvent::Page(1), BuildEvent::Page(3)]);
+    }
+
+    #[test]
+    fn test_incremental_errors() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex\n\
+             ! Undefined control sequence.\n\
+             l.5 \\foo\n",
+        );
+
+        let Some(BuildEvent::Errors(errors)) = events.last() else {
+            panic!("expected errors: {events:?}");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Undefined control sequence.");
+        assert_eq!(errors[0].line, Some(4));
+
+        let events = process(
+            &mut monitor,
+            "LaTeX Warning: Reference `foo' on page 1 undefined on input line 7.\n",
+        );
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(monitor.count(BuildErrorLevel::Warning), 1);
+        assert!(monitor.finish().is_empty());
+    }
+
+    #[test]
+    fn test_errors_in_nested_files() {
+        let mut monitor = BuildMonitor::default();
+        process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex (./chapter.tex\n\
+             LaTeX Warning: Citation `foo' on page 1 undefined on input line 3.\n\
+             ) (./figure.tex)\n\
+             ! Undefined control sequence.\n\
+             l.5 \\foo\n\
+             Package hyperref Warning: Token not allowed in a PDF string\n\
+             (hyperref)                removing `\\foo' on input line 9.\n",
+        );
+
+        let errors: Vec<_> = monitor
+            .errors()
+            .iter()
+            .map(|error| (error.relative_path.to_str().unwrap(), error.line))
+            .collect();
+
+        assert_eq!(
+            errors,
+            [
+                ("./chapter.tex", Some(2)),
+                ("./main.tex", Some(4)),
+                ("./main.tex", Some(8))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_of_later_pass() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex\n\
+             LaTeX Warning: Reference `foo' on page 1 undefined on input line 3.\n\
+             LaTeX Warning: Citation `bar' on page 1 undefined on input line 4.\n\
+             )\n\
+             This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex\n\
+             LaTeX Warning: Citation `bar' on page 1 undefined on input line 4.\n\
+             )\n",
+        );
+
+        let errors: Vec<_> = events
+            .into_iter()
+            .chain(monitor.finish())
+            .filter_map(|event| match event {
+                BuildEvent::Errors(errors) => Some(errors.len()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(errors, [1, 2, 1]);
+        assert_eq!(monitor.errors()[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_continuous_rounds_fixed() {
+        let mut monitor = BuildMonitor::default();
+        let events = process(
+            &mut monitor,
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex\n\
+             LaTeX Warning: Reference `foo' on page 1 undefined on input line 3.\n\
+             )\n\
+             === Watching for updated files. Use ctrl/C to stop ...\n\
+             This is pdfTeX, Version 3.141592653-2.6-1.40.25\n\
+             (./main.tex)\n\
+             === Watching for updated files. Use ctrl/C to stop ...",
+        );
+
+        let errors: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                BuildEvent::Errors(errors) => Some(errors.len()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(errors, [1, 0]);
+        assert!(monitor.errors().is_empty());
+    }
+}
@@ -1,7 +1,46 @@
-use std::process::Stdio;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use base_db::{Document, Workspace, deps::ProjectRoot};
+use base_db::{
+    Document, Workspace,
+    deps::{self, EdgeData, ProjectRoot},
+};
+use distro::Language;
+use rustc_hash::FxHashSet;
+use syntax::file_list::FileList;
+use url::Url;
+
+/// The extensions of the intermediate files that the compiler and its helpers write.
+pub(crate) const AUXILIARY_EXTENSIONS: &[&str] = &[
+    "aux",
+    "bbl",
+    "bcf",
+    "blg",
+    "brf",
+    "fdb_latexmk",
+    "fls",
+    "glg",
+    "glo",
+    "gls",
+    "idx",
+    "ilg",
+    "ind",
+    "ist",
+    "lof",
+    "log",
+    "lot",
+    "nav",
+    "out",
+    "run.xml",
+    "snm",
+    "thm",
+    "toc",
+    "vrb",
+];
+
+/// The extensions of the documents that a build produces.
+pub(crate) const ARTIFACT_EXTENSIONS: &[&str] =
+    &["pdf", "dvi", "ps", "xdv", "synctex", "synctex.gz"];
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum CleanTarget {
@@ -9,59 +48,349 @@ pub enum CleanTarget {
     Artifacts,
 }
 
+/// Removes the files that compiling a project has produced without relying on `latexmk`.
 #[derive(Debug)]
 pub struct CleanCommand {
-    executable: String,
-    args: Vec<String>,
+    files: Vec<PathBuf>,
 }
 
 impl CleanCommand {
     pub fn new(workspace: &Workspace, document: &Document, target: CleanTarget) -> Result<Self> {
-        let Some(path) = document.path.as_deref() else {
+        if document.path.is_none() {
             anyhow::bail!("document '{}' is not a local file", document.uri)
-        };
+        }
+
+        let document = deps::parents(workspace, document)
+            .into_iter()
+            .next()
+            .unwrap_or(document);
 
-        let Some(document_dir) = &document.dir else {
+        let Some(root) = ProjectRoot::from_document(workspace, document) else {
             anyhow::bail!("document '{}' is not a local file", document.uri)
         };
+        let mut collector = Collector::new(&root, target);
+        collector.add_outputs(
+            document,
+            AUXILIARY_EXTENSIONS,
+            &[&root.aux_dir, &root.log_dir],
+        );
+        if target == CleanTarget::Artifacts {
+            collector.add_outputs(document, ARTIFACT_EXTENSIONS, &[&root.pdf_dir]);
+        }
 
-        let root = ProjectRoot::walk_and_find(workspace, document_dir);
+        if let Some(graph) = workspace.graphs().get(&document.uri) {
+            for edge in &graph.edges {
+                match edge.data {
+                    EdgeData::Artifact => collector.add_file(&edge.target),
+                    EdgeData::DirectLink(_) => {
+                        // Every `\include` writes its own auxiliary file.
+                        let child = workspace
+                            .lookup(&edge.target)
+                            .filter(|child| child.language == Language::Tex);
 
-        let flag = match target {
-            CleanTarget::Auxiliary => "-c",
-            CleanTarget::Artifacts => "-C",
-        };
+                        if let Some(child) = child {
+                            collector.add_outputs(child, &["aux"], &[&root.aux_dir]);
+                        }
+                    }
+                    EdgeData::FileList(_) | EdgeData::AdditionalFiles => {}
+                }
+            }
+        }
 
-        let out_dir = match target {
-            CleanTarget::Auxiliary => root.aux_dir,
-            CleanTarget::Artifacts => root.pdf_dir,
-        };
+        collector.add_file_list(workspace, document);
+
+        let mut files: Vec<_> = collector.files.into_iter().collect();
+        files.sort();
+        Ok(Self { files })
+    }
+
+    /// The files that running the command would remove.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Removes the files and returns the ones that could be deleted.
+    pub fn run(self) -> Result<Vec<PathBuf>> {
+        log::debug!("Cleaning output files: {:?}", self.files);
+        let mut removed = Vec::new();
+        for path in self.files {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed.push(path),
+                Err(why) => log::warn!("Unable to remove \"{}\": {why}", path.display()),
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+struct Collector<'a> {
+    root: &'a ProjectRoot,
+    target: CleanTarget,
+    dirs: Vec<PathBuf>,
+    files: FxHashSet<PathBuf>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(root: &'a ProjectRoot, target: CleanTarget) -> Self {
+        let dirs = [
+            &root.compile_dir,
+            &root.aux_dir,
+            &root.log_dir,
+            &root.pdf_dir,
+        ]
+        .into_iter()
+        .filter_map(|dir| dir.to_file_path().ok())
+        .collect();
+
+        Self {
+            root,
+            target,
+            dirs,
+            files: FxHashSet::default(),
+        }
+    }
+
+    /// Computes the path of the file with the given extension that compiling `document` writes to `dir`.
+    fn output_path(&self, document: &Document, extension: &str, dir: &Url) -> Option<PathBuf> {
+        let relative_path = self.root.compile_dir.make_relative(&document.uri)?;
+        let stem = relative_path
+            .rsplit_once('.')
+            .map_or(relative_path.as_str(), |(stem, _)| stem);
+
+        let uri = dir.join(&format!("{stem}.{extension}")).ok()?;
+        uri.to_file_path().ok()
+    }
+
+    fn add_outputs(&mut self, document: &Document, extensions: &[&str], dirs: &[&Url]) {
+        let dirs = dirs
+            .iter()
+            .copied()
+            .chain(std::iter::once(&self.root.compile_dir));
+        for dir in dirs {
+            for extension in extensions {
+                if let Some(path) = self.output_path(document, extension, dir) {
+                    self.add_path(path);
+                }
+            }
+        }
+    }
+
+    fn add_file(&mut self, uri: &Url) {
+        if let Ok(path) = uri.to_file_path() {
+            self.add_path(path);
+        }
+    }
+
+    /// Adds the files that the compiler has recorded as `OUTPUT` in its `.fls` file.
+    fn add_file_list(&mut self, workspace: &Workspace, document: &Document) -> Option<()> {
+        let path = self.output_path(document, "fls", &self.root.aux_dir)?;
+        let file_list = read_file_list(workspace, &path)?;
+        let working_dir = file_list.working_dir.as_deref().or(path.parent())?;
+
+        let outputs: Vec<_> = file_list
+            .outputs
+            .iter()
+            .map(|path| working_dir.join(path))
+            .filter(|path| self.dirs.iter().any(|dir| path.starts_with(dir)))
+            .filter(|path| {
+                // Files like `filecontents` environments are sources that are merely written by the compiler.
+                !matches!(
+                    Language::from_linked_path(path),
+                    Some(Language::Tex | Language::Bib | Language::CslJson)
+                )
+            })
+            .filter(|path| self.target == CleanTarget::Artifacts || !is_artifact(path))
+            .collect();
 
-        let out_dir_path = out_dir.to_file_path().unwrap();
+        for path in outputs {
+            self.add_path(path);
+        }
+
+        Some(())
+    }
+
+    fn add_path(&mut self, path: PathBuf) {
+        if path.is_file() {
+            self.files.insert(path);
+        }
+    }
+}
+
+pub(crate) fn read_file_list(workspace: &Workspace, path: &Path) -> Option<FileList> {
+    let uri = Url::from_file_path(path).ok()?;
+    match workspace.lookup(&uri) {
+        Some(document) => document.data.as_file_list().cloned(),
+        None => Some(parser::parse_file_list(
+            &std::fs::read_to_string(path).ok()?,
+        )),
+    }
+}
+
+fn is_artifact(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    ARTIFACT_EXTENSIONS
+        .iter()
+        .any(|extension| name.ends_with(&format!(".{extension}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use base_db::{BuildConfig, BuildProfile, Config, Owner, Workspace};
+    use distro::Language;
+    use line_index::LineCol;
+    use url::Url;
+
+    use super::{CleanCommand, CleanTarget};
+
+    fn clean(dir: &Path, target: CleanTarget) -> Vec<String> {
+        clean_with_profile(dir, target, None)
+    }
 
-        let executable = String::from("latexmk");
-        let args = vec![
-            format!("-outdir={}", out_dir_path.display()),
-            String::from(flag),
-            path.display().to_string(),
+    fn clean_with_profile(dir: &Path, target: CleanTarget, profile: Option<&str>) -> Vec<String> {
+        let mut workspace = Workspace::default();
+        workspace.set_config(Config {
+            build: BuildConfig {
+                profiles: vec![BuildProfile {
+                    name: "lualatex".into(),
+                    aux_dir: Some("build".into()),
+                    ..BuildProfile::default()
+                }],
+                ..BuildConfig::default()
+            },
+            ..Config::default()
+        });
+
+        for name in ["main.tex", "chapter.tex"] {
+            let path = dir.join(name);
+            let text = std::fs::read_to_string(&path).unwrap();
+            let uri = Url::from_file_path(&path).unwrap();
+            let cursor = LineCol { line: 0, col: 0 };
+            workspace.open(uri, text, Language::Tex, Owner::Client, cursor);
+        }
+
+        let root_uri = Url::from_file_path(dir.join("main.tex")).unwrap();
+        workspace.set_build_profile(root_uri, profile.map(String::from));
+
+        let uri = Url::from_file_path(dir.join("chapter.tex")).unwrap();
+        let document = workspace.lookup(&uri).unwrap();
+        let command = CleanCommand::new(&workspace, document, target).unwrap();
+        command
+            .files()
+            .iter()
+            .map(|path| path.strip_prefix(dir).unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        let files = [
+            (
+                "main.tex",
+                "\\documentclass{article}\\begin{document}\\include{chapter}\\end{document}",
+            ),
+            ("chapter.tex", "Foo"),
+            ("main.aux", ""),
+            ("main.log", ""),
+            ("main.toc", ""),
+            ("main.pdf", ""),
+            ("main.figlist", ""),
+            (
+                "main.fls",
+                "PWD /\nOUTPUT main.aux\nOUTPUT main.figlist\nOUTPUT main.pdf\nOUTPUT data.tex\n",
+            ),
+            ("chapter.aux", ""),
+            ("data.tex", ""),
+            ("notes.txt", ""),
         ];
 
-        Ok(Self { executable, args })
+        for (name, text) in files {
+            let text = text.replace("PWD /", &format!("PWD {}", dir.display()));
+            std::fs::write(dir.join(name), text).unwrap();
+        }
+
+        assert_eq!(
+            clean(&dir, CleanTarget::Auxiliary),
+            [
+                "chapter.aux",
+                "main.aux",
+                "main.figlist",
+                "main.fls",
+                "main.log",
+                "main.toc"
+            ]
+        );
+
+        assert_eq!(
+            clean(&dir, CleanTarget::Artifacts),
+            [
+                "chapter.aux",
+                "main.aux",
+                "main.figlist",
+                "main.fls",
+                "main.log",
+                "main.pdf",
+                "main.toc"
+            ]
+        );
     }
 
-    pub fn run(self) -> Result<()> {
-        log::debug!("Cleaning output files: {} {:?}", self.executable, self.args);
-        let result = std::process::Command::new(self.executable)
-            .args(self.args)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status();
-
-        if let Err(why) = result {
-            anyhow::bail!("failed to spawn process: {why}")
+    #[test]
+    fn test_profile_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(dir.join("build")).unwrap();
+        let files = [
+            (
+                "main.tex",
+                "% !TEX program = lualatex\n\\documentclass{article}\\begin{document}\\include{chapter}\\end{document}",
+            ),
+            ("chapter.tex", "Foo"),
+            ("build/main.aux", ""),
+            ("build/main.log", ""),
+            ("build/chapter.aux", ""),
+        ];
+
+        for (name, text) in files {
+            std::fs::write(dir.join(name), text).unwrap();
+        }
+
+        assert_eq!(
+            clean(&dir, CleanTarget::Auxiliary),
+            ["build/chapter.aux", "build/main.aux", "build/main.log"]
+        );
+    }
+
+    #[test]
+    fn test_requested_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(dir.join("build")).unwrap();
+        let files = [
+            (
+                "main.tex",
+                "\\documentclass{article}\\begin{document}\\include{chapter}\\end{document}",
+            ),
+            ("chapter.tex", "Foo"),
+            ("build/main.aux", ""),
+            ("build/chapter.aux", ""),
+        ];
+
+        for (name, text) in files {
+            std::fs::write(dir.join(name), text).unwrap();
         }
 
-        Ok(())
+        assert!(clean(&dir, CleanTarget::Auxiliary).is_empty());
+        assert_eq!(
+            clean_with_profile(&dir, CleanTarget::Auxiliary, Some("lualatex")),
+            ["build/chapter.aux", "build/main.aux"]
+        );
     }
 }
@@ -0,0 +1,481 @@
+use std::path::{Path, PathBuf};
+
+use base_db::{
+    Document, Workspace,
+    deps::{self, EdgeData, ProjectRoot},
+    semantics::tex::LinkKind,
+};
+use rowan::{TextRange, TextSize, ast::AstNode};
+use syntax::latex;
+use thiserror::Error;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum FlattenError {
+    #[error("document '{0}' is not a local file")]
+    NotLocal(Url),
+
+    #[error("document '{0}' is not a LaTeX document")]
+    NotTex(Url),
+
+    #[error("unable to write the flattened document: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlattenOptions {
+    /// Replace `\bibliography` with the contents of the generated `.bbl` file.
+    pub inline_bibliography: bool,
+
+    /// Remove comments and `\iffalse ... \fi` blocks.
+    pub strip_comments: bool,
+}
+
+/// Merges a project into a single document by inlining every included file.
+#[derive(Debug)]
+pub struct FlattenCommand {
+    text: String,
+    output_path: PathBuf,
+}
+
+impl FlattenCommand {
+    pub fn new(
+        workspace: &Workspace,
+        uri: &Url,
+        output_path: PathBuf,
+        options: FlattenOptions,
+    ) -> Result<Self, FlattenError> {
+        let Some(document) = workspace.lookup(uri) else {
+            return Err(FlattenError::NotTex(uri.clone()));
+        };
+
+        let document = deps::parents(workspace, document)
+            .into_iter()
+            .next()
+            .unwrap_or(document);
+
+        let root = ProjectRoot::from_document(workspace, document);
+        let (Some(path), Some(root)) = (&document.path, root) else {
+            return Err(FlattenError::NotLocal(document.uri.clone()));
+        };
+
+        if document.data.as_tex().is_none() {
+            return Err(FlattenError::NotTex(document.uri.clone()));
+        }
+
+        let bibliography = options
+            .inline_bibliography
+            .then(|| read_bibliography(workspace, &root, path))
+            .flatten();
+
+        let flattener = Flattener {
+            workspace,
+            graph: &workspace.graphs()[&document.uri],
+            options,
+            bibliography,
+        };
+
+        let mut stack = Vec::new();
+        let text = flattener.flatten(document, &mut stack);
+        Ok(Self { text, output_path })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Writes the flattened document and returns its path.
+    pub fn run(self) -> Result<PathBuf, FlattenError> {
+        std::fs::write(&self.output_path, self.text)?;
+        Ok(self.output_path)
+    }
+}
+
+fn read_bibliography(workspace: &Workspace, root: &ProjectRoot, path: &Path) -> Option<String> {
+    let stem = workspace
+        .config()
+        .build
+        .output_filename
+        .as_deref()
+        .map_or(path, Path::new)
+        .file_stem()?
+        .to_str()?;
+
+    let bbl_path = root.aux_dir.join(&format!("{stem}.bbl")).ok()?;
+    std::fs::read_to_string(bbl_path.to_file_path().ok()?).ok()
+}
+
+struct Flattener<'a> {
+    workspace: &'a Workspace,
+    graph: &'a deps::Graph,
+    options: FlattenOptions,
+    bibliography: Option<String>,
+}
+
+impl Flattener<'_> {
+    fn flatten(&self, document: &Document, stack: &mut Vec<Url>) -> String {
+        let Some(data) = document.data.as_tex() else {
+            return document.text.clone();
+        };
+
+        stack.push(document.uri.clone());
+        let root = data.root_node();
+        let mut replacements = Vec::new();
+        for node in root.descendants() {
+            if let Some(include) = latex::Include::cast(node.clone()) {
+                self.replace_include(document, include, stack, &mut replacements);
+            } else if let Some(import) = latex::Import::cast(node) {
+                let range = latex::small_range(&import);
+                if let Some(text) = self.inline_children(document, range, stack) {
+                    replacements.push((range, text));
+                }
+            }
+        }
+
+        if self.options.strip_comments {
+            find_comments(document, &root, &mut replacements);
+            find_false_blocks(&root, &mut replacements);
+        }
+
+        stack.pop();
+
+        // Included documents with their own preamble only contribute their body.
+        let body = if stack.is_empty() {
+            TextRange::up_to(root.text_range().end())
+        } else {
+            find_document_body(&root).unwrap_or(root.text_range())
+        };
+
+        apply_replacements(&document.text, body, replacements)
+    }
+
+    fn replace_include(
+        &self,
+        document: &Document,
+        include: latex::Include,
+        stack: &mut Vec<Url>,
+        replacements: &mut Vec<(TextRange, String)>,
+    ) {
+        let range = latex::small_range(&include);
+        match include.syntax().kind() {
+            latex::LATEX_INCLUDE => {
+                let Some(text) = self.inline_children(document, range, stack) else {
+                    return;
+                };
+
+                let is_include = include
+                    .command()
+                    .is_some_and(|command| command.text() == "\\include");
+
+                let text = if is_include {
+                    format!("\\clearpage\n{text}\n\\clearpage")
+                } else {
+                    text
+                };
+
+                replacements.push((range, text));
+            }
+            latex::BIBTEX_INCLUDE => {
+                if let Some(bibliography) = &self.bibliography {
+                    let text = bibliography.strip_suffix('\n').unwrap_or(bibliography);
+                    replacements.push((range, text.into()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Flattens the documents that the command in `range` links to.
+    fn inline_children(
+        &self,
+        document: &Document,
+        range: TextRange,
+        stack: &mut Vec<Url>,
+    ) -> Option<String> {
+        let children: Vec<_> = self
+            .graph
+            .edges
+            .iter()
+            .filter(|edge| edge.source == document.uri)
+            .filter_map(|edge| match &edge.data {
+                EdgeData::DirectLink(data)
+                    if data.link.kind == LinkKind::Tex
+                        && range.contains_range(data.link.path.range) =>
+                {
+                    self.workspace.lookup(&edge.target)
+                }
+                _ => None,
+            })
+            .filter(|child| !stack.contains(&child.uri))
+            .collect();
+
+        if children.is_empty() {
+            return None;
+        }
+
+        let texts: Vec<_> = children
+            .into_iter()
+            .map(|child| {
+                let text = self.flatten(child, stack);
+                match text.strip_suffix('\n') {
+                    Some(text) => text.to_string(),
+                    None => text,
+                }
+            })
+            .collect();
+
+        Some(texts.join("\n"))
+    }
+}
+
+fn find_document_body(root: &latex::SyntaxNode) -> Option<TextRange> {
+    let environment = root
+        .descendants()
+        .filter_map(latex::Environment::cast)
+        .find(|environment| {
+            environment
+                .begin()
+                .and_then(|begin| begin.name())
+                .and_then(|name| name.key())
+                .is_some_and(|name| name.to_string() == "document")
+        })?;
+
+    let start = environment.begin()?.syntax().text_range().end();
+    let end = environment.end()?.syntax().text_range().start();
+    Some(TextRange::new(start, end))
+}
+
+/// Removes comments that fill a whole line together with their line break
+/// and replaces trailing comments with a bare `%` to keep the line joined.
+fn find_comments(
+    document: &Document,
+    root: &latex::SyntaxNode,
+    replacements: &mut Vec<(TextRange, String)>,
+) {
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == latex::COMMENT)
+    {
+        let range = token.text_range();
+        let text = &document.text;
+        let line_start = text[..usize::from(range.start())]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+
+        if text[line_start..usize::from(range.start())]
+            .trim()
+            .is_empty()
+        {
+            let line_end = text[usize::from(range.end())..]
+                .find('\n')
+                .map_or(text.len(), |index| usize::from(range.end()) + index + 1);
+
+            let range = TextRange::new(
+                TextSize::try_from(line_start).unwrap(),
+                TextSize::try_from(line_end).unwrap(),
+            );
+
+            replacements.push((range, String::new()));
+        } else {
+            replacements.push((range, "%".into()));
+        }
+    }
+}
+
+/// Removes `\iffalse ... \fi` blocks while keeping track of nested conditionals.
+fn find_false_blocks(root: &latex::SyntaxNode, replacements: &mut Vec<(TextRange, String)>) {
+    let mut start = None;
+    let mut depth = 0;
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == latex::COMMAND_NAME)
+    {
+        let name = token.text();
+        match start {
+            None if name == "\\iffalse" => {
+                start = Some(token.text_range().start());
+                depth = 1;
+            }
+            None => {}
+            Some(block_start) => {
+                if name.starts_with("\\if") && name != "\\ifthenelse" {
+                    depth += 1;
+                } else if name == "\\fi" {
+                    depth -= 1;
+                    if depth == 0 {
+                        let range = TextRange::new(block_start, token.text_range().end());
+                        replacements.push((range, String::new()));
+                        start = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Applies the replacements inside of `body`, skipping those nested in an earlier one.
+fn apply_replacements(
+    text: &str,
+    body: TextRange,
+    mut replacements: Vec<(TextRange, String)>,
+) -> String {
+    replacements.retain(|(range, _)| body.contains_range(*range));
+    replacements.sort_by_key(|(range, _)| (range.start(), std::cmp::Reverse(range.end())));
+
+    let mut output = String::new();
+    let mut offset = body.start();
+    for (range, replacement) in replacements {
+        if range.start() < offset {
+            continue;
+        }
+
+        output.push_str(&text[TextRange::new(offset, range.start())]);
+        output.push_str(&replacement);
+        offset = range.end();
+    }
+
+    output.push_str(&text[TextRange::new(offset, body.end())]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use base_db::{BuildConfig, Config, Owner, Workspace};
+    use distro::Language;
+    use line_index::LineCol;
+    use test_utils::fixture::Fixture;
+    use url::Url;
+
+    use super::{FlattenCommand, FlattenOptions};
+
+    fn flatten(fixture: &str, options: FlattenOptions) -> String {
+        let fixture = Fixture::parse(fixture);
+        let uri = &fixture.documents[0].uri;
+        let output_path = PathBuf::from("flat.tex");
+        let command = FlattenCommand::new(&fixture.workspace, uri, output_path, options);
+        command.unwrap().text().to_string()
+    }
+
+    #[test]
+    fn test_input() {
+        let text = flatten(
+            r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\input{intro}
+\include{chapter}
+\end{document}
+
+%! intro.tex
+Intro % comment
+
+%! chapter.tex
+Chapter"#,
+            FlattenOptions::default(),
+        );
+
+        assert_eq!(
+            text,
+            "\\documentclass{article}\n\\begin{document}\nIntro % comment\n\\clearpage\nChapter\n\\clearpage\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_subfile_body() {
+        let text = flatten(
+            r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\subfile{sub}
+\end{document}
+
+%! sub.tex
+\documentclass[main]{subfiles}
+\begin{document}
+Sub
+\end{document}"#,
+            FlattenOptions::default(),
+        );
+
+        assert_eq!(
+            text,
+            "\\documentclass{article}\n\\begin{document}\nSub\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        let options = FlattenOptions {
+            strip_comments: true,
+            ..FlattenOptions::default()
+        };
+
+        let text = flatten(
+            r#"
+%! main.tex
+% !TEX program = lualatex
+\documentclass{article}
+\begin{document}
+Foo% comment
+\iffalse
+\ifdraft Draft \fi
+\fi
+Bar
+\end{document}"#,
+            options,
+        );
+
+        assert_eq!(
+            text,
+            "\\documentclass{article}\n\\begin{document}\nFoo%\n\nBar\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_bibliography_output_filename() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        let text =
+            "\\documentclass{article}\n\\begin{document}\n\\bibliography{refs}\n\\end{document}\n";
+        std::fs::write(dir.join("main.tex"), text).unwrap();
+        std::fs::write(dir.join("main.bbl"), "Wrong\n").unwrap();
+        std::fs::write(dir.join("thesis.bbl"), "Bibliography\n").unwrap();
+
+        let mut workspace = Workspace::default();
+        workspace.set_config(Config {
+            build: BuildConfig {
+                output_filename: Some("thesis.pdf".into()),
+                ..BuildConfig::default()
+            },
+            ..Config::default()
+        });
+
+        let uri = Url::from_file_path(dir.join("main.tex")).unwrap();
+        let cursor = LineCol { line: 0, col: 0 };
+        workspace.open(
+            uri.clone(),
+            text.into(),
+            Language::Tex,
+            Owner::Client,
+            cursor,
+        );
+
+        let options = FlattenOptions {
+            inline_bibliography: true,
+            ..FlattenOptions::default()
+        };
+
+        let output_path = dir.join("flat.tex");
+        let command = FlattenCommand::new(&workspace, &uri, output_path, options).unwrap();
+        assert_eq!(
+            command.text(),
+            "\\documentclass{article}\n\\begin{document}\nBibliography\n\\end{document}\n"
+        );
+    }
+}
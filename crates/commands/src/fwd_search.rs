@@ -84,12 +84,10 @@ impl ForwardSearch {
     }
 
     fn find_pdf(workspace: &Workspace, document: &Document) -> Result<PathBuf, ForwardSearchError> {
-        let Some(document_dir) = &document.dir else {
+        let Some(root) = ProjectRoot::from_document(workspace, document) else {
             return Err(ForwardSearchError::NotLocal(document.uri.clone()));
         };
 
-        let root = ProjectRoot::walk_and_find(workspace, document_dir);
-
         log::debug!("[FwdSearch] root={root:#?}");
 
         let pdf_dir = root
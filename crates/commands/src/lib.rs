@@ -1,16 +1,31 @@
+mod align_table;
+mod archive;
 mod build;
+mod build_monitor;
 mod change_env;
 mod clean;
 mod dep_graph;
 mod find_envs;
+mod flatten;
 mod fwd_search;
 mod placeholders;
+mod statistics;
+mod synctex;
 
 pub use self::{
+    align_table::{AlignTableEdit, TABLE_ENVIRONMENTS, align_table, align_tables},
+    archive::{ArchiveCommand, ArchiveError},
     build::{BuildCommand, BuildError},
+    build_monitor::{BuildEvent, BuildMonitor, BuildPass},
     change_env::{ChangeEnvironmentResult, change_environment},
     clean::{CleanCommand, CleanTarget},
     dep_graph::show_dependency_graph,
     find_envs::find_environments,
+    flatten::{FlattenCommand, FlattenError, FlattenOptions},
     fwd_search::{ForwardSearch, ForwardSearchError},
+    statistics::{DocumentStatistics, SectionStatistics, Statistics, document_statistics},
+    synctex::{
+        SyncTexBox, SyncTexData, SyncTexError, SyncTexForward, SyncTexForwardResult,
+        SyncTexInverse, SyncTexLocation,
+    },
 };
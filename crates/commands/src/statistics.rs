@@ -0,0 +1,453 @@
+use std::{ops::AddAssign, str::FromStr};
+
+use base_db::{
+    Document, Workspace,
+    deps::{self, EdgeData},
+    semantics::tex::LinkKind,
+    util::FloatKind,
+};
+use rowan::{TextRange, TextSize, ast::AstNode};
+use rustc_hash::{FxHashMap, FxHashSet};
+use symbols::{Symbol, SymbolKind};
+use syntax::latex::{self, HasCurly};
+use url::Url;
+
+/// The counts that `texcount` would report for a part of a project.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Statistics {
+    pub text_words: usize,
+    pub header_words: usize,
+    pub caption_words: usize,
+    pub inline_formulas: usize,
+    pub display_formulas: usize,
+    pub floats: usize,
+    pub citations: usize,
+    pub labels: usize,
+}
+
+impl AddAssign for Statistics {
+    fn add_assign(&mut self, other: Self) {
+        self.text_words += other.text_words;
+        self.header_words += other.header_words;
+        self.caption_words += other.caption_words;
+        self.inline_formulas += other.inline_formulas;
+        self.display_formulas += other.display_formulas;
+        self.floats += other.floats;
+        self.citations += other.citations;
+        self.labels += other.labels;
+    }
+}
+
+/// The counts of a section, from its heading up to the next one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SectionStatistics {
+    /// The name of the section or `None` for the text before the first heading.
+    pub name: Option<String>,
+    pub uri: Url,
+    pub range: TextRange,
+    pub statistics: Statistics,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DocumentStatistics {
+    pub total: Statistics,
+    pub sections: Vec<SectionStatistics>,
+}
+
+/// Counts the words, formulas, floats, citations and labels of the project that contains `document`.
+/// Comments, verbatim content and math do not contribute to the word counts.
+pub fn document_statistics(workspace: &Workspace, document: &Document) -> DocumentStatistics {
+    let root = deps::parents(workspace, document)
+        .into_iter()
+        .next()
+        .unwrap_or(document);
+
+    let mut counter = Counter {
+        workspace,
+        graph: workspace.graphs().get(&root.uri),
+        visited: FxHashSet::default(),
+        sections: vec![SectionStatistics {
+            name: None,
+            uri: root.uri.clone(),
+            range: TextRange::default(),
+            statistics: Statistics::default(),
+        }],
+    };
+
+    counter.visit_document(root);
+
+    let mut sections = counter.sections;
+    if sections[0].statistics == Statistics::default() {
+        sections.remove(0);
+    }
+
+    let mut total = Statistics::default();
+    for section in &sections {
+        total += section.statistics;
+    }
+
+    DocumentStatistics { total, sections }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum WordKind {
+    Text,
+    Header,
+    Caption,
+}
+
+struct Counter<'a> {
+    workspace: &'a Workspace,
+    graph: Option<&'a deps::Graph>,
+    visited: FxHashSet<Url>,
+    sections: Vec<SectionStatistics>,
+}
+
+impl Counter<'_> {
+    fn current(&mut self) -> &mut Statistics {
+        &mut self.sections.last_mut().unwrap().statistics
+    }
+
+    fn visit_document(&mut self, document: &Document) {
+        let Some(data) = document.data.as_tex() else {
+            return;
+        };
+
+        if !self.visited.insert(document.uri.clone()) {
+            return;
+        }
+
+        let mut symbols = Vec::new();
+        for symbol in symbols::document_symbols(self.workspace, document) {
+            symbol.flatten(&mut symbols);
+        }
+
+        let section_names = symbols
+            .into_iter()
+            .filter(|symbol| symbol.kind == SymbolKind::Section)
+            .map(
+                |Symbol {
+                     name, full_range, ..
+                 }| (full_range.start(), name),
+            )
+            .collect();
+
+        let context = DocumentContext {
+            document,
+            section_names,
+        };
+
+        // The preamble of a root document does not contribute to the counts.
+        let root = data.root_node();
+        let body = data
+            .semantics
+            .can_be_root
+            .then(|| find_document_environment(&root))
+            .flatten();
+
+        self.visit_children(&context, body.as_ref().unwrap_or(&root), WordKind::Text);
+    }
+
+    fn visit_children(
+        &mut self,
+        context: &DocumentContext,
+        node: &latex::SyntaxNode,
+        kind: WordKind,
+    ) {
+        for element in node.children_with_tokens() {
+            match element {
+                rowan::NodeOrToken::Node(child) => self.visit_node(context, &child, kind),
+                rowan::NodeOrToken::Token(token) => {
+                    if node.kind() == latex::TEXT
+                        && token.kind() == latex::WORD
+                        && token.text().chars().any(char::is_alphanumeric)
+                    {
+                        self.add_word(kind);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_node(&mut self, context: &DocumentContext, node: &latex::SyntaxNode, kind: WordKind) {
+        match node.kind() {
+            latex::BLOCK_COMMENT
+            | latex::CURLY_GROUP_KEY_VALUE
+            | latex::BRACK_GROUP_KEY_VALUE
+            | latex::OLD_COMMAND_DEFINITION
+            | latex::NEW_COMMAND_DEFINITION
+            | latex::ENVIRONMENT_DEFINITION
+            | latex::MATH_OPERATOR
+            | latex::THEOREM_DEFINITION_AMSTHM
+            | latex::THEOREM_DEFINITION_THMTOOLS
+            | latex::GLOSSARY_ENTRY_DEFINITION
+            | latex::ACRONYM_DEFINITION
+            | latex::ACRONYM_DECLARATION
+            | latex::COLOR_DEFINITION
+            | latex::COLOR_SET_DEFINITION => {}
+            latex::FORMULA => {
+                let is_display = node.first_token().is_some_and(|token| token.text() == "$$");
+
+                if is_display {
+                    self.current().display_formulas += 1;
+                } else {
+                    self.current().inline_formulas += 1;
+                }
+            }
+            latex::EQUATION => {
+                self.current().display_formulas += 1;
+            }
+            latex::CAPTION => {
+                self.visit_children(context, node, WordKind::Caption);
+            }
+            latex::CITATION => {
+                let keys = latex::Citation::cast(node.clone())
+                    .and_then(|citation| citation.key_list())
+                    .map_or(0, |list| list.keys().count());
+
+                self.current().citations += keys;
+            }
+            latex::LABEL_DEFINITION => {
+                self.current().labels += 1;
+            }
+            latex::LATEX_INCLUDE | latex::IMPORT => {
+                self.visit_included(context.document, node.text_range());
+            }
+            latex::ENVIRONMENT => self.visit_environment(context, node, kind),
+            _ => match latex::Section::cast(node.clone()) {
+                Some(section) => self.visit_section(context, &section, kind),
+                None => self.visit_children(context, node, kind),
+            },
+        }
+    }
+
+    fn visit_section(
+        &mut self,
+        context: &DocumentContext,
+        section: &latex::Section,
+        kind: WordKind,
+    ) {
+        let range = latex::small_range(section);
+        let heading = section.name();
+        let name = context
+            .section_names
+            .get(&range.start())
+            .cloned()
+            .or_else(|| heading.as_ref().and_then(|group| group.content_text()));
+
+        self.sections.push(SectionStatistics {
+            name,
+            uri: context.document.uri.clone(),
+            range: heading
+                .as_ref()
+                .map_or(range, |group| latex::small_range(group)),
+            statistics: Statistics::default(),
+        });
+
+        for element in section.syntax().children_with_tokens() {
+            let Some(child) = element.into_node() else {
+                continue;
+            };
+
+            if heading
+                .as_ref()
+                .is_some_and(|group| group.syntax() == &child)
+            {
+                self.visit_children(context, &child, WordKind::Header);
+            } else {
+                self.visit_node(context, &child, kind);
+            }
+        }
+    }
+
+    fn visit_environment(
+        &mut self,
+        context: &DocumentContext,
+        node: &latex::SyntaxNode,
+        kind: WordKind,
+    ) {
+        let name = latex::Environment::cast(node.clone())
+            .and_then(|environment| environment.begin())
+            .and_then(|begin| begin.name())
+            .and_then(|name| name.key())
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+
+        let config = &self.workspace.config().syntax;
+        if config.math_environments.contains(&name) {
+            self.current().display_formulas += 1;
+        } else if !config.verbatim_environments.contains(&name) {
+            if FloatKind::from_str(&name).is_ok() {
+                self.current().floats += 1;
+            }
+
+            self.visit_children(context, node, kind);
+        }
+    }
+
+    /// Continues with the documents that the include command in `range` refers to.
+    fn visit_included(&mut self, document: &Document, range: TextRange) {
+        let Some(graph) = self.graph else {
+            return;
+        };
+
+        let workspace = self.workspace;
+        let children = graph
+            .edges
+            .iter()
+            .filter(|edge| edge.source == document.uri)
+            .filter_map(|edge| match &edge.data {
+                EdgeData::DirectLink(data)
+                    if data.link.kind == LinkKind::Tex
+                        && range.contains_range(data.link.path.range) =>
+                {
+                    workspace.lookup(&edge.target)
+                }
+                _ => None,
+            });
+
+        for child in children {
+            self.visit_document(child);
+        }
+    }
+
+    fn add_word(&mut self, kind: WordKind) {
+        let statistics = self.current();
+        match kind {
+            WordKind::Text => statistics.text_words += 1,
+            WordKind::Header => statistics.header_words += 1,
+            WordKind::Caption => statistics.caption_words += 1,
+        }
+    }
+}
+
+struct DocumentContext<'a> {
+    document: &'a Document,
+    section_names: FxHashMap<TextSize, String>,
+}
+
+fn find_document_environment(root: &latex::SyntaxNode) -> Option<latex::SyntaxNode> {
+    root.descendants()
+        .filter_map(latex::Environment::cast)
+        .find(|environment| {
+            environment
+                .begin()
+                .and_then(|begin| begin.name())
+                .and_then(|name| name.key())
+                .is_some_and(|name| name.to_string() == "document")
+        })
+        .map(|environment| environment.syntax().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::fixture::Fixture;
+
+    use super::{Statistics, document_statistics};
+
+    #[test]
+    fn test_project() {
+        let fixture = Fixture::parse(
+            r#"
+%! main.tex
+\documentclass{article}
+\newcommand{\foo}{Not counted}
+\begin{document}
+Some words before % not counted
+\section{Introduction}
+\label{sec:intro}
+Hello $x + y$ world \cite{foo, bar}.
+\begin{figure}
+\caption{A small caption}
+\end{figure}
+\input{chapter}
+\end{document}
+
+%! chapter.tex
+\subsection{Details here}
+\[ a = b \]
+\begin{verbatim}
+not counted
+\end{verbatim}
+\begin{equation}
+c = d
+\end{equation}
+Final text"#,
+        );
+
+        let workspace = &fixture.workspace;
+        let document = workspace.lookup(&fixture.documents[1].uri).unwrap();
+        let statistics = document_statistics(workspace, document);
+
+        let sections: Vec<_> = statistics
+            .sections
+            .iter()
+            .map(|section| (section.name.clone(), section.statistics))
+            .collect();
+
+        assert_eq!(
+            sections,
+            [
+                (
+                    None,
+                    Statistics {
+                        text_words: 3,
+                        ..Statistics::default()
+                    }
+                ),
+                (
+                    Some("Introduction".into()),
+                    Statistics {
+                        text_words: 2,
+                        header_words: 1,
+                        caption_words: 3,
+                        inline_formulas: 1,
+                        floats: 1,
+                        citations: 2,
+                        labels: 1,
+                        ..Statistics::default()
+                    }
+                ),
+                (
+                    Some("Details here".into()),
+                    Statistics {
+                        text_words: 2,
+                        header_words: 2,
+                        display_formulas: 2,
+                        ..Statistics::default()
+                    }
+                ),
+            ]
+        );
+
+        assert_eq!(statistics.total.text_words, 7);
+        assert_eq!(statistics.total.display_formulas, 2);
+    }
+
+    #[test]
+    fn test_preamble() {
+        let fixture = Fixture::parse(
+            r#"
+%! main.tex
+\documentclass{article}
+\usepackage{hyperref}
+\hypersetup{pdftitle={Words in the preamble}}
+\title{A title with $x$}
+\author{Some Author}
+\begin{document}
+Only these four words.
+\end{document}"#,
+        );
+
+        let workspace = &fixture.workspace;
+        let document = workspace.lookup(&fixture.documents[0].uri).unwrap();
+        let statistics = document_statistics(workspace, document);
+        assert_eq!(
+            statistics.total,
+            Statistics {
+                text_words: 4,
+                ..Statistics::default()
+            }
+        );
+    }
+}
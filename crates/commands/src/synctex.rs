@@ -0,0 +1,563 @@
+use std::{
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use base_db::{Document, Workspace, deps::ProjectRoot};
+use flate2::read::GzDecoder;
+use thiserror::Error;
+use url::Url;
+
+/// The number of scaled points per PDF point.
+const SP_PER_BP: f64 = 65781.76;
+
+#[derive(Debug, Error)]
+pub enum SyncTexError {
+    #[error("Document \"{0}\" does not exist on the local file system")]
+    NotLocal(Url),
+
+    #[error("Document \"{0}\" has an invalid file path")]
+    InvalidPath(Url),
+
+    #[error("TeX document \"{0}\" not found")]
+    TexNotFound(Url),
+
+    #[error("SyncTeX file for \"{0}\" not found")]
+    SyncTexNotFound(PathBuf),
+
+    #[error("Unable to read SyncTeX file: {0}")]
+    Read(#[from] std::io::Error),
+}
+
+/// A rectangle on a PDF page in PDF points, measured from the top-left corner of the page.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SyncTexBox {
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// A position in a source file with a one-based line number.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyncTexLocation {
+    pub path: PathBuf,
+    pub line: u32,
+}
+
+/// The result of a forward search, pointing into the output document.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyncTexForwardResult {
+    pub pdf_path: PathBuf,
+    pub boxes: Vec<SyncTexBox>,
+}
+
+/// The kinds of records ordered by how precisely they describe the output of a line.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum RecordKind {
+    HBox,
+    VBox,
+    Point,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Record {
+    kind: RecordKind,
+    page: u32,
+    input: u32,
+    line: u32,
+    h: f64,
+    v: f64,
+    width: f64,
+    height: f64,
+    depth: f64,
+}
+
+/// The contents of a `.synctex(.gz)` file with all coordinates converted to PDF points.
+#[derive(Debug, Default, Clone)]
+pub struct SyncTexData {
+    inputs: Vec<(u32, PathBuf)>,
+    records: Vec<Record>,
+}
+
+impl SyncTexData {
+    pub fn load(path: &Path, base_dir: &Path) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let text = if path.extension().is_some_and(|ext| ext == "gz") {
+            let mut text = String::new();
+            GzDecoder::new(&bytes[..]).read_to_string(&mut text)?;
+            text
+        } else {
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+
+        Ok(Self::parse(&text, base_dir))
+    }
+
+    /// Parses the textual SyncTeX format.
+    /// Relative input paths are resolved against `base_dir`, which is the directory the compiler was invoked in.
+    pub fn parse(text: &str, base_dir: &Path) -> Self {
+        let mut data = Self::default();
+        let mut unit = 1.0;
+        let mut magnification = 1000.0;
+        let mut x_offset = 0.0;
+        let mut y_offset = 0.0;
+        let mut page = 0;
+        let mut in_content = false;
+        for line in text.lines() {
+            if let Some(input) = line.strip_prefix("Input:") {
+                let input = input
+                    .split_once(':')
+                    .and_then(|(tag, name)| Some((tag.parse().ok()?, name)));
+
+                if let Some((tag, name)) = input {
+                    data.inputs.push((tag, normalize(&base_dir.join(name))));
+                }
+
+                continue;
+            }
+
+            if !in_content {
+                let (key, value) = line.split_once(':').unwrap_or((line, ""));
+                let value = value.trim().parse::<f64>().ok();
+                match (key, value) {
+                    ("Unit", Some(value)) => unit = value,
+                    ("Magnification", Some(value)) => magnification = value,
+                    ("X Offset", Some(value)) => x_offset = value,
+                    ("Y Offset", Some(value)) => y_offset = value,
+                    ("Content", _) => in_content = true,
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            if line.starts_with("Postamble:") {
+                break;
+            }
+
+            let Some(kind) = line.chars().next() else {
+                continue;
+            };
+
+            let rest = &line[kind.len_utf8()..];
+            let record_kind = match kind {
+                '{' => {
+                    page = rest.parse().unwrap_or(page + 1);
+                    continue;
+                }
+                '(' | 'h' => RecordKind::HBox,
+                '[' | 'v' => RecordKind::VBox,
+                'x' | 'k' | 'g' | '$' => RecordKind::Point,
+                _ => continue,
+            };
+
+            let scale = unit * magnification / 1000.0 / SP_PER_BP;
+            if let Some(record) = parse_record(rest, record_kind, page) {
+                data.records.push(Record {
+                    h: (record.h + x_offset) * scale,
+                    v: (record.v + y_offset) * scale,
+                    width: record.width * scale,
+                    height: record.height * scale,
+                    depth: record.depth * scale,
+                    ..record
+                });
+            }
+        }
+
+        data
+    }
+
+    /// Finds the boxes in the output document that belong to the given one-based line of a source file.
+    /// If the line itself did not produce any output, the nearest line that did is used instead.
+    pub fn forward(&self, path: &Path, line: u32) -> Vec<SyncTexBox> {
+        let path = normalize(path);
+        let inputs: Vec<u32> = self
+            .inputs
+            .iter()
+            .filter(|(_, input)| *input == path)
+            .map(|(tag, _)| *tag)
+            .collect();
+
+        let candidates = || {
+            self.records
+                .iter()
+                .filter(|record| inputs.contains(&record.input) && record.line > 0)
+        };
+
+        let Some(best) =
+            candidates().min_by_key(|record| (record.line.abs_diff(line), record.line))
+        else {
+            return Vec::new();
+        };
+
+        let matches: Vec<_> = candidates()
+            .filter(|record| record.line == best.line && record.page == best.page)
+            .collect();
+
+        let kind = matches.iter().map(|record| record.kind).min();
+        matches
+            .into_iter()
+            .filter(|record| Some(record.kind) == kind)
+            .map(|record| SyncTexBox {
+                page: record.page,
+                x: record.h,
+                y: record.v - record.height,
+                width: record.width,
+                height: record.height + record.depth,
+            })
+            .collect()
+    }
+
+    /// Finds the source location of the given point on a PDF page.
+    /// The smallest box containing the point wins; otherwise, the closest record is used.
+    pub fn inverse(&self, page: u32, x: f64, y: f64) -> Option<SyncTexLocation> {
+        let records = || {
+            self.records
+                .iter()
+                .filter(|record| record.page == page && record.line > 0)
+        };
+
+        let containing = records()
+            .filter(|record| record.kind != RecordKind::Point)
+            .filter(|record| {
+                x >= record.h
+                    && x <= record.h + record.width
+                    && y >= record.v - record.height
+                    && y <= record.v + record.depth
+            })
+            .min_by(|a, b| a.area().total_cmp(&b.area()));
+
+        let record = containing
+            .or_else(|| records().min_by(|a, b| a.distance(x, y).total_cmp(&b.distance(x, y))))?;
+
+        let path = self
+            .inputs
+            .iter()
+            .find(|(tag, _)| *tag == record.input)
+            .map(|(_, path)| path.clone())?;
+
+        Some(SyncTexLocation {
+            path,
+            line: record.line,
+        })
+    }
+}
+
+impl Record {
+    fn area(&self) -> f64 {
+        self.width * (self.height + self.depth)
+    }
+
+    fn distance(&self, x: f64, y: f64) -> f64 {
+        let dx = if x < self.h {
+            self.h - x
+        } else {
+            (x - self.h - self.width).max(0.0)
+        };
+
+        let dy = if y < self.v - self.height {
+            self.v - self.height - y
+        } else {
+            (y - self.v - self.depth).max(0.0)
+        };
+
+        dx.hypot(dy)
+    }
+}
+
+/// Parses `tag,line[,column]:h,v[:width[,height,depth]]`.
+fn parse_record(text: &str, kind: RecordKind, page: u32) -> Option<Record> {
+    let mut parts = text.split(':');
+    let mut link = parts.next()?.split(',');
+    let input = link.next()?.parse().ok()?;
+    let line = link.next()?.parse().ok()?;
+
+    let mut point = parts.next()?.split(',');
+    let h = point.next()?.parse().ok()?;
+    let v = point.next()?.parse().ok()?;
+
+    let mut size = parts.next().into_iter().flat_map(|size| size.split(','));
+    let mut next = || {
+        size.next()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.0)
+    };
+    let width = next();
+    let height = next();
+    let depth = next();
+
+    // Empty boxes cannot contain anything, so they only mark a position.
+    let kind = if width > 0.0 { kind } else { RecordKind::Point };
+
+    Some(Record {
+        kind,
+        page,
+        input,
+        line,
+        h,
+        v,
+        width,
+        height,
+        depth,
+    })
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| {
+        path.components()
+            .filter(|component| *component != Component::CurDir)
+            .collect()
+    })
+}
+
+/// Locates the PDF and SyncTeX files of a root document.
+fn find_output(
+    workspace: &Workspace,
+    document: &Document,
+) -> Result<(PathBuf, PathBuf), SyncTexError> {
+    let Some(root) = ProjectRoot::from_document(workspace, document) else {
+        return Err(SyncTexError::NotLocal(document.uri.clone()));
+    };
+    let invalid_path = || SyncTexError::InvalidPath(document.uri.clone());
+    let pdf_dir = root.pdf_dir.to_file_path().map_err(|()| invalid_path())?;
+    let aux_dir = root.aux_dir.to_file_path().map_err(|()| invalid_path())?;
+
+    let pdf_name = workspace
+        .config()
+        .build
+        .output_filename
+        .clone()
+        .or_else(|| {
+            let stem = document.path.as_ref()?.file_stem()?;
+            Some(format!("{}.pdf", stem.to_string_lossy()))
+        })
+        .ok_or_else(invalid_path)?;
+
+    let pdf_path = pdf_dir.join(pdf_name);
+    let stem = pdf_path.file_stem().ok_or_else(invalid_path)?;
+    let stem = stem.to_string_lossy();
+    let synctex_path = [&pdf_dir, &aux_dir]
+        .into_iter()
+        .flat_map(|dir| {
+            [
+                dir.join(format!("{stem}.synctex.gz")),
+                dir.join(format!("{stem}.synctex")),
+            ]
+        })
+        .find(|path| path.exists())
+        .ok_or_else(|| SyncTexError::SyncTexNotFound(pdf_path.clone()))?;
+
+    Ok((pdf_path, synctex_path))
+}
+
+#[derive(Debug)]
+pub struct SyncTexForward {
+    synctex_path: PathBuf,
+    pdf_path: PathBuf,
+    base_dir: PathBuf,
+    tex_path: PathBuf,
+    line: u32,
+}
+
+impl SyncTexForward {
+    /// Prepares a forward search from the given zero-based line of a TeX document.
+    pub fn new(workspace: &Workspace, uri: &Url, line: Option<u32>) -> Result<Self, SyncTexError> {
+        let child = workspace
+            .lookup(uri)
+            .ok_or_else(|| SyncTexError::TexNotFound(uri.clone()))?;
+
+        let parent = base_db::deps::parents(workspace, child)
+            .into_iter()
+            .next()
+            .unwrap_or(child);
+
+        let (pdf_path, synctex_path) = find_output(workspace, parent)?;
+        let base_dir = parent
+            .dir
+            .as_ref()
+            .and_then(|dir| dir.to_file_path().ok())
+            .ok_or_else(|| SyncTexError::NotLocal(parent.uri.clone()))?;
+
+        let tex_path = child
+            .path
+            .clone()
+            .ok_or_else(|| SyncTexError::InvalidPath(child.uri.clone()))?;
+
+        let line = line.unwrap_or(child.cursor.line) + 1;
+        Ok(Self {
+            synctex_path,
+            pdf_path,
+            base_dir,
+            tex_path,
+            line,
+        })
+    }
+
+    pub fn run(self) -> Result<SyncTexForwardResult, SyncTexError> {
+        log::debug!("[SyncTeX] Forward search: {:?}", self);
+        let data = SyncTexData::load(&self.synctex_path, &self.base_dir)?;
+        let boxes = data.forward(&self.tex_path, self.line);
+        Ok(SyncTexForwardResult {
+            pdf_path: self.pdf_path,
+            boxes,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SyncTexInverse {
+    synctex_path: PathBuf,
+    base_dir: PathBuf,
+    page: u32,
+    x: f64,
+    y: f64,
+}
+
+impl SyncTexInverse {
+    /// Prepares an inverse search from a point on a one-based page of the given PDF file.
+    pub fn new(
+        workspace: &Workspace,
+        pdf_path: &Path,
+        page: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<Self, SyncTexError> {
+        let pdf_path = normalize(pdf_path);
+        let (synctex_path, base_dir) = workspace
+            .iter()
+            .filter(|document| {
+                document
+                    .data
+                    .as_tex()
+                    .is_some_and(|data| data.semantics.can_be_root)
+            })
+            .find_map(|document| {
+                let (pdf, synctex) = find_output(workspace, document).ok()?;
+                let dir = document.dir.as_ref()?.to_file_path().ok()?;
+                (normalize(&pdf) == pdf_path).then_some((synctex, dir))
+            })
+            .ok_or_else(|| SyncTexError::SyncTexNotFound(pdf_path.clone()))?;
+
+        Ok(Self {
+            synctex_path,
+            base_dir,
+            page,
+            x,
+            y,
+        })
+    }
+
+    pub fn run(self) -> Result<Option<SyncTexLocation>, SyncTexError> {
+        log::debug!("[SyncTeX] Inverse search: {:?}", self);
+        let data = SyncTexData::load(&self.synctex_path, &self.base_dir)?;
+        Ok(data.inverse(self.page, self.x, self.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{SyncTexBox, SyncTexData, SyncTexLocation};
+
+    static SYNCTEX: &str = r#"SyncTeX Version:1
+Input:1:/project/main.tex
+Input:2:./chapter.tex
+Output:pdf
+Magnification:1000
+Unit:1
+X Offset:0
+Y Offset:0
+Content:
+!100
+{1
+[1,3:0,0:39469056,52625408,0
+(1,4:6578176,13156352:32890880,657818,197345
+x1,4:6578176,13156352
+)
+(2,7:6578176,26312704:16445440,657818,197345
+k2,7:9867264,26312704:65782
+)
+]
+}1
+{2
+[2,9:0,0:39469056,52625408,0
+(2,9:6578176,6578176:32890880,657818,197345
+)
+]
+}2
+Postamble:
+Count:10
+"#;
+
+    fn data() -> SyncTexData {
+        SyncTexData::parse(SYNCTEX, Path::new("/project"))
+    }
+
+    fn round(rect: SyncTexBox) -> SyncTexBox {
+        let round = |value: f64| (value * 100.0).round() / 100.0;
+        SyncTexBox {
+            page: rect.page,
+            x: round(rect.x),
+            y: round(rect.y),
+            width: round(rect.width),
+            height: round(rect.height),
+        }
+    }
+
+    #[test]
+    fn test_forward_box() {
+        let boxes = data().forward(Path::new("/project/main.tex"), 4);
+        assert_eq!(
+            boxes.into_iter().map(round).collect::<Vec<_>>(),
+            vec![SyncTexBox {
+                page: 1,
+                x: 100.0,
+                y: 190.0,
+                width: 500.0,
+                height: 13.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_forward_relative_input() {
+        let boxes = data().forward(Path::new("/project/chapter.tex"), 9);
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].page, 2);
+    }
+
+    #[test]
+    fn test_forward_nearest_line() {
+        let boxes = data().forward(Path::new("/project/chapter.tex"), 6);
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].page, 1);
+        assert_eq!(round(boxes[0]).width, 250.0);
+    }
+
+    #[test]
+    fn test_inverse_containing_box() {
+        let location = data().inverse(1, 150.0, 398.0);
+        assert_eq!(
+            location,
+            Some(SyncTexLocation {
+                path: "/project/chapter.tex".into(),
+                line: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn test_inverse_outside_box() {
+        let location = data().inverse(2, 700.0, 95.0);
+        assert_eq!(
+            location,
+            Some(SyncTexLocation {
+                path: "/project/chapter.tex".into(),
+                line: 9,
+            })
+        );
+    }
+}
@@ -58,8 +58,11 @@ pub enum CompletionItemData<'a> {
     Argument(ArgumentData<'a>),
     Package(&'a str),
     DocumentClass(&'a str),
+    BeamerTheme(&'a str),
+    Font(&'a str),
     EntryType(EntryTypeData<'a>),
     Field(FieldTypeData<'a>),
+    FieldValue(FieldValueData),
     TikzLibrary(&'a str),
 }
 
@@ -80,8 +83,11 @@ impl<'a> CompletionItemData<'a> {
             Self::Argument(data) => data.0.name,
             Self::Package(name) => name,
             Self::DocumentClass(name) => name,
+            Self::BeamerTheme(name) => name,
+            Self::Font(name) => name,
             Self::EntryType(data) => data.0.name,
             Self::Field(data) => data.0.name,
+            Self::FieldValue(data) => data.string_name.as_deref().unwrap_or(&data.value),
             Self::TikzLibrary(name) => name,
         }
     }
@@ -104,8 +110,11 @@ impl<'a> CompletionItemData<'a> {
             Self::Argument(_) => 1,
             Self::Package(_) => 1,
             Self::DocumentClass(_) => 1,
+            Self::BeamerTheme(_) => 1,
+            Self::Font(_) => 1,
             Self::EntryType(_) => 1,
             Self::Field(_) => 1,
+            Self::FieldValue(_) => 1,
             Self::TikzLibrary(_) => 1,
         }
     }
@@ -190,8 +199,16 @@ impl<'a> std::fmt::Debug for FieldTypeData<'a> {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldValueData {
+    pub value: String,
+    pub string_name: Option<String>,
+    pub insert_text: String,
+    pub keywords: String,
+}
+
 pub fn complete<'a>(params: &'a CompletionParams<'a>) -> CompletionResult<'a> {
-    let mut builder = CompletionBuilder::from(params.feature.workspace);
+    let mut builder = CompletionBuilder::from(params);
     providers::complete_commands(params, &mut builder);
     providers::complete_environments(params, &mut builder);
     providers::complete_citations(params, &mut builder);
@@ -204,8 +221,11 @@ pub fn complete<'a>(params: &'a CompletionParams<'a>) -> CompletionResult<'a> {
     providers::complete_includes(params, &mut builder);
     providers::complete_arguments(params, &mut builder);
     providers::complete_imports(params, &mut builder);
+    providers::complete_beamer_themes(params, &mut builder);
+    providers::complete_fonts(params, &mut builder);
     providers::complete_entry_types(params, &mut builder);
     providers::complete_fields(params, &mut builder);
+    providers::complete_field_values(params, &mut builder);
     providers::complete_tikz_libraries(params, &mut builder);
     builder.finish()
 }
@@ -6,11 +6,14 @@ mod command;
 mod entry_type;
 mod environment;
 mod field;
+mod field_value;
+mod font;
 mod glossary;
 mod import;
 mod include;
 mod label_def;
 mod label_ref;
+mod theme;
 mod tikz_library;
 
 pub use argument::complete_arguments;
@@ -21,9 +24,12 @@ pub use command::complete_commands;
 pub use entry_type::complete_entry_types;
 pub use environment::complete_environments;
 pub use field::complete_fields;
+pub use field_value::complete_field_values;
+pub use font::complete_fonts;
 pub use glossary::{complete_acronyms, complete_glossaries};
 pub use import::complete_imports;
 pub use include::complete_includes;
 pub use label_def::complete_label_definitions;
 pub use label_ref::complete_label_references;
+pub use theme::complete_beamer_themes;
 pub use tikz_library::complete_tikz_libraries;
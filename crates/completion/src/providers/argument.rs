@@ -1,18 +1,17 @@
-use base_db::semantics::Span;
 use completion_data::included_packages;
-use rowan::{TokenAtOffset, ast::AstNode};
+use rowan::ast::AstNode;
 use syntax::latex;
 
 use crate::{
     ArgumentData, CompletionItem, CompletionItemData, CompletionParams,
-    util::{CompletionBuilder, is_inside_latex_curly},
+    util::{CompletionBuilder, find_curly_group},
 };
 
 pub fn complete_arguments<'a>(
     params: &'a CompletionParams<'a>,
     builder: &mut CompletionBuilder<'a>,
 ) -> Option<()> {
-    let (cursor, group) = find_argument(params)?;
+    let (cursor, group) = find_curly_group(params)?;
 
     let command = latex::GenericCommand::cast(group.syntax().parent()?)?;
 
@@ -51,27 +50,3 @@ pub fn complete_arguments<'a>(
 
     Some(())
 }
-
-fn find_argument(params: &CompletionParams) -> Option<(Span, latex::CurlyGroup)> {
-    let data = params.feature.document.data.as_tex()?;
-    let tokens = data.root_node().token_at_offset(params.offset);
-
-    let (span, token) = match tokens.clone().find(|token| token.kind() == latex::WORD) {
-        Some(token) => (Span::from(&token), token),
-        None if matches!(tokens, TokenAtOffset::Between(_, _)) => {
-            (Span::empty(params.offset), tokens.left_biased()?)
-        }
-        None => return None,
-    };
-
-    let group = latex::CurlyGroup::cast(token.parent()?)
-        .or_else(|| {
-            token
-                .parent()
-                .and_then(|node| node.parent())
-                .and_then(latex::CurlyGroup::cast)
-        })
-        .filter(|group| is_inside_latex_curly(group, params.offset))?;
-
-    Some((span, group))
-}
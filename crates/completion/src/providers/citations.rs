@@ -15,7 +15,7 @@ pub fn complete_citations<'a>(
     let cursor = find_citation(params)?;
 
     for document in &params.feature.project.documents {
-        if let Some(data) = document.data.as_bib() {
+        if let Some(data) = document.data.as_bibliography() {
             let items = data.semantics.entries.par_iter().filter_map(|entry| {
                 let score = builder.matcher.score(&entry.name.text, &cursor.text)?;
                 let data = CompletionItemData::Citation(CitationData { document, entry });
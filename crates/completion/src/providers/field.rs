@@ -1,5 +1,9 @@
-use base_db::semantics::Span;
+use base_db::semantics::{
+    Span,
+    bib::{EntryIndex, ResolvedEntry},
+};
 use rowan::{TokenAtOffset, ast::AstNode};
+use rustc_hash::FxHashSet;
 use syntax::bibtex::{self, HasName};
 
 use crate::{
@@ -11,8 +15,13 @@ pub fn complete_fields<'a>(
     builder: &mut CompletionBuilder<'a>,
 ) -> Option<()> {
     let cursor = find_field(params)?;
+    let inherited = find_inherited_fields(params).unwrap_or_default();
 
     for field in base_db::data::BIBTEX_FIELD_TYPES {
+        if inherited.contains(field.name) {
+            continue;
+        }
+
         if let Some(score) = builder.matcher.score(field.name, &cursor.text) {
             let data = CompletionItemData::Field(FieldTypeData(*field));
             builder
@@ -46,6 +55,26 @@ fn find_field(params: &CompletionParams) -> Option<Span> {
     })
 }
 
+/// Finds the fields that the entry at the cursor inherits through `crossref` or `xdata`.
+/// They already have a value, so suggesting them would only add noise.
+fn find_inherited_fields(params: &CompletionParams) -> Option<FxHashSet<String>> {
+    let data = params.feature.document.data.as_bib()?;
+    let entry = select_token(params)?
+        .parent_ancestors()
+        .find_map(bibtex::Entry::cast)?;
+
+    let index = EntryIndex::new(params.feature.workspace);
+    let entry = ResolvedEntry::resolve(&index, entry, &data.semantics);
+    let names = entry
+        .fields
+        .iter()
+        .filter(|field| !entry.is_own(field))
+        .map(|field| field.name.clone())
+        .collect();
+
+    Some(names)
+}
+
 fn select_token(params: &CompletionParams) -> Option<bibtex::SyntaxToken> {
     let data = params.feature.document.data.as_bib()?;
     Some(match data.root_node().token_at_offset(params.offset) {
@@ -0,0 +1,354 @@
+use base_db::semantics::{
+    Span,
+    bib::{self, EntryIndex, ResolvedField},
+};
+use bibtex_utils::field::{
+    author::{AuthorField, AuthorFieldData},
+    text::TextFieldData,
+};
+use rowan::{TextRange, TextSize, ast::AstNode};
+use rustc_hash::FxHashMap;
+use syntax::bibtex::{self, HasComma, HasEq, HasName, HasValue};
+
+use crate::{
+    CompletionItem, CompletionItemData, CompletionParams, FieldValueData, util::CompletionBuilder,
+};
+
+pub fn complete_field_values<'a>(
+    params: &'a CompletionParams<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) -> Option<()> {
+    let cursor = find_field_value(params)?;
+
+    let mut values: FxHashMap<String, i32> = FxHashMap::default();
+    let mut abbreviations: FxHashMap<&str, &str> = FxHashMap::default();
+    for document in params.feature.workspace.iter() {
+        let Some(data) = document.data.as_bibliography() else {
+            continue;
+        };
+
+        // Strings can only be used within the same project.
+        let defs = &data.semantics.expanded_defs;
+        if params.feature.project.documents.contains(document) {
+            for (name, value) in defs {
+                abbreviations.entry(value).or_insert(name);
+            }
+        }
+
+        let Some(root) = bibtex::Root::cast(data.root_node()) else {
+            continue;
+        };
+
+        for field in root.entries().flat_map(|entry| entry.fields()) {
+            if document == params.feature.document
+                && field
+                    .syntax()
+                    .text_range()
+                    .contains_inclusive(params.offset)
+            {
+                continue;
+            }
+
+            let kind = field
+                .name_token()
+                .and_then(|name| ValueKind::parse(name.text()));
+
+            if kind == Some(cursor.kind) {
+                add_values(&mut values, &field, cursor.kind, defs, 1);
+            }
+        }
+    }
+
+    // The values of the parents of the entry are the most likely ones.
+    for field in find_inherited_fields(params, cursor.kind).unwrap_or_default() {
+        let defs = &field.semantics.expanded_defs;
+        add_values(
+            &mut values,
+            &field.field,
+            cursor.kind,
+            defs,
+            MAX_COUNT_BONUS,
+        );
+    }
+
+    for (value, count) in values {
+        let string_name = abbreviations
+            .get(value.as_str())
+            .filter(|_| cursor.whole_value)
+            .map(|name| name.to_string());
+
+        let score = builder
+            .matcher
+            .score(&value, &cursor.span.text)
+            .or_else(|| {
+                builder
+                    .matcher
+                    .score(string_name.as_deref()?, &cursor.span.text)
+            });
+
+        let Some(score) = score else {
+            continue;
+        };
+
+        let keywords = match &string_name {
+            Some(name) => format!("{}{} {}", cursor.delimiter, value, name),
+            None => format!("{}{}", cursor.delimiter, value),
+        };
+
+        let insert_text = match &string_name {
+            Some(name) => name.clone(),
+            None if cursor.whole_value => format!("{{{value}}}"),
+            None => value.clone(),
+        };
+
+        let data = CompletionItemData::FieldValue(FieldValueData {
+            value,
+            string_name,
+            insert_text,
+            keywords,
+        });
+
+        let score = score + count.min(MAX_COUNT_BONUS);
+        builder
+            .items
+            .push(CompletionItem::new_simple(score, cursor.span.range, data));
+    }
+
+    Some(())
+}
+
+const MAX_COUNT_BONUS: i32 = 10;
+
+fn add_values(
+    values: &mut FxHashMap<String, i32>,
+    field: &bibtex::Field,
+    kind: ValueKind,
+    defs: &FxHashMap<String, String>,
+    count: i32,
+) -> Option<()> {
+    let value = field.value()?;
+    match kind {
+        ValueKind::Person => {
+            let data = AuthorFieldData::parse(&value, defs)?;
+            for name in data.names_last_first() {
+                *values.entry(name).or_default() += count;
+            }
+        }
+        ValueKind::Keywords => {
+            let data = TextFieldData::parse(&value, defs)?;
+            for keyword in data.text.split([',', ';']).map(str::trim) {
+                if !keyword.is_empty() {
+                    *values.entry(keyword.into()).or_default() += count;
+                }
+            }
+        }
+        ValueKind::Journal | ValueKind::Publisher | ValueKind::Series | ValueKind::Location => {
+            let data = TextFieldData::parse(&value, defs)?;
+            if !data.text.is_empty() {
+                *values.entry(data.text).or_default() += count;
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// Finds the fields of the given kind that the parents of the entry at the cursor define.
+fn find_inherited_fields<'a>(
+    params: &CompletionParams<'a>,
+    kind: ValueKind,
+) -> Option<Vec<ResolvedField<'a>>> {
+    let data = params.feature.document.data.as_bib()?;
+    let token = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .left_biased()?;
+    let entry = token.parent_ancestors().find_map(bibtex::Entry::cast)?;
+    let index = EntryIndex::new(params.feature.workspace);
+    let fields = bib::parent_fields(&index, &entry, &data.semantics)
+        .into_iter()
+        .filter(|field| ValueKind::parse(&field.name) == Some(kind))
+        .collect();
+
+    Some(fields)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ValueKind {
+    Journal,
+    Publisher,
+    Series,
+    Location,
+    Keywords,
+    Person,
+}
+
+impl ValueKind {
+    fn parse(name: &str) -> Option<Self> {
+        if AuthorField::parse(name).is_some() {
+            return Some(Self::Person);
+        }
+
+        Some(match name.to_ascii_lowercase().as_str() {
+            "journal" | "journaltitle" => Self::Journal,
+            "publisher" => Self::Publisher,
+            "series" => Self::Series,
+            "address" | "location" => Self::Location,
+            "keywords" => Self::Keywords,
+            _ => return None,
+        })
+    }
+}
+
+struct FieldValueCursor {
+    kind: ValueKind,
+    span: Span,
+    whole_value: bool,
+    /// The opening delimiter of the replaced value, which clients include when filtering.
+    delimiter: &'static str,
+}
+
+fn find_field_value(params: &CompletionParams) -> Option<FieldValueCursor> {
+    let document = params.feature.document;
+    let data = document.data.as_bib()?;
+    let offset = params.offset;
+    let token = data.root_node().token_at_offset(offset).left_biased()?;
+    let field = token.parent_ancestors().find_map(bibtex::Field::cast)?;
+    let kind = ValueKind::parse(field.name_token()?.text())?;
+    if offset < field.eq_token()?.text_range().end() {
+        return None;
+    }
+
+    let Some(value) = field.value() else {
+        if field
+            .comma_token()
+            .is_some_and(|comma| comma.text_range().start() < offset)
+        {
+            return None;
+        }
+
+        let span = Span::empty(offset);
+        return Some(FieldValueCursor {
+            kind,
+            span,
+            whole_value: true,
+            delimiter: "",
+        });
+    };
+
+    let range = value.syntax().text_range();
+    if offset < range.start() || offset > range.end() {
+        return None;
+    }
+
+    let (closing_kind, delimiter) = match value {
+        bibtex::Value::Literal(_) => {
+            let span = Span::new(value.syntax().text().to_string(), range);
+            return Some(FieldValueCursor {
+                kind,
+                span,
+                whole_value: true,
+                delimiter: "",
+            });
+        }
+        bibtex::Value::CurlyGroup(_) => (bibtex::R_CURLY, "{"),
+        bibtex::Value::QuoteGroup(_) => (bibtex::QUOTE, "\""),
+        bibtex::Value::Join(_) | bibtex::Value::Accent(_) | bibtex::Value::Command(_) => {
+            return None;
+        }
+    };
+
+    let is_closed = range.len() > TextSize::from(1)
+        && value
+            .syntax()
+            .last_token()
+            .is_some_and(|token| token.kind() == closing_kind);
+
+    let start = range.start() + TextSize::from(1);
+    let mut end = if is_closed {
+        range.end() - TextSize::from(1)
+    } else {
+        range.end()
+    };
+
+    if offset < start || offset > end {
+        return None;
+    }
+
+    // Unclosed groups may swallow the rest of the entry, so do not replace past the current line.
+    let mut value_end = range.end();
+    if let Some(index) = document.text[TextRange::new(offset, end)].find('\n') {
+        end = offset + TextSize::from(index as u32);
+        value_end = end;
+    }
+
+    let content = &document.text[TextRange::new(start, end)];
+    let cursor_in_content = usize::from(offset - start);
+    let segment = match kind {
+        ValueKind::Person => find_name_segment(content, cursor_in_content),
+        ValueKind::Keywords => find_keyword_segment(content, cursor_in_content),
+        ValueKind::Journal | ValueKind::Publisher | ValueKind::Series | ValueKind::Location => {
+            let range = TextRange::new(range.start(), value_end);
+            let span = Span::new(content.trim().into(), range);
+            return Some(FieldValueCursor {
+                kind,
+                span,
+                whole_value: true,
+                delimiter,
+            });
+        }
+    };
+
+    let range = segment + start;
+    let span = Span::new(document.text[range].into(), range);
+    Some(FieldValueCursor {
+        kind,
+        span,
+        whole_value: false,
+        delimiter: "",
+    })
+}
+
+/// Finds the name around the cursor in a list of names separated by `and`.
+fn find_name_segment(text: &str, offset: usize) -> TextRange {
+    let separators = text
+        .split_whitespace()
+        .filter(|word| word.eq_ignore_ascii_case("and"))
+        .map(|word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            (start, start + word.len())
+        });
+
+    find_segment(text, offset, separators)
+}
+
+/// Finds the keyword around the cursor in a list of keywords separated by commas or semicolons.
+fn find_keyword_segment(text: &str, offset: usize) -> TextRange {
+    let separators = text
+        .match_indices([',', ';'])
+        .map(|(start, separator)| (start, start + separator.len()));
+
+    find_segment(text, offset, separators)
+}
+
+fn find_segment(
+    text: &str,
+    offset: usize,
+    separators: impl Iterator<Item = (usize, usize)>,
+) -> TextRange {
+    let mut start = 0;
+    let mut end = text.len();
+    for (separator_start, separator_end) in separators {
+        if separator_end <= offset {
+            start = separator_end;
+        } else if separator_start >= offset {
+            end = end.min(separator_start);
+        }
+    }
+
+    let segment = &text[start..end];
+    let leading = segment.len() - segment.trim_start().len();
+    let start = (start + leading).min(offset);
+    let end = (end - (segment.len() - segment.trim_end().len())).max(offset);
+    TextRange::new((start as u32).into(), (end as u32).into())
+}
@@ -0,0 +1,97 @@
+use base_db::semantics::Span;
+use rowan::{TextRange, TextSize, ast::AstNode};
+use syntax::latex::{self, HasCurly};
+
+use crate::{
+    CompletionItem, CompletionItemData, CompletionParams,
+    util::{CompletionBuilder, find_curly_group},
+};
+
+pub fn complete_fonts<'a>(
+    params: &'a CompletionParams<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) -> Option<()> {
+    let (_, group) = find_curly_group(params)?;
+
+    let command = latex::GenericCommand::cast(group.syntax().parent()?)?;
+    if !is_font_argument(&command, &group)? {
+        return None;
+    }
+
+    // Font names may contain spaces, so the whole argument is replaced.
+    let start = group.left_curly()?.text_range().end();
+    let end = group.right_curly().map_or_else(
+        || group.syntax().text_range().end(),
+        |token| token.text_range().start(),
+    );
+
+    let text = &params.feature.document.text;
+    let end = text[usize::from(start)..usize::from(end)]
+        .find('\n')
+        .map_or(end, |index| start + TextSize::from(index as u32));
+
+    if params.offset < start || params.offset > end {
+        return None;
+    }
+
+    let range = TextRange::new(start, end);
+    let cursor = Span::new(text[range].trim().into(), range);
+
+    let font_db = &params.feature.workspace.distro().font_db;
+    for file_name in font_db.iter() {
+        if let Some(score) = builder.matcher.score(file_name, &cursor.text) {
+            let data = CompletionItemData::Font(file_name);
+            builder
+                .items
+                .push(CompletionItem::new_simple(score, cursor.range, data));
+        }
+    }
+
+    Some(())
+}
+
+fn is_font_argument(command: &latex::GenericCommand, group: &latex::CurlyGroup) -> Option<bool> {
+    let first_group = command
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroup::cast)?;
+
+    if first_group.syntax() != group.syntax() {
+        return Some(false);
+    }
+
+    let name = command.name()?;
+    if FONT_COMMANDS.contains(&name.text()) {
+        return Some(true);
+    }
+
+    // In `\newfontfamily\foo{...}`, the font name is parsed as the argument of `\foo`.
+    let definition = command
+        .syntax()
+        .prev_sibling()
+        .and_then(latex::GenericCommand::cast)
+        .filter(|command| command.syntax().children().next().is_none())
+        .and_then(|command| command.name())?;
+
+    Some(FONT_DEFINITION_COMMANDS.contains(&definition.text()))
+}
+
+static FONT_COMMANDS: &[&str] = &[
+    "\\setmainfont",
+    "\\setsansfont",
+    "\\setmonofont",
+    "\\setmathfont",
+    "\\setromanfont",
+    "\\fontspec",
+];
+
+static FONT_DEFINITION_COMMANDS: &[&str] = &[
+    "\\newfontfamily",
+    "\\renewfontfamily",
+    "\\setfontfamily",
+    "\\providefontfamily",
+    "\\newfontface",
+    "\\renewfontface",
+    "\\setfontface",
+    "\\providefontface",
+];
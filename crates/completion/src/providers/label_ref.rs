@@ -21,21 +21,27 @@ pub fn complete_label_references<'a>(
     params: &'a CompletionParams<'a>,
     builder: &mut CompletionBuilder<'a>,
 ) -> Option<()> {
-    let FindResult {
-        cursor,
-        is_math,
-        command,
-    } = find_reference(params).or_else(|| find_reference_range(params))?;
-    let ref_pref = params
-        .feature
-        .workspace
-        .config()
-        .syntax
+    let FindResult { cursor, command } =
+        find_reference(params).or_else(|| find_reference_range(params))?;
+    let syntax_config = &params.feature.workspace.config().syntax;
+    let ref_pref = syntax_config
         .label_reference_prefixes
         .iter()
         .find_map(|(k, v)| if *k == command { Some(v) } else { None })
         .map(|x| x.as_str());
 
+    let kinds = syntax_config
+        .label_reference_kinds
+        .iter()
+        .find_map(|(k, v)| if *k == command { Some(v) } else { None });
+
+    // With `cleveref`, a partially typed label prefix like `fig:` only allows labels with the same prefix.
+    let typed_prefix = cursor
+        .text
+        .rfind(':')
+        .filter(|_| is_cleveref_command(&command))
+        .map(|index| &cursor.text[..=index]);
+
     for document in &params.feature.project.documents {
         let DocumentData::Tex(data) = &document.data else {
             continue;
@@ -50,10 +56,19 @@ pub fn complete_label_references<'a>(
             if ref_pref.is_some_and(|pref| !label.name.text.starts_with(pref)) {
                 continue;
             }
+
             let labeltext = trim_prefix(ref_pref, &label.name.text);
+            if typed_prefix.is_some_and(|pref| !labeltext.starts_with(pref)) {
+                continue;
+            }
+
             match render_label(params.feature.workspace, &params.feature.project, label) {
                 Some(rendered_label) => {
-                    if is_math && !matches!(rendered_label.object, RenderedObject::Equation) {
+                    if kinds.is_some_and(|kinds| {
+                        !kinds
+                            .iter()
+                            .any(|kind| rendered_label.object.matches_kind(kind))
+                    }) {
                         continue;
                     }
 
@@ -103,19 +118,21 @@ pub fn complete_label_references<'a>(
     Some(())
 }
 
+fn is_cleveref_command(command: &str) -> bool {
+    let command = command.to_lowercase();
+    command.contains("cref") || command.contains("cpageref")
+}
+
 struct FindResult {
     cursor: Span,
-    is_math: bool,
     command: String,
 }
 
 fn find_reference(params: &CompletionParams) -> Option<FindResult> {
     let (cursor, group) = find_curly_group_word_list(params)?;
     let reference = latex::LabelReference::cast(group.syntax().parent()?)?;
-    let is_math = reference.command()?.text() == "\\eqref";
     Some(FindResult {
         cursor,
-        is_math,
         command: reference.command()?.text()[1..].to_string(),
     })
 }
@@ -125,7 +142,6 @@ fn find_reference_range(params: &CompletionParams) -> Option<FindResult> {
     let refrange = latex::LabelReferenceRange::cast(group.syntax().parent()?)?;
     Some(FindResult {
         cursor,
-        is_math: false,
         command: refrange.command()?.text()[1..].to_string(),
     })
 }
@@ -0,0 +1,53 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{
+    CompletionItem, CompletionItemData, CompletionParams,
+    util::{CompletionBuilder, find_curly_group},
+};
+
+pub fn complete_beamer_themes<'a>(
+    params: &'a CompletionParams<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) -> Option<()> {
+    let (cursor, group) = find_curly_group(params)?;
+
+    let command = latex::GenericCommand::cast(group.syntax().parent()?)?;
+    let first_group = command
+        .syntax()
+        .children()
+        .find_map(latex::CurlyGroup::cast)?;
+
+    if first_group.syntax() != group.syntax() {
+        return None;
+    }
+
+    let prefix = match command.name()?.text() {
+        "\\usetheme" => "beamertheme",
+        "\\usecolortheme" => "beamercolortheme",
+        "\\usefonttheme" => "beamerfonttheme",
+        "\\useinnertheme" => "beamerinnertheme",
+        "\\useoutertheme" => "beameroutertheme",
+        _ => return None,
+    };
+
+    let file_name_db = &params.feature.workspace.distro().file_name_db;
+    for (file_name, _) in file_name_db.iter() {
+        let Some(name) = file_name
+            .strip_prefix(prefix)
+            .and_then(|name| name.strip_suffix(".sty"))
+            .filter(|name| !name.is_empty())
+        else {
+            continue;
+        };
+
+        if let Some(score) = builder.matcher.score(name, &cursor.text) {
+            let data = CompletionItemData::BeamerTheme(name);
+            builder
+                .items
+                .push(CompletionItem::new_simple(score, cursor.range, data));
+        }
+    }
+
+    Some(())
+}
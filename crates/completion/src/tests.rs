@@ -1,16 +1,14 @@
-use base_db::{Config, FeatureParams};
+use base_db::{Config, FeatureParams, Workspace};
+use distro::{Distro, FileNameDB, FontDB};
 use expect_test::{Expect, expect};
 use parser::SyntaxConfig;
 use rowan::TextRange;
 
 use crate::CompletionParams;
 
-fn check_with_syntax_config(config: SyntaxConfig, input: &str, expect: Expect) {
+fn check_with_workspace(input: &str, expect: Expect, setup: impl FnOnce(&mut Workspace)) {
     let mut fixture = test_utils::fixture::Fixture::parse(input);
-    fixture.workspace.set_config(Config {
-        syntax: config,
-        ..Config::default()
-    });
+    setup(&mut fixture.workspace);
     let fixture = fixture;
 
     let (offset, spec) = fixture
@@ -43,6 +41,19 @@ fn check_with_syntax_config(config: SyntaxConfig, input: &str, expect: Expect) {
     expect.assert_debug_eq(&items);
 }
 
+fn check_with_syntax_config(config: SyntaxConfig, input: &str, expect: Expect) {
+    check_with_workspace(input, expect, |workspace| {
+        workspace.set_config(Config {
+            syntax: config,
+            ..Config::default()
+        });
+    });
+}
+
+fn check_with_distro(distro: Distro, input: &str, expect: Expect) {
+    check_with_workspace(input, expect, |workspace| workspace.set_distro(distro));
+}
+
 fn check(input: &str, expect: Expect) {
     check_with_syntax_config(SyntaxConfig::default(), input, expect)
 }
@@ -378,6 +389,7 @@ fn citation() {
                             full_range: 88..107,
                             keywords: "bar:2005 @article",
                             category: Article,
+                            parents: [],
                         },
                     },
                 ),
@@ -394,6 +406,42 @@ fn citation() {
                             full_range: 0..86,
                             keywords: "foo:2019 @article Foo Bar Baz Qux 2019",
                             category: Article,
+                            parents: [],
+                        },
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn citation_csl_json() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.json}
+\cite{
+      |
+
+%! main.json
+[{"id": "foo", "type": "book", "title": "Bar"}]"#,
+        expect![[r#"
+            [
+                Citation(
+                    CitationData {
+                        document: Document(
+                            "file:///texlab/main.json",
+                        ),
+                        entry: Entry {
+                            name: Span(
+                                "foo",
+                                9..12,
+                            ),
+                            full_range: 1..46,
+                            keywords: "foo @book Bar",
+                            category: Book,
+                            parents: [],
                         },
                     },
                 ),
@@ -428,6 +476,7 @@ fn citation_open_brace() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            parents: [],
                         },
                     },
                 ),
@@ -463,6 +512,7 @@ fn citation_open_brace_multiple() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            parents: [],
                         },
                     },
                 ),
@@ -497,6 +547,7 @@ fn citation_acronym() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            parents: [],
                         },
                     },
                 ),
@@ -1352,6 +1403,203 @@ fn field_entry_two_fields_name_closed() {
     );
 }
 
+#[test]
+fn field_inherited() {
+    check(
+        r#"
+%! main.bib
+@book{parent, abstract = {Foo}, addendum = {Bar}}
+
+@inbook{child, crossref = {parent},
+                                   |"#,
+        expect![[r#"
+            [
+                Field(
+                    FieldTypeData(
+                        "address",
+                    ),
+                ),
+                Field(
+                    FieldTypeData(
+                        "afterword",
+                    ),
+                ),
+                Field(
+                    FieldTypeData(
+                        "annotation",
+                    ),
+                ),
+                Field(
+                    FieldTypeData(
+                        "annotator",
+                    ),
+                ),
+                Field(
+                    FieldTypeData(
+                        "annote",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_value_journal() {
+    check(
+        r#"
+%! main.bib
+@string{jcp = {Journal of Chemical Physics}}
+
+@article{foo, journal = jcp}
+
+@article{bar, journal = {Journal of Chemistry}}
+
+@article{baz, journal = {Journal of Ch}}
+                              |
+                        ^^^^^^^^^^^^^^^"#,
+        expect![[r#"
+            [
+                FieldValue(
+                    FieldValueData {
+                        value: "Journal of Chemistry",
+                        string_name: None,
+                        insert_text: "{Journal of Chemistry}",
+                        keywords: "{Journal of Chemistry",
+                    },
+                ),
+                FieldValue(
+                    FieldValueData {
+                        value: "Journal of Chemical Physics",
+                        string_name: Some(
+                            "jcp",
+                        ),
+                        insert_text: "jcp",
+                        keywords: "{Journal of Chemical Physics jcp",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_value_journal_other_project() {
+    check(
+        r#"
+%! main.bib
+@article{baz, journal = {Journal of Ch}}
+                              |
+                        ^^^^^^^^^^^^^^^
+
+%! other.bib
+@string{jcp = {Journal of Chemical Physics}}
+
+@article{foo, journal = jcp}"#,
+        expect![[r#"
+            [
+                FieldValue(
+                    FieldValueData {
+                        value: "Journal of Chemical Physics",
+                        string_name: None,
+                        insert_text: "{Journal of Chemical Physics}",
+                        keywords: "{Journal of Chemical Physics",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_value_author() {
+    check(
+        r#"
+%! main.bib
+@article{foo, author = {John Doe and Jane Roe}}
+
+@article{bar, editor = {Doe, John}}
+
+@article{baz, author = {Max Mustermann and Do}}
+                                            |
+                                           ^^"#,
+        expect![[r#"
+            [
+                FieldValue(
+                    FieldValueData {
+                        value: "Doe, John",
+                        string_name: None,
+                        insert_text: "Doe, John",
+                        keywords: "Doe, John",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_value_keywords() {
+    check(
+        r#"
+%! main.bib
+@article{foo, keywords = {physics, chemistry}}
+
+@article{bar, keywords = {biology; ch}}
+                                    |
+                                   ^^"#,
+        expect![[r#"
+            [
+                FieldValue(
+                    FieldValueData {
+                        value: "chemistry",
+                        string_name: None,
+                        insert_text: "chemistry",
+                        keywords: "chemistry",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_value_inherited() {
+    check(
+        r#"
+%! main.bib
+@book{parent, publisher = {Springer}}
+
+@book{foo, publisher = {Spring Press}}
+
+@book{bar, publisher = {Spring Press}}
+
+@inbook{child, crossref = {parent}, publisher = {Spr}}
+                                                    |
+                                                ^^^^^"#,
+        expect![[r#"
+            [
+                FieldValue(
+                    FieldValueData {
+                        value: "Springer",
+                        string_name: None,
+                        insert_text: "{Springer}",
+                        keywords: "{Springer",
+                    },
+                ),
+                FieldValue(
+                    FieldValueData {
+                        value: "Spring Press",
+                        string_name: None,
+                        insert_text: "{Spring Press}",
+                        keywords: "{Spring Press",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn import_package_open_brace() {
     check(
@@ -1616,6 +1864,144 @@ Lorem ipsum dolor sit amet.
     );
 }
 
+#[test]
+fn label_kind_filter() {
+    check(
+        r#"
+%! main.tex
+\begin{equation}%
+\label{eq:foo}
+    1 + 1 = 2
+\end{equation}
+
+\begin{figure}%
+\caption{Baz}%
+\label{fig:baz}
+\end{figure}
+
+\figref{}
+        |"#,
+        expect![[r#"
+            [
+                Label(
+                    LabelData {
+                        name: "fig:baz",
+                        header: Some(
+                            "Figure",
+                        ),
+                        footer: Some(
+                            "Baz",
+                        ),
+                        object: Some(
+                            Float {
+                                kind: Figure,
+                                caption: "Baz",
+                            },
+                        ),
+                        keywords: "fig:baz Figure: Baz",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn label_typed_prefix() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}%
+\label{sec:foo}
+
+\begin{figure}%
+\caption{Section}%
+\label{fig:baz}
+\end{figure}
+
+\cref{sec:}
+          |
+      ^^^^"#,
+        expect![[r#"
+            [
+                Label(
+                    LabelData {
+                        name: "sec:foo",
+                        header: Some(
+                            "Section (Foo)",
+                        ),
+                        footer: None,
+                        object: Some(
+                            Section {
+                                prefix: "Section",
+                                text: "Foo",
+                            },
+                        ),
+                        keywords: "sec:foo Section (Foo)",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn label_typed_prefix_ref() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}%
+\label{sec:foo}
+
+\begin{figure}%
+\caption{Section: Baz}%
+\label{fig:baz}
+\end{figure}
+
+\ref{sec:}
+         |
+     ^^^^"#,
+        expect![[r#"
+            [
+                Label(
+                    LabelData {
+                        name: "sec:foo",
+                        header: Some(
+                            "Section (Foo)",
+                        ),
+                        footer: None,
+                        object: Some(
+                            Section {
+                                prefix: "Section",
+                                text: "Foo",
+                            },
+                        ),
+                        keywords: "sec:foo Section (Foo)",
+                    },
+                ),
+                Label(
+                    LabelData {
+                        name: "fig:baz",
+                        header: Some(
+                            "Figure",
+                        ),
+                        footer: Some(
+                            "Section: Baz",
+                        ),
+                        object: Some(
+                            Float {
+                                kind: Figure,
+                                caption: "Section: Baz",
+                            },
+                        ),
+                        keywords: "fig:baz Figure: Section: Baz",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn label_undefined() {
     check(
@@ -1846,6 +2232,88 @@ fn test_user_environment() {
     );
 }
 
+#[test]
+fn test_user_command_frequency() {
+    check(
+        r#"
+%! main.tex
+\input{sub}
+\foobar
+\foobaz \foobaz
+
+%! sub.tex
+\foobaz
+\fooba
+   |
+ ^^^^^"#,
+        expect![[r#"
+            [
+                Command(
+                    CommandData {
+                        name: "foobaz",
+                        package: "<user>",
+                    },
+                ),
+                Command(
+                    CommandData {
+                        name: "foobar",
+                        package: "<user>",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_recency() {
+    check(
+        r#"
+%! main.tex
+\foobar
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+\foobaz
+\fooba
+   |
+ ^^^^^"#,
+        expect![[r#"
+            [
+                Command(
+                    CommandData {
+                        name: "foobaz",
+                        package: "<user>",
+                    },
+                ),
+                Command(
+                    CommandData {
+                        name: "foobar",
+                        package: "<user>",
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_project_resolution_import() {
     check(
@@ -2371,3 +2839,90 @@ fn test_custom_glossary_reference_command() {
         "#]],
     );
 }
+
+fn theme_distro() -> Distro {
+    let file_name_db = [
+        "/texmf/beamerthemeMadrid.sty",
+        "/texmf/beamerthemeWarsaw.sty",
+        "/texmf/beamercolorthemebeaver.sty",
+        "/texmf/beamerfontthemeserif.sty",
+    ]
+    .into_iter()
+    .map(Into::into)
+    .collect::<FileNameDB>();
+
+    Distro {
+        file_name_db,
+        ..Distro::default()
+    }
+}
+
+#[test]
+fn beamer_theme() {
+    check_with_distro(
+        theme_distro(),
+        r#"
+%! main.tex
+\usetheme{Ma}
+           |
+          ^^"#,
+        expect![[r#"
+            [
+                BeamerTheme(
+                    "Madrid",
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn beamer_color_theme_empty() {
+    check_with_distro(
+        theme_distro(),
+        r#"
+%! main.tex
+\usecolortheme{}
+               |"#,
+        expect![[r#"
+            [
+                BeamerTheme(
+                    "beaver",
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn font_file_name() {
+    let font_db = [
+        "/fonts/opentype/texgyretermes-regular.otf",
+        "/fonts/truetype/DejaVuSans.ttf",
+        "/fonts/type1/cmr10.pfb",
+    ]
+    .into_iter()
+    .map(Into::into)
+    .collect::<FontDB>();
+
+    let distro = Distro {
+        font_db,
+        ..Distro::default()
+    };
+
+    check_with_distro(
+        distro,
+        r#"
+%! main.tex
+\newfontfamily\termes[Ligatures=TeX]{gyreterm}
+                                         |
+                                     ^^^^^^^^"#,
+        expect![[r#"
+            [
+                Font(
+                    "texgyretermes-regular.otf",
+                ),
+            ]
+        "#]],
+    );
+}
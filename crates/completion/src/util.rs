@@ -1,6 +1,7 @@
 mod builder;
 pub mod matchers;
 mod patterns;
+mod usage;
 
 pub use builder::*;
 pub use patterns::*;
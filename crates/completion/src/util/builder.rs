@@ -1,17 +1,22 @@
-use base_db::{MatchingAlgo, Workspace};
+use base_db::MatchingAlgo;
 use fuzzy_matcher::skim::SkimMatcherV2;
 
-use crate::{CompletionItem, CompletionResult};
+use crate::{CompletionItem, CompletionParams, CompletionResult};
 
-use super::matchers::{self, Matcher};
+use super::{
+    matchers::{self, Matcher},
+    usage::UsageStats,
+};
 
 pub struct CompletionBuilder<'a> {
     pub matcher: Box<dyn Matcher>,
     pub items: Vec<CompletionItem<'a>>,
+    usage: UsageStats<'a>,
 }
 
-impl<'a> From<&Workspace> for CompletionBuilder<'a> {
-    fn from(workspace: &Workspace) -> Self {
+impl<'a> From<&'a CompletionParams<'a>> for CompletionBuilder<'a> {
+    fn from(params: &'a CompletionParams<'a>) -> Self {
+        let workspace = params.feature.workspace;
         let matcher: Box<dyn Matcher> = match workspace.config().completion.matcher {
             MatchingAlgo::Skim => Box::<SkimMatcherV2>::default(),
             MatchingAlgo::SkimIgnoreCase => Box::new(SkimMatcherV2::default().ignore_case()),
@@ -22,12 +27,17 @@ impl<'a> From<&Workspace> for CompletionBuilder<'a> {
         Self {
             matcher,
             items: Vec::new(),
+            usage: UsageStats::new(params),
         }
     }
 }
 
 impl<'a> CompletionBuilder<'a> {
     pub fn finish(mut self) -> CompletionResult<'a> {
+        for item in &mut self.items {
+            item.score += self.usage.bonus(&item.data);
+        }
+
         self.items.sort_by(|a, b| {
             b.preselect
                 .cmp(&a.preselect)
@@ -1,5 +1,5 @@
 use base_db::semantics::Span;
-use rowan::{TextRange, TextSize, ast::AstNode};
+use rowan::{TextRange, TextSize, TokenAtOffset, ast::AstNode};
 use syntax::latex;
 
 use crate::CompletionParams;
@@ -70,6 +70,30 @@ pub fn find_curly_group_word_list(
     Some((span, group))
 }
 
+pub fn find_curly_group(params: &CompletionParams) -> Option<(Span, latex::CurlyGroup)> {
+    let data = params.feature.document.data.as_tex()?;
+    let tokens = data.root_node().token_at_offset(params.offset);
+
+    let (span, token) = match tokens.clone().find(|token| token.kind() == latex::WORD) {
+        Some(token) => (Span::from(&token), token),
+        None if matches!(tokens, TokenAtOffset::Between(_, _)) => {
+            (Span::empty(params.offset), tokens.left_biased()?)
+        }
+        None => return None,
+    };
+
+    let group = latex::CurlyGroup::cast(token.parent()?)
+        .or_else(|| {
+            token
+                .parent()
+                .and_then(|node| node.parent())
+                .and_then(latex::CurlyGroup::cast)
+        })
+        .filter(|group| is_inside_latex_curly(group, params.offset))?;
+
+    Some((span, group))
+}
+
 pub fn is_inside_latex_curly(group: &impl latex::HasCurly, offset: TextSize) -> bool {
     latex::small_range(group).contains(offset) || group.right_curly().is_none()
 }
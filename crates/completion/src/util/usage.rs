@@ -0,0 +1,116 @@
+use base_db::semantics::{Span, tex::LabelKind};
+use rowan::TextSize;
+use rustc_hash::FxHashMap;
+
+use crate::{CompletionItemData, CompletionParams};
+
+const FREQUENCY_WEIGHT: i32 = 8;
+
+const RECENCY_WEIGHT: i32 = 16;
+
+const RECENCY_WINDOW: u32 = 100;
+
+/// Tracks how often the objects of a project are used
+/// and how close their last use in the current document is to the cursor.
+#[derive(Debug, Default)]
+pub struct UsageStats<'a> {
+    commands: FxHashMap<&'a str, Usage>,
+    environments: FxHashMap<&'a str, Usage>,
+    citations: FxHashMap<&'a str, Usage>,
+    labels: FxHashMap<&'a str, Usage>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Usage {
+    count: u32,
+    distance: Option<u32>,
+}
+
+impl<'a> UsageStats<'a> {
+    pub fn new(params: &'a CompletionParams<'a>) -> Self {
+        let mut stats = Self::default();
+        let current = params.feature.document;
+        let cursor_line = current.line_index.line_col(params.offset).line;
+        for document in &params.feature.project.documents {
+            let Some(data) = document.data.as_tex() else {
+                continue;
+            };
+
+            let collector = Collector {
+                cursor: (*document == current).then_some((params.offset, cursor_line)),
+                line_index: &document.line_index,
+            };
+
+            let semantics = &data.semantics;
+            for name in &semantics.commands {
+                collector.add(&mut stats.commands, name);
+            }
+
+            for name in &semantics.environments {
+                collector.add(&mut stats.environments, name);
+            }
+
+            for citation in &semantics.citations {
+                collector.add(&mut stats.citations, &citation.name);
+            }
+
+            for label in &semantics.labels {
+                if label.kind != LabelKind::Definition {
+                    collector.add(&mut stats.labels, &label.name);
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Computes the score bonus of an item based on its usage.
+    pub fn bonus(&self, data: &CompletionItemData) -> i32 {
+        let usage = match data {
+            CompletionItemData::Command(data) => self.commands.get(data.name),
+            CompletionItemData::Environment(data) => self.environments.get(data.name),
+            CompletionItemData::Citation(data) => self.citations.get(data.entry.name.text.as_str()),
+            CompletionItemData::Label(data) => self.labels.get(data.name),
+            _ => None,
+        };
+
+        usage.map_or(0, |usage| {
+            let frequency = (u32::BITS - usage.count.leading_zeros()) as i32;
+            let recency = usage.distance.map_or(0, |distance| {
+                let remaining = RECENCY_WINDOW.saturating_sub(distance) as i32;
+                remaining * RECENCY_WEIGHT / RECENCY_WINDOW as i32
+            });
+
+            frequency * FREQUENCY_WEIGHT + recency
+        })
+    }
+}
+
+struct Collector<'a> {
+    cursor: Option<(TextSize, u32)>,
+    line_index: &'a line_index::LineIndex,
+}
+
+impl Collector<'_> {
+    fn add<'a>(&self, usages: &mut FxHashMap<&'a str, Usage>, name: &'a Span) {
+        // The word that is being completed does not count as a usage.
+        if self
+            .cursor
+            .is_some_and(|(offset, _)| name.range.contains_inclusive(offset))
+        {
+            return;
+        }
+
+        let usage = usages.entry(name.text.as_str()).or_default();
+        usage.count += 1;
+
+        let previous = self
+            .cursor
+            .filter(|(offset, _)| name.range.end() <= *offset);
+
+        if let Some((_, line)) = previous {
+            let distance = line - self.line_index.line_col(name.range.start()).line;
+            usage.distance = Some(usage.distance.map_or(distance, |d| d.min(distance)));
+        }
+    }
+}
@@ -116,6 +116,23 @@ fn test_entry() {
     )
 }
 
+#[test]
+fn test_entry_csl_json() {
+    check(
+        r#"
+%! foo.tex
+\addbibresource{baz.json}
+\cite{foo}
+      |
+      ^^^
+
+%! baz.json
+[{"id": "foo", "type": "book"}]
+         ^^^
+ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"#,
+    )
+}
+
 #[test]
 fn test_string_simple() {
     check(
@@ -4,6 +4,7 @@ use base_db::{
 use multimap::MultiMap;
 use rowan::TextRange;
 use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::BuildError;
 use url::Url;
 
 use crate::types::Diagnostic;
@@ -31,6 +32,20 @@ impl Manager {
         super::build_log::update(workspace, document, &mut self.build_log);
     }
 
+    /// Updates the diagnostics of a build log with the errors reported by a running build.
+    /// They are replaced once the log file itself is reloaded.
+    pub fn update_build_output(
+        &mut self,
+        workspace: &Workspace,
+        log_uri: Url,
+        root_document: &Document,
+        errors: &[BuildError],
+    ) {
+        if let Some(errors) = super::build_log::collect(workspace, root_document, errors) {
+            self.build_log.insert(log_uri, errors);
+        }
+    }
+
     /// Updates the ChkTeX diagnostics for the given document.
     pub fn update_chktex(&mut self, uri: Url, diagnostics: Vec<Diagnostic>) {
         self.chktex.insert(uri, diagnostics);
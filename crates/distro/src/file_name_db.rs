@@ -99,3 +99,10 @@ impl FileNameDB {
         }
     }
 }
+
+impl FromIterator<PathBuf> for FileNameDB {
+    fn from_iter<T: IntoIterator<Item = PathBuf>>(iter: T) -> Self {
+        let files = iter.into_iter().map(DistroFile).collect();
+        Self { files }
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use rustc_hash::FxHashSet;
+
+const MAX_DEPTH: usize = 8;
+
+/// A set of OpenType and TrueType font files that can be loaded with `fontspec`.
+/// The font directories are only searched once the files are requested for the first time.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct FontDB {
+    dirs: Vec<PathBuf>,
+    files: OnceLock<FxHashSet<String>>,
+}
+
+impl FontDB {
+    /// Creates a database of the fonts in the TeX distribution and the system font directories.
+    pub(crate) fn new(root_dirs: &[PathBuf]) -> Self {
+        let mut dirs = Vec::new();
+        for dir in root_dirs {
+            dirs.push(dir.join("fonts").join("opentype"));
+            dirs.push(dir.join("fonts").join("truetype"));
+        }
+
+        dirs.extend(system_font_dirs());
+        Self {
+            dirs,
+            files: OnceLock::new(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let files = self.files.get_or_init(|| {
+            let mut files = FxHashSet::default();
+            for dir in &self.dirs {
+                read_dir(&mut files, dir, 0);
+            }
+
+            files
+        });
+
+        files.iter().map(String::as_str)
+    }
+}
+
+impl FromIterator<PathBuf> for FontDB {
+    fn from_iter<T: IntoIterator<Item = PathBuf>>(iter: T) -> Self {
+        let files: FxHashSet<_> = iter.into_iter().filter_map(font_file_name).collect();
+        Self {
+            dirs: Vec::new(),
+            files: OnceLock::from(files),
+        }
+    }
+}
+
+fn read_dir(files: &mut FxHashSet<String>, dir: &Path, depth: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(ty) if ty.is_dir() && depth < MAX_DEPTH => read_dir(files, &path, depth + 1),
+            Ok(ty) if ty.is_file() => files.extend(font_file_name(path)),
+            _ => {}
+        }
+    }
+}
+
+fn font_file_name(path: PathBuf) -> Option<String> {
+    if !is_font_file(&path) {
+        return None;
+    }
+
+    Some(path.file_name()?.to_str()?.to_string())
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "otf" | "ttf" | "ttc"))
+}
+
+fn system_font_dirs() -> Vec<PathBuf> {
+    let home_dir = env::var_os("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+    if cfg!(windows) {
+        dirs.extend(env::var_os("WINDIR").map(|dir| PathBuf::from(dir).join("Fonts")));
+        dirs.extend(env::var_os("LOCALAPPDATA").map(|dir| {
+            PathBuf::from(dir)
+                .join("Microsoft")
+                .join("Windows")
+                .join("Fonts")
+        }));
+    } else if cfg!(target_os = "macos") {
+        dirs.push("/System/Library/Fonts".into());
+        dirs.push("/Library/Fonts".into());
+        dirs.extend(home_dir.map(|dir| dir.join("Library").join("Fonts")));
+    } else {
+        dirs.push("/usr/share/fonts".into());
+        dirs.push("/usr/local/share/fonts".into());
+        if let Some(home_dir) = home_dir {
+            dirs.push(home_dir.join(".fonts"));
+            dirs.push(home_dir.join(".local").join("share").join("fonts"));
+        }
+    }
+
+    dirs
+}
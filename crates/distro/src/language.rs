@@ -4,6 +4,7 @@ use std::path::Path;
 pub enum Language {
     Tex,
     Bib,
+    CslJson,
     Aux,
     Log,
     Root,
@@ -38,10 +39,20 @@ impl Language {
         }
     }
 
+    /// Like `from_path`, but also recognizes JSON bibliographies.
+    /// Most JSON files are unrelated to TeX, so they are only loaded when a document links them.
+    pub fn from_linked_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Self::CslJson),
+            _ => Self::from_path(path),
+        }
+    }
+
     pub fn from_id(id: &str) -> Option<Self> {
         match id {
             "tex" | "latex" => Some(Self::Tex),
             "bib" | "bibtex" => Some(Self::Bib),
+            "json" => Some(Self::CslJson),
             "texlabroot" => Some(Self::Root),
             _ => None,
         }
@@ -1,4 +1,5 @@
 mod file_name_db;
+mod font_db;
 mod kpsewhich;
 mod language;
 mod miktex;
@@ -11,7 +12,7 @@ use std::{
 
 use anyhow::Result;
 
-pub use self::{file_name_db::FileNameDB, language::Language};
+pub use self::{file_name_db::FileNameDB, font_db::FontDB, language::Language};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum DistroKind {
@@ -31,6 +32,7 @@ impl Default for DistroKind {
 pub struct Distro {
     pub kind: DistroKind,
     pub file_name_db: FileNameDB,
+    pub font_db: FontDB,
 }
 
 impl Distro {
@@ -61,21 +63,27 @@ impl Distro {
             }
         };
 
-        let mut file_name_db = match kind {
+        let (mut file_name_db, font_db) = match kind {
             DistroKind::Texlive => {
                 let root_dirs = kpsewhich::root_directories()?;
-                FileNameDB::parse(&root_dirs, &mut texlive::read_database)?
+                let file_name_db = FileNameDB::parse(&root_dirs, &mut texlive::read_database)?;
+                (file_name_db, FontDB::new(&root_dirs))
             }
             DistroKind::Miktex => {
                 let root_dirs = kpsewhich::root_directories()?;
-                FileNameDB::parse(&root_dirs, &mut miktex::read_database)?
+                let file_name_db = FileNameDB::parse(&root_dirs, &mut miktex::read_database)?;
+                (file_name_db, FontDB::new(&root_dirs))
             }
-            DistroKind::Tectonic | DistroKind::Unknown => FileNameDB::default(),
+            DistroKind::Tectonic | DistroKind::Unknown => (FileNameDB::default(), FontDB::new(&[])),
         };
 
         Self::read_env_dir(&mut file_name_db, "TEXINPUTS");
         Self::read_env_dir(&mut file_name_db, "BIBINPUTS");
-        Ok(Self { kind, file_name_db })
+        Ok(Self {
+            kind,
+            file_name_db,
+            font_db,
+        })
     }
 
     fn read_env_dir(file_name_db: &mut FileNameDB, env_var: &str) {
@@ -1,4 +1,8 @@
-use base_db::{DocumentData, util::queries};
+use base_db::{
+    DocumentData,
+    semantics::bib::{EntryIndex, ResolvedEntry},
+    util::queries,
+};
 use rowan::ast::AstNode;
 use syntax::bibtex;
 
@@ -16,7 +20,7 @@ pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
             )?;
             (&result.object.name.text, result.range)
         }
-        DocumentData::Bib(data) => {
+        DocumentData::Bib(data) | DocumentData::CslJson(data) => {
             let result = queries::object_at_cursor(
                 &data.semantics.entries,
                 *offset,
@@ -27,11 +31,15 @@ pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
         _ => return None,
     };
 
+    let style = citeproc::Style::from_project(feature.workspace, &feature.project);
+    let language = feature.project.language();
+    let index = EntryIndex::new(feature.workspace);
     let text = feature.project.documents.iter().find_map(|document| {
-        let data = document.data.as_bib()?;
+        let data = document.data.as_bibliography()?;
         let root = bibtex::Root::cast(data.root_node())?;
         let entry = root.find_entry(name)?;
-        citeproc::render(&entry, &data.semantics)
+        let entry = ResolvedEntry::resolve(&index, entry, &data.semantics);
+        citeproc::render(&entry, style.as_deref(), language)
     })?;
 
     let data = HoverData::Citation(text);
@@ -48,6 +48,160 @@ fn test_latex_citation() {
     );
 }
 
+#[test]
+fn test_latex_citation_crossref() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\addbibresource{proceedings.bib}
+\cite{foo}
+       |
+      ^^^
+%! main.bib
+@inproceedings{foo, author = {Foo Bar}, title = {Baz Qux}, crossref = {conf}}
+%! proceedings.bib
+@proceedings{conf, title = {Conference}, editor = {Qux Quux}, publisher = {Corge}, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Baz Qux\". *Conference*. Ed. by Q. Quux. Corge, 1337.",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_babel() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\usepackage[english, main=ngerman]{babel}
+\addbibresource{main.bib}
+\begin{document}
+\cite{foo}
+       |
+      ^^^
+\end{document}
+%! main.bib
+@book{foo, author = {Foo Bar}, editor = {Baz Qux}, title = {Titel}, edition = 2, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Titel\". Hrsg. von B. Qux. 2. Aufl. 1337.",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_polyglossia() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\usepackage{polyglossia}
+\setdefaultlanguage[variant=spanish]{spanish}
+\addbibresource{main.bib}
+\begin{document}
+\cite{foo}
+       |
+      ^^^
+\end{document}
+%! main.bib
+@book{foo, author = {Foo Bar}, editor = {Baz Qux}, title = {Titel}, edition = 2, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Titel\". Ed. por B. Qux. 2.º ed. 1337.",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_biblatex_style() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\usepackage[backend=biber, style=apa]{biblatex}
+\addbibresource{main.bib}
+\begin{document}
+\cite{foo}
+       |
+      ^^^
+\end{document}
+%! main.bib
+@book{foo, author = {Foo Bar}, title = {Title}, publisher = {Baz}, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "Bar, F. (1337). *Title*. Baz.",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_bibliography_style() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\cite{foo}
+       |
+      ^^^
+\bibliographystyle{IEEEtran}
+\bibliography{main}
+\end{document}
+%! main.bib
+@book{foo, author = {Foo Bar}, title = {Title}, publisher = {Baz}, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar, *Title*, Baz, 1337.",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_csl_json() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.json}
+\cite{foo}
+       |
+      ^^^
+%! main.json
+[
+  {
+    "id": "foo",
+    "type": "article-journal",
+    "author": [{ "family": "Bar", "given": "Foo" }],
+    "title": "Baz Qux",
+    "issued": { "date-parts": [[1337]] }
+  }
+]"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Baz Qux\". (1337).",
+                ),
+            )
+        "#]],
+    );
+}
+
 #[test]
 fn test_bibtex_entry_key() {
     check(
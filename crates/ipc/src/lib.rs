@@ -1,9 +1,10 @@
 use std::{
     io::{self, BufRead, BufReader, BufWriter, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 #[cfg(unix)]
 use std::os::unix::net::{UnixListener, UnixStream};
@@ -11,27 +12,90 @@ use std::os::unix::net::{UnixListener, UnixStream};
 #[cfg(windows)]
 use uds_windows::{UnixListener, UnixStream};
 
-fn socket_path() -> PathBuf {
+/// Describes a running server in the registry of instances.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub pid: u32,
+    pub folders: Vec<PathBuf>,
+    pub started: u64,
+}
+
+impl Instance {
+    fn socket_path(&self) -> PathBuf {
+        registry_dir().join(format!("{}.sock", self.pid))
+    }
+
+    fn info_path(&self) -> PathBuf {
+        registry_dir().join(format!("{}.json", self.pid))
+    }
+
+    fn remove(&self) {
+        let _ = std::fs::remove_file(self.socket_path());
+        let _ = std::fs::remove_file(self.info_path());
+    }
+}
+
+/// Keeps the socket of the current instance registered until it is dropped.
+#[derive(Debug)]
+pub struct ServerHandle {
+    instance: Instance,
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        self.instance.remove();
+    }
+}
+
+fn registry_dir() -> PathBuf {
     dirs::runtime_dir()
         .unwrap_or(std::env::temp_dir())
-        .join("texlab.sock")
+        .join("texlab")
 }
 
-pub fn send_request<T: Serialize>(msg: T) -> io::Result<()> {
-    let stream = UnixStream::connect(socket_path())?;
-    let mut conn = BufWriter::new(stream);
-    serde_json::to_writer(&mut conn, &msg)?;
-    Ok(())
+/// Sends a message to the instance whose workspace contains `path`.
+/// Falls back to the most recently started instance if none of them does.
+pub fn send_request<T: Serialize>(path: &Path, msg: T) -> io::Result<()> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no running instance found");
+    for instance in select_instances(read_instances(), path) {
+        match UnixStream::connect(instance.socket_path()) {
+            Ok(stream) => {
+                let mut conn = BufWriter::new(stream);
+                serde_json::to_writer(&mut conn, &msg)?;
+                return Ok(());
+            }
+            Err(why) => {
+                log::info!("Removing stale instance {}: {why}", instance.pid);
+                instance.remove();
+                last_error = why;
+            }
+        }
+    }
+
+    Err(last_error)
 }
 
-pub fn spawn_server<T, F>(mut event_handler: F) -> io::Result<()>
+pub fn spawn_server<T, F>(folders: Vec<PathBuf>, mut event_handler: F) -> io::Result<ServerHandle>
 where
     T: DeserializeOwned,
     F: FnMut(T) + Send + 'static,
 {
-    let socket_path = socket_path();
+    std::fs::create_dir_all(registry_dir())?;
+
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64);
+
+    let instance = Instance {
+        pid: std::process::id(),
+        folders,
+        started,
+    };
+
+    let socket_path = instance.socket_path();
     let _ = std::fs::remove_file(&socket_path);
     let listener = UnixListener::bind(socket_path)?;
+    std::fs::write(instance.info_path(), serde_json::to_vec(&instance)?)?;
 
     std::thread::spawn(move || {
         for conn in listener.incoming().flatten() {
@@ -39,7 +103,38 @@ where
         }
     });
 
-    Ok(())
+    Ok(ServerHandle { instance })
+}
+
+fn read_instances() -> Vec<Instance> {
+    let Ok(entries) = std::fs::read_dir(registry_dir()) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| std::fs::read(path).ok())
+        .filter_map(|data| serde_json::from_slice(&data).ok())
+        .collect()
+}
+
+/// Orders the instances by how well their workspace folders match `path`,
+/// preferring recently started instances among equally good matches.
+fn select_instances(mut instances: Vec<Instance>, path: &Path) -> Vec<Instance> {
+    let match_length = |instance: &Instance| {
+        instance
+            .folders
+            .iter()
+            .filter(|folder| path.starts_with(folder))
+            .map(|folder| folder.components().count())
+            .max()
+            .unwrap_or(0)
+    };
+
+    instances.sort_by_key(|instance| std::cmp::Reverse((match_length(instance), instance.started)));
+    instances
 }
 
 fn handle_request<T, F>(conn: impl Read, event_handler: &mut F) -> io::Result<()>
@@ -54,3 +149,38 @@ where
     event_handler(msg);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{Instance, select_instances};
+
+    fn instance(pid: u32, folders: &[&str], started: u64) -> Instance {
+        Instance {
+            pid,
+            folders: folders.iter().map(Into::into).collect(),
+            started,
+        }
+    }
+
+    #[test]
+    fn test_select_instances() {
+        let instances = vec![
+            instance(1, &["/home/user/thesis"], 1),
+            instance(2, &["/home/user"], 2),
+            instance(3, &[], 3),
+        ];
+
+        let pids = |path: &str| -> Vec<u32> {
+            select_instances(instances.clone(), Path::new(path))
+                .into_iter()
+                .map(|instance| instance.pid)
+                .collect()
+        };
+
+        assert_eq!(pids("/home/user/thesis/main.tex"), [1, 2, 3]);
+        assert_eq!(pids("/home/user/paper/main.tex"), [2, 3, 1]);
+        assert_eq!(pids("/tmp/main.tex"), [3, 2, 1]);
+    }
+}
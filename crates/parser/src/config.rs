@@ -13,6 +13,7 @@ pub struct SyntaxConfig {
     pub label_definition_prefixes: Vec<(String, String)>,
     pub label_reference_commands: FxHashSet<String>,
     pub label_reference_prefixes: Vec<(String, String)>,
+    pub label_reference_kinds: Vec<(String, Vec<String>)>,
     pub label_reference_range_commands: FxHashSet<String>,
 }
 
@@ -63,6 +64,11 @@ impl Default for SyntaxConfig {
             .map(|(x, y)| (ToString::to_string(x), ToString::to_string(y)))
             .collect();
 
+        let label_reference_kinds = DEFAULT_LABEL_REFERENCE_KINDS
+            .iter()
+            .map(|(x, y)| (x.to_string(), y.iter().map(ToString::to_string).collect()))
+            .collect();
+
         let label_reference_range_commands = DEFAULT_LABEL_REFERENCE_RANGE_COMMANDS
             .iter()
             .map(ToString::to_string)
@@ -80,6 +86,7 @@ impl Default for SyntaxConfig {
             label_definition_prefixes,
             label_reference_commands,
             label_reference_prefixes,
+            label_reference_kinds,
             label_reference_range_commands,
         }
     }
@@ -273,10 +280,26 @@ static DEFAULT_LABEL_REFERENCE_COMMANDS: &[&str] = &[
     "labelcref",
     "labelcpageref",
     "eqref",
+    "figref",
+    "tabref",
+    "secref",
+    "thmref",
+    "lstref",
+    "algref",
 ];
 
 static DEFAULT_LABEL_REFERENCE_PREFIXES: &[(&str, &str)] = &[];
 
+static DEFAULT_LABEL_REFERENCE_KINDS: &[(&str, &[&str])] = &[
+    ("eqref", &["equation"]),
+    ("figref", &["figure"]),
+    ("tabref", &["table"]),
+    ("secref", &["section"]),
+    ("thmref", &["theorem"]),
+    ("lstref", &["listing"]),
+    ("algref", &["algorithm"]),
+];
+
 static DEFAULT_LABEL_REFERENCE_RANGE_COMMANDS: &[&str] = &[
     "crefrange",
     "crefrange*",
@@ -0,0 +1,537 @@
+use std::fmt;
+
+use rowan::{TextRange, TextSize};
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::{Value, value::RawValue};
+use syntax::csl_json::{CslJsonData, CslJsonItem, is_valid_key};
+
+/// Parses a CSL-JSON array or a BetterBibTeX JSON export (an object with an `items` array).
+/// Other JSON documents like `package.json` are not bibliographies and yield no items.
+pub fn parse_csl_json(input: &str) -> CslJsonData {
+    let items = parse_csl_items(input)
+        .or_else(|| parse_zotero_items(input))
+        .unwrap_or_default();
+
+    CslJsonData { items }
+}
+
+fn parse_csl_items(input: &str) -> Option<Vec<CslJsonItem>> {
+    let items = parse_objects(input, input)?;
+    let is_csl = !items.is_empty()
+        && items
+            .iter()
+            .all(|(_, item)| item.get("id").is_some() && item.get("type").is_some());
+
+    is_csl.then(|| {
+        items
+            .iter()
+            .filter_map(|(range, item)| convert_csl_item(input, *range, item))
+            .collect()
+    })
+}
+
+fn parse_zotero_items(input: &str) -> Option<Vec<CslJsonItem>> {
+    let root: Object = serde_json::from_str(input).ok()?;
+    let items = parse_objects(input, root.get("items")?.get())?;
+    let is_zotero = items.iter().all(|(_, item)| item.get("itemType").is_some());
+
+    is_zotero.then(|| {
+        items
+            .iter()
+            .filter_map(|(range, item)| convert_zotero_item(input, *range, item))
+            .collect()
+    })
+}
+
+/// Parses an array of objects, where `text` is a slice of `input`.
+fn parse_objects<'a>(input: &str, text: &'a str) -> Option<Vec<(TextRange, Object<'a>)>> {
+    let elements: Vec<&RawValue> = serde_json::from_str(text).ok()?;
+    elements
+        .into_iter()
+        .map(|raw| Some((range_of(input, raw), serde_json::from_str(raw.get()).ok()?)))
+        .collect()
+}
+
+static CSL_TYPES: &[(&str, &str)] = &[
+    ("article", "article"),
+    ("article-journal", "article"),
+    ("article-magazine", "article"),
+    ("article-newspaper", "article"),
+    ("book", "book"),
+    ("chapter", "incollection"),
+    ("dataset", "dataset"),
+    ("entry-dictionary", "inreference"),
+    ("entry-encyclopedia", "inreference"),
+    ("manuscript", "unpublished"),
+    ("paper-conference", "inproceedings"),
+    ("patent", "patent"),
+    ("post", "online"),
+    ("post-weblog", "online"),
+    ("report", "report"),
+    ("software", "software"),
+    ("thesis", "thesis"),
+    ("webpage", "online"),
+];
+
+static CSL_FIELDS: &[(&str, &str)] = &[
+    ("title", "title"),
+    ("title-short", "shorttitle"),
+    ("collection-title", "series"),
+    ("volume", "volume"),
+    ("issue", "number"),
+    ("number", "number"),
+    ("page", "pages"),
+    ("publisher", "publisher"),
+    ("publisher-place", "location"),
+    ("edition", "edition"),
+    ("event", "eventtitle"),
+    ("event-title", "eventtitle"),
+    ("genre", "type"),
+    ("version", "version"),
+    ("language", "language"),
+    ("keyword", "keywords"),
+    ("note", "note"),
+    ("abstract", "abstract"),
+    ("DOI", "doi"),
+    ("URL", "url"),
+    ("ISBN", "isbn"),
+    ("ISSN", "issn"),
+];
+
+static CSL_NAMES: &[(&str, &str)] = &[
+    ("author", "author"),
+    ("editor", "editor"),
+    ("translator", "translator"),
+];
+
+static CSL_DATES: &[(&str, &str)] = &[("issued", "date"), ("accessed", "urldate")];
+
+static ZOTERO_TYPES: &[(&str, &str)] = &[
+    ("blogPost", "online"),
+    ("book", "book"),
+    ("bookSection", "incollection"),
+    ("computerProgram", "software"),
+    ("conferencePaper", "inproceedings"),
+    ("dataset", "dataset"),
+    ("dictionaryEntry", "inreference"),
+    ("encyclopediaArticle", "inreference"),
+    ("forumPost", "online"),
+    ("journalArticle", "article"),
+    ("magazineArticle", "article"),
+    ("manuscript", "unpublished"),
+    ("newspaperArticle", "article"),
+    ("patent", "patent"),
+    ("report", "report"),
+    ("thesis", "thesis"),
+    ("webpage", "online"),
+];
+
+static ZOTERO_FIELDS: &[(&str, &str)] = &[
+    ("title", "title"),
+    ("shortTitle", "shorttitle"),
+    ("publicationTitle", "journal"),
+    ("bookTitle", "booktitle"),
+    ("proceedingsTitle", "booktitle"),
+    ("encyclopediaTitle", "booktitle"),
+    ("dictionaryTitle", "booktitle"),
+    ("series", "series"),
+    ("volume", "volume"),
+    ("issue", "number"),
+    ("number", "number"),
+    ("reportNumber", "number"),
+    ("patentNumber", "number"),
+    ("pages", "pages"),
+    ("publisher", "publisher"),
+    ("institution", "institution"),
+    ("university", "school"),
+    ("place", "location"),
+    ("edition", "edition"),
+    ("conferenceName", "eventtitle"),
+    ("thesisType", "type"),
+    ("reportType", "type"),
+    ("versionNumber", "version"),
+    ("language", "language"),
+    ("date", "date"),
+    ("accessDate", "urldate"),
+    ("abstractNote", "abstract"),
+    ("DOI", "doi"),
+    ("url", "url"),
+    ("ISBN", "isbn"),
+    ("ISSN", "issn"),
+];
+
+fn lookup(table: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| *value)
+}
+
+fn convert_csl_item(input: &str, full_range: TextRange, item: &Object) -> Option<CslJsonItem> {
+    let (key, key_range) = ["citation-key", "id"]
+        .into_iter()
+        .find_map(|name| item.get(name))
+        .and_then(|raw| citation_key(input, raw))?;
+
+    let entry_type = item
+        .string("type")
+        .and_then(|ty| lookup(CSL_TYPES, &ty))
+        .unwrap_or("misc");
+
+    let mut fields = Vec::new();
+    for (name, raw) in item.members() {
+        let value = parse_value(raw);
+        let field = if let Some(field) = lookup(CSL_FIELDS, name) {
+            as_scalar(&value).map(|text| (field, text))
+        } else if name == "container-title" {
+            let field = if entry_type == "article" {
+                "journal"
+            } else {
+                "booktitle"
+            };
+
+            as_scalar(&value).map(|text| (field, text))
+        } else if let Some(field) = lookup(CSL_NAMES, name) {
+            let names = elements(&value)
+                .filter_map(convert_csl_name)
+                .collect::<Vec<_>>();
+
+            (!names.is_empty()).then(|| (field, names.join(" and ")))
+        } else {
+            lookup(CSL_DATES, name).and_then(|field| Some((field, convert_csl_date(&value)?)))
+        };
+
+        fields.extend(field.map(|(name, text)| (name.into(), text)));
+    }
+
+    Some(CslJsonItem {
+        key,
+        key_range,
+        full_range,
+        entry_type: entry_type.into(),
+        fields,
+    })
+}
+
+fn convert_csl_name(value: &Value) -> Option<String> {
+    if let Some(literal) = value.get("literal").and_then(Value::as_str) {
+        return Some(format!("{{{literal}}}"));
+    }
+
+    let family = value.get("family").and_then(Value::as_str)?;
+    let family = match value.get("non-dropping-particle").and_then(Value::as_str) {
+        Some(particle) => format!("{particle} {family}"),
+        None => family.to_string(),
+    };
+
+    let given = value.get("given").and_then(Value::as_str);
+    let suffix = value.get("suffix").and_then(Value::as_str);
+    Some(match (given, suffix) {
+        (Some(given), Some(suffix)) => format!("{family}, {suffix}, {given}"),
+        (Some(given), None) => format!("{family}, {given}"),
+        (None, _) => family,
+    })
+}
+
+fn convert_csl_date(value: &Value) -> Option<String> {
+    if let Some(parts) = value.get("date-parts").and_then(Value::as_array) {
+        let parts = elements(parts.first()?)
+            .filter_map(as_scalar)
+            .filter_map(|part| part.parse::<u32>().ok())
+            .collect::<Vec<_>>();
+
+        return match parts.as_slice() {
+            [year] => Some(format!("{year}")),
+            [year, month] => Some(format!("{year}-{month:02}")),
+            [year, month, day, ..] => Some(format!("{year}-{month:02}-{day:02}")),
+            [] => None,
+        };
+    }
+
+    ["raw", "literal"]
+        .into_iter()
+        .find_map(|name| value.get(name))
+        .and_then(as_scalar)
+}
+
+fn convert_zotero_item(input: &str, full_range: TextRange, item: &Object) -> Option<CslJsonItem> {
+    let (key, key_range) = ["citationKey", "citekey"]
+        .into_iter()
+        .find_map(|name| item.get(name))
+        .and_then(|raw| citation_key(input, raw))?;
+
+    let entry_type = item
+        .string("itemType")
+        .and_then(|ty| lookup(ZOTERO_TYPES, &ty))
+        .unwrap_or("misc");
+
+    let mut fields = Vec::new();
+    for (name, raw) in item.members() {
+        let value = parse_value(raw);
+        if let Some(field) = lookup(ZOTERO_FIELDS, name) {
+            if let Some(text) = as_scalar(&value).filter(|text| !text.is_empty()) {
+                fields.push((field.into(), text));
+            }
+        } else if name == "creators" {
+            let mut creators: Vec<(&str, Vec<String>)> = Vec::new();
+            for creator in elements(&value) {
+                let Some(name) = convert_zotero_creator(creator) else {
+                    continue;
+                };
+
+                let field = match creator.get("creatorType").and_then(Value::as_str) {
+                    Some("editor" | "seriesEditor") => "editor",
+                    Some("translator") => "translator",
+                    _ => "author",
+                };
+
+                match creators.iter_mut().find(|(name, _)| *name == field) {
+                    Some((_, names)) => names.push(name),
+                    None => creators.push((field, vec![name])),
+                }
+            }
+
+            for (field, names) in creators {
+                fields.push((field.into(), names.join(" and ")));
+            }
+        } else if name == "tags" {
+            let tags = elements(&value)
+                .filter_map(|tag| tag.get("tag").and_then(Value::as_str))
+                .collect::<Vec<_>>();
+
+            if !tags.is_empty() {
+                fields.push(("keywords".into(), tags.join(", ")));
+            }
+        }
+    }
+
+    Some(CslJsonItem {
+        key,
+        key_range,
+        full_range,
+        entry_type: entry_type.into(),
+        fields,
+    })
+}
+
+fn convert_zotero_creator(value: &Value) -> Option<String> {
+    if let Some(name) = value.get("name").and_then(Value::as_str) {
+        return Some(format!("{{{name}}}"));
+    }
+
+    let last_name = value.get("lastName").and_then(Value::as_str)?;
+    Some(match value.get("firstName").and_then(Value::as_str) {
+        Some(first_name) if !first_name.is_empty() => format!("{last_name}, {first_name}"),
+        _ => last_name.to_string(),
+    })
+}
+
+/// Reads the citation key of an item together with its range in the source.
+/// Keys that are written with escape sequences do not have a contiguous range
+/// and keys that are not valid in BibTeX cannot be cited, so both are rejected.
+fn citation_key(input: &str, raw: &RawValue) -> Option<(String, TextRange)> {
+    let key = as_scalar(&parse_value(raw))?;
+    let range = range_of(input, raw);
+    let range = match raw.get().strip_prefix('"') {
+        Some(text) if text.strip_suffix('"') == Some(key.as_str()) => TextRange::new(
+            range.start() + TextSize::from(1),
+            range.end() - TextSize::from(1),
+        ),
+        Some(_) => return None,
+        None => range,
+    };
+
+    is_valid_key(&key).then_some((key, range))
+}
+
+/// Computes the range of a value that was borrowed from `input`.
+fn range_of(input: &str, raw: &RawValue) -> TextRange {
+    let start = raw.get().as_ptr() as usize - input.as_ptr() as usize;
+    let end = start + raw.get().len();
+    TextRange::new((start as u32).into(), (end as u32).into())
+}
+
+fn parse_value(raw: &RawValue) -> Value {
+    serde_json::from_str(raw.get()).unwrap_or_default()
+}
+
+fn as_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn elements(value: &Value) -> impl Iterator<Item = &Value> {
+    value.as_array().into_iter().flatten()
+}
+
+/// The members of a JSON object in source order.
+/// The values are borrowed from the input, which allows computing their ranges.
+struct Object<'a>(Vec<(String, &'a RawValue)>);
+
+impl<'a> Object<'a> {
+    fn get(&self, name: &str) -> Option<&'a RawValue> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn string(&self, name: &str) -> Option<String> {
+        match parse_value(self.get(name)?) {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn members(&self) -> impl Iterator<Item = (&str, &'a RawValue)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), *value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Object<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ObjectVisitor;
+
+        impl<'de> Visitor<'de> for ObjectVisitor {
+            type Value = Object<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut members = Vec::new();
+                while let Some(member) = map.next_entry()? {
+                    members.push(member);
+                }
+
+                Ok(Object(members))
+            }
+        }
+
+        deserializer.deserialize_map(ObjectVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{Expect, expect};
+
+    use super::*;
+
+    fn check(input: &str, expect: Expect) {
+        let data = parse_csl_json(input);
+        expect.assert_eq(&data.to_bibtex());
+    }
+
+    #[test]
+    fn test_csl_json() {
+        check(
+            r#"[
+  {
+    "id": "foo",
+    "type": "article-journal",
+    "title": "A {Title} with \"quotes\" é",
+    "author": [
+      { "family": "Doe", "given": "John" },
+      { "literal": "ACME Corp." }
+    ],
+    "container-title": "Journal",
+    "volume": 3,
+    "issued": { "date-parts": [[2020, 1, 5]] }
+  }
+]"#,
+            expect![[r#"
+                @article{foo,
+                  title = {A {Title} with "quotes" é},
+                  author = {Doe, John and {ACME Corp.}},
+                  journal = {Journal},
+                  volume = {3},
+                  date = {2020-01-05},
+                }
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_better_bibtex_json() {
+        check(
+            r#"{
+  "config": {},
+  "items": [
+    {
+      "itemType": "bookSection",
+      "citationKey": "bar",
+      "title": "Chapter",
+      "bookTitle": "Book",
+      "creators": [
+        { "creatorType": "author", "firstName": "Jane", "lastName": "Roe" },
+        { "creatorType": "editor", "firstName": "Max", "lastName": "Mustermann" }
+      ],
+      "date": "2021",
+      "tags": [{ "tag": "foo" }, { "tag": "bar" }]
+    }
+  ]
+}"#,
+            expect![[r#"
+                @incollection{bar,
+                  title = {Chapter},
+                  booktitle = {Book},
+                  author = {Roe, Jane},
+                  editor = {Mustermann, Max},
+                  date = {2021},
+                  keywords = {foo, bar},
+                }
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_key_range() {
+        let input = r#"[{"id": "foo", "type": "book"}]"#;
+        let data = parse_csl_json(input);
+        let item = &data.items[0];
+        assert_eq!(&input[item.key_range], "foo");
+        assert_eq!(&input[item.full_range], r#"{"id": "foo", "type": "book"}"#);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(parse_csl_json(r#"[{"id": "foo""#), CslJsonData::default());
+    }
+
+    #[test]
+    fn test_not_a_bibliography() {
+        let input = r#"{"name": "foo", "version": "1.0.0", "items": [{"id": "bar"}]}"#;
+        assert_eq!(parse_csl_json(input), CslJsonData::default());
+        assert_eq!(
+            parse_csl_json(r#"[{"id": 1}, "foo"]"#),
+            CslJsonData::default()
+        );
+    }
+
+    #[test]
+    fn test_escaped_key() {
+        check(
+            r#"[{"id": "f\u006fo", "type": "book"}, {"id": "bar", "type": "book"}]"#,
+            expect![[r#"
+                @book{bar,
+                }
+
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        check(
+            r#"[{"id": "foo,bar", "type": "book"}, {"id": "{baz}", "type": "book"}]"#,
+            expect![[""]],
+        );
+    }
+}
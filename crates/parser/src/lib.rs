@@ -1,12 +1,13 @@
 mod bibtex;
 mod build_log;
 mod config;
+mod csl_json;
 mod file_list;
 mod latex;
 mod latexmkrc;
 pub(crate) mod util;
 
 pub use self::{
-    bibtex::parse_bibtex, build_log::parse_build_log, config::*, file_list::parse_file_list,
-    latex::parse_latex, latexmkrc::parse_latexmkrc,
+    bibtex::parse_bibtex, build_log::parse_build_log, config::*, csl_json::parse_csl_json,
+    file_list::parse_file_list, latex::parse_latex, latexmkrc::parse_latexmkrc,
 };
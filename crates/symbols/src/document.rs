@@ -19,7 +19,8 @@ pub fn document_symbols(workspace: &Workspace, document: &Document) -> Vec<Symbo
                 .filter_map(|node| builder.visit(&node))
                 .collect()
         }
-        DocumentData::Aux(_)
+        DocumentData::CslJson(_)
+        | DocumentData::Aux(_)
         | DocumentData::Log(_)
         | DocumentData::Root
         | DocumentData::Latexmkrc(_)
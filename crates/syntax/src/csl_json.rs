@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use rowan::TextRange;
+
+/// A bibliography exported as CSL-JSON or as BetterBibTeX JSON.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CslJsonData {
+    pub items: Vec<CslJsonItem>,
+}
+
+/// A single item of a JSON bibliography, translated to BibTeX terms.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CslJsonItem {
+    pub key: String,
+    pub key_range: TextRange,
+    pub full_range: TextRange,
+    pub entry_type: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl CslJsonData {
+    /// Renders the bibliography as BibTeX source.
+    pub fn to_bibtex(&self) -> String {
+        let mut output = String::new();
+        for item in self.items.iter().filter(|item| is_valid_key(&item.key)) {
+            let _ = writeln!(output, "@{}{{{},", item.entry_type, item.key);
+            for (name, value) in &item.fields {
+                let _ = writeln!(output, "  {} = {{{}}},", name, escape(value));
+            }
+
+            output.push_str("}\n\n");
+        }
+
+        output
+    }
+}
+
+/// Checks whether a citation key can be written as the key of a BibTeX entry.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '(' | ')' | '{' | '}' | '@' | ',' | '='))
+}
+
+fn escape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    for c in value.chars() {
+        match c {
+            '{' => {
+                depth += 1;
+                output.push(c);
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                output.push(c);
+            }
+            '}' => output.push_str("\\}"),
+            _ => output.push(c),
+        }
+    }
+
+    for _ in 0..depth {
+        output.push('}');
+    }
+
+    output
+}
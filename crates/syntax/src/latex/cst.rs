@@ -461,6 +461,10 @@ impl Include {
         self.syntax().first_token()
     }
 
+    pub fn options(&self) -> Option<BrackGroupKeyValue> {
+        self.syntax().children().find_map(BrackGroupKeyValue::cast)
+    }
+
     pub fn path_list(&self) -> Option<CurlyGroupWordList> {
         self.syntax().children().find_map(CurlyGroupWordList::cast)
     }
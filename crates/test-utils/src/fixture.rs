@@ -29,7 +29,8 @@ impl Fixture {
         let mut workspace = Workspace::default();
         for document in &documents {
             let path = PathBuf::from(document.uri.path());
-            let language = distro::Language::from_path(&path).unwrap_or(distro::Language::Tex);
+            let language =
+                distro::Language::from_linked_path(&path).unwrap_or(distro::Language::Tex);
 
             workspace.open(
                 document.uri.clone(),
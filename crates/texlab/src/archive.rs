@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use commands::ArchiveCommand;
+use url::Url;
+
+use crate::check::load_workspace;
+
+/// Collects the files of the project that contains the given file.
+pub fn prepare_archive(
+    path: &Path,
+    output_path: Option<PathBuf>,
+    config: Option<&Path>,
+) -> Result<ArchiveCommand> {
+    let path = path.canonicalize()?;
+    let Some(dir) = path.parent() else {
+        anyhow::bail!("invalid path: {}", path.display())
+    };
+
+    let workspace = load_workspace(dir, config)?;
+    let Ok(uri) = Url::from_file_path(&path) else {
+        anyhow::bail!("invalid path: {}", path.display())
+    };
+
+    Ok(ArchiveCommand::new(&workspace, &uri, output_path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prepare_archive;
+
+    #[test]
+    fn test_prepare_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        std::fs::write(
+            dir.join("main.tex"),
+            "\\documentclass{article}\n\\begin{document}\n\\input{intro}\n\\input{missing}\n\\end{document}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("intro.tex"), "Intro\n").unwrap();
+        std::fs::write(dir.join("unused.tex"), "Unused\n").unwrap();
+
+        let command = prepare_archive(&dir.join("main.tex"), None, None).unwrap();
+        let mut files: Vec<_> = command
+            .files()
+            .map(|path| {
+                path.strip_prefix(&dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+
+        files.sort();
+        assert_eq!(files, ["intro.tex", "main.tex"]);
+        assert!(command.missing().iter().any(|name| name == "missing"));
+        assert_eq!(command.unreferenced(), [std::path::Path::new("unused.tex")]);
+    }
+}
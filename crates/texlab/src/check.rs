@@ -0,0 +1,355 @@
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use base_db::{Owner, Workspace};
+use distro::{Distro, Language};
+use line_index::LineCol;
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+use rustc_hash::FxHashSet;
+use url::Url;
+
+use crate::util::{from_proto, to_proto};
+
+/// The format in which `texlab check` prints the diagnostics.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+}
+
+/// The minimum severity of a diagnostic that fails the check.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+impl From<Severity> for DiagnosticSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Information => DiagnosticSeverity::INFORMATION,
+            Severity::Hint => DiagnosticSeverity::HINT,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CheckOptions {
+    /// The directory that contains the project.
+    pub dir: PathBuf,
+
+    /// A JSON file with the same settings that an editor would send.
+    pub config: Option<PathBuf>,
+
+    /// Run ChkTeX on every TeX document.
+    pub chktex: bool,
+}
+
+/// The diagnostics of a project, grouped by file.
+#[derive(Debug)]
+pub struct Report {
+    dir: PathBuf,
+    files: Vec<(Url, Vec<Diagnostic>)>,
+}
+
+/// Computes the diagnostics that an editor would show for the project in the given directory.
+pub fn check(options: &CheckOptions) -> Result<Report> {
+    let dir = options.dir.canonicalize()?;
+    let workspace = load_workspace(&dir, options.config.as_deref())?;
+
+    let mut manager = diagnostics::Manager::default();
+    for document in workspace.iter() {
+        manager.update_syntax(&workspace, document);
+        if !options.chktex {
+            continue;
+        }
+
+        let command = diagnostics::chktex::Command::new(&workspace, document);
+        if let Some(diagnostics) = command.and_then(|command| command.run().ok()) {
+            manager.update_chktex(document.uri.clone(), diagnostics);
+        }
+    }
+
+    let mut files: Vec<_> = manager
+        .get(&workspace)
+        .into_iter()
+        .filter_map(|(uri, diagnostics)| {
+            let document = workspace.lookup(&uri)?;
+            let mut diagnostics: Vec<_> = diagnostics
+                .iter()
+                .filter_map(|diagnostic| to_proto::diagnostic(&workspace, document, diagnostic))
+                .collect();
+
+            diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+            Some((uri, diagnostics))
+        })
+        .filter(|(_, diagnostics)| !diagnostics.is_empty())
+        .collect();
+
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(Report { dir, files })
+}
+
+impl Report {
+    /// Counts the diagnostics that are at least as severe as the given one.
+    pub fn count(&self, severity: Severity) -> usize {
+        let threshold = DiagnosticSeverity::from(severity);
+        self.diagnostics()
+            .filter(|(_, diagnostic)| {
+                diagnostic
+                    .severity
+                    .is_some_and(|severity| severity <= threshold)
+            })
+            .count()
+    }
+
+    pub fn write(&self, format: ReportFormat, writer: &mut impl Write) -> io::Result<()> {
+        match format {
+            ReportFormat::Text => self.write_text(writer),
+            ReportFormat::Json => {
+                let files: Vec<_> = self
+                    .files
+                    .iter()
+                    .map(|(uri, diagnostics)| {
+                        serde_json::json!({ "uri": uri, "diagnostics": diagnostics })
+                    })
+                    .collect();
+
+                serde_json::to_writer_pretty(&mut *writer, &files)?;
+                writeln!(writer)
+            }
+            ReportFormat::Sarif => {
+                serde_json::to_writer_pretty(&mut *writer, &self.sarif())?;
+                writeln!(writer)
+            }
+        }
+    }
+
+    fn diagnostics(&self) -> impl Iterator<Item = (&Url, &Diagnostic)> {
+        self.files
+            .iter()
+            .flat_map(|(uri, diagnostics)| diagnostics.iter().map(move |diag| (uri, diag)))
+    }
+
+    fn relative_path(&self, uri: &Url) -> String {
+        let path = uri.to_file_path().unwrap_or_else(|_| uri.path().into());
+        let path = path.strip_prefix(&self.dir).unwrap_or(&path);
+        path.display().to_string()
+    }
+
+    fn write_text(&self, writer: &mut impl Write) -> io::Result<()> {
+        for (uri, diagnostic) in self.diagnostics() {
+            let start = diagnostic.range.start;
+            let code = match &diagnostic.code {
+                Some(NumberOrString::Number(code)) => format!("[{code}]"),
+                Some(NumberOrString::String(code)) => format!("[{code}]"),
+                None => String::new(),
+            };
+
+            writeln!(
+                writer,
+                "{}:{}:{}: {}{code}: {}",
+                self.relative_path(uri),
+                start.line + 1,
+                start.character + 1,
+                severity_name(diagnostic.severity),
+                diagnostic.message,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn sarif(&self) -> serde_json::Value {
+        let results: Vec<_> = self
+            .diagnostics()
+            .map(|(uri, diagnostic)| {
+                let rule_id = match &diagnostic.code {
+                    Some(NumberOrString::Number(code)) => code.to_string(),
+                    Some(NumberOrString::String(code)) => code.clone(),
+                    None => diagnostic.source.clone().unwrap_or_default(),
+                };
+
+                let level = match diagnostic.severity {
+                    Some(DiagnosticSeverity::ERROR) => "error",
+                    Some(DiagnosticSeverity::WARNING) => "warning",
+                    _ => "note",
+                };
+
+                let range = diagnostic.range;
+                serde_json::json!({
+                    "ruleId": rule_id,
+                    "level": level,
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": self.relative_path(uri).replace('\\', "/") },
+                            "region": {
+                                "startLine": range.start.line + 1,
+                                "startColumn": range.start.character + 1,
+                                "endLine": range.end.line + 1,
+                                "endColumn": range.end.character + 1,
+                            },
+                        },
+                    }],
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "TexLab",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/latex-lsp/texlab",
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+}
+
+/// Loads the files in the given directory and their dependencies like an editor would.
+pub(crate) fn load_workspace(dir: &Path, config: Option<&Path>) -> Result<Workspace> {
+    let mut workspace = Workspace::default();
+    if let Some(path) = config {
+        workspace.set_config(from_proto::config_file(path)?);
+    }
+
+    let distro = Distro::detect().unwrap_or_else(|why| {
+        log::warn!("Unable to load distro files: {}", why);
+        Distro::default()
+    });
+
+    workspace.set_distro(distro);
+    workspace.set_folders(vec![dir.to_path_buf()]);
+
+    for (path, language) in find_files(dir) {
+        let Ok(uri) = Url::from_file_path(&path) else {
+            continue;
+        };
+
+        let text = String::from_utf8_lossy(&std::fs::read(&path)?).into_owned();
+        let cursor = LineCol { line: 0, col: 0 };
+        workspace.open(uri, text, language, Owner::Client, cursor);
+    }
+
+    let mut checked_paths = FxHashSet::default();
+    base_db::deps::discover(&mut workspace, &mut checked_paths);
+    Ok(workspace)
+}
+
+fn severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        _ => "hint",
+    }
+}
+
+/// Collects the TeX, BibTeX and auxiliary files of the project, skipping hidden directories like `.git`.
+/// Other files like build markers are discovered through the documents that need them.
+fn find_files(dir: &Path) -> Vec<(PathBuf, Language)> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            match entry.file_type() {
+                Ok(ty) if ty.is_dir() && !is_hidden => stack.push(path),
+                Ok(ty) if ty.is_file() => {
+                    let language = Language::from_path(&path).filter(|language| {
+                        matches!(language, Language::Tex | Language::Bib | Language::Aux)
+                    });
+
+                    files.extend(language.map(|language| (path, language)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckOptions, ReportFormat, Severity, check, load_workspace};
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        std::fs::write(
+            dir.join("main.tex"),
+            "\\documentclass{article}\n\\begin{document}\n\\label{foo}\n\\ref{bar}\n\\end{document}\n",
+        )
+        .unwrap();
+
+        let options = CheckOptions {
+            dir: dir.to_path_buf(),
+            ..CheckOptions::default()
+        };
+
+        let report = check(&options).unwrap();
+        let mut output = Vec::new();
+        report.write(ReportFormat::Text, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "main.tex:3:8: hint[9]: Unused label\nmain.tex:4:6: error[10]: Undefined reference\n"
+        );
+
+        assert_eq!(report.count(Severity::Error), 1);
+        assert_eq!(report.count(Severity::Hint), 2);
+    }
+
+    #[test]
+    fn test_find_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(dir.join("data")).unwrap();
+        std::fs::create_dir(dir.join(".git")).unwrap();
+        let files = [
+            "main.tex",
+            "main.aux",
+            "other.log",
+            "refs.bib",
+            "data/values.json",
+            ".git/config.tex",
+        ];
+
+        for name in files {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let workspace = load_workspace(&dir, None).unwrap();
+        let mut paths: Vec<_> = workspace
+            .iter()
+            .filter_map(|document| document.path.as_deref())
+            .map(|path| path.strip_prefix(&dir).unwrap().display().to_string())
+            .collect();
+
+        paths.sort();
+        assert_eq!(paths, ["main.aux", "main.tex", "refs.bib"]);
+    }
+}
@@ -0,0 +1,54 @@
+use base_db::Workspace;
+use code_actions::DocumentChange;
+use serde_json::Value;
+
+use crate::util::{ClientFlags, from_proto, to_proto};
+
+pub fn find_all(
+    workspace: &Workspace,
+    params: lsp_types::CodeActionParams,
+    client_flags: &ClientFlags,
+) -> Option<Vec<Value>> {
+    let params = from_proto::code_action_params(workspace, params, client_flags)?;
+    let actions = code_actions::find_all(&params)
+        .into_iter()
+        .filter_map(|action| {
+            let is_snippet = action
+                .changes
+                .iter()
+                .any(|change| matches!(change, DocumentChange::Snippet { .. }));
+
+            let action = to_proto::code_action(workspace, action, client_flags)?;
+            let mut value = serde_json::to_value(action).ok()?;
+            if is_snippet && client_flags.snippet_text_edits {
+                mark_snippet_edits(&mut value);
+            }
+
+            Some(value)
+        })
+        .collect();
+
+    Some(actions)
+}
+
+/// Marks the edits of an action as snippets like the `snippetTextEdit` extension expects.
+/// `lsp_types::TextEdit` has no field for the insert text format.
+fn mark_snippet_edits(action: &mut Value) {
+    let operations = action
+        .pointer_mut("/edit/documentChanges")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten();
+
+    for edit in operations
+        .filter_map(|operation| operation.get_mut("edits"))
+        .filter_map(Value::as_array_mut)
+        .flatten()
+        .filter_map(Value::as_object_mut)
+    {
+        edit.insert(
+            "insertTextFormat".into(),
+            serde_json::to_value(lsp_types::InsertTextFormat::SNIPPET).unwrap(),
+        );
+    }
+}
@@ -1,5 +1,15 @@
-use base_db::{MatchingAlgo, Workspace, util::RenderedObject};
-use completion::{ArgumentData, CompletionItem, CompletionItemData, EntryTypeData, FieldTypeData};
+use std::cell::OnceCell;
+
+use base_db::{
+    MatchingAlgo, Workspace,
+    data::BibtexEntryTypeCategory,
+    deps::Project,
+    semantics::bib::{EntryIndex, ResolvedEntry, resolve_keywords},
+    util::RenderedObject,
+};
+use completion::{
+    ArgumentData, CompletionItem, CompletionItemData, EntryTypeData, FieldTypeData, FieldValueData,
+};
 use line_index::LineIndex;
 use rowan::ast::AstNode;
 use serde::{Deserialize, Serialize};
@@ -18,6 +28,8 @@ pub fn complete(
     let result = completion::complete(&params);
 
     let item_builder = ItemBuilder {
+        workspace,
+        entry_index: OnceCell::new(),
         line_index: &params.feature.document.line_index,
         client_flags,
     };
@@ -60,10 +72,15 @@ pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> O
             ));
         }
         ResolveInfo::Citation { uri, key } => {
-            let data = workspace.lookup(&from_proto::url(&uri))?.data.as_bib()?;
+            let document = workspace.lookup(&from_proto::url(&uri))?;
+            let data = document.data.as_bibliography()?;
             let root = bibtex::Root::cast(data.root_node())?;
             let entry = root.find_entry(&key)?;
-            let value = citeproc::render(&entry, &data.semantics)?;
+            let project = Project::from_child(workspace, document);
+            let style = citeproc::Style::from_project(workspace, &project);
+            let entry = ResolvedEntry::resolve(&EntryIndex::new(workspace), entry, &data.semantics);
+            let language = project.language();
+            let value = citeproc::render(&entry, style.as_deref(), language)?;
             item.documentation = Some(lsp_types::Documentation::MarkupContent(
                 lsp_types::MarkupContent {
                     kind: lsp_types::MarkupKind::Markdown,
@@ -77,11 +94,19 @@ pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> O
 }
 
 struct ItemBuilder<'a> {
+    workspace: &'a Workspace,
+    /// Only needed for citations, so it is built when the first one is converted.
+    entry_index: OnceCell<EntryIndex<'a>>,
     line_index: &'a LineIndex,
     client_flags: &'a ClientFlags,
 }
 
 impl<'a> ItemBuilder<'a> {
+    fn entry_index(&self) -> &EntryIndex<'a> {
+        self.entry_index
+            .get_or_init(|| EntryIndex::new(self.workspace))
+    }
+
     pub fn convert(&self, item: CompletionItem, index: usize) -> Option<lsp_types::CompletionItem> {
         let mut result = lsp_types::CompletionItem::default();
         let range = self.line_index.line_col_lsp_range(item.range)?;
@@ -129,12 +154,21 @@ impl<'a> ItemBuilder<'a> {
             CompletionItemData::DocumentClass(name) => {
                 self.convert_document_class(&mut result, range, name);
             }
+            CompletionItemData::BeamerTheme(name) => {
+                self.convert_beamer_theme(&mut result, range, name);
+            }
+            CompletionItemData::Font(name) => {
+                self.convert_font(&mut result, range, name);
+            }
             CompletionItemData::EntryType(data) => {
                 self.convert_entry_type(&mut result, range, data);
             }
             CompletionItemData::Field(data) => {
                 self.convert_field(&mut result, range, data);
             }
+            CompletionItemData::FieldValue(data) => {
+                self.convert_field_value(&mut result, range, data);
+            }
             CompletionItemData::TikzLibrary(name) => {
                 self.convert_tikz_library(&mut result, range, name);
             }
@@ -227,7 +261,7 @@ impl<'a> ItemBuilder<'a> {
     ) {
         result.label = data.entry.name.text.clone();
         result.kind = Some(Structure::Entry(data.entry.category).completion_kind());
-        result.filter_text = Some(data.entry.keywords.clone());
+        result.filter_text = Some(resolve_keywords(self.entry_index(), data.entry));
         let text_edit = lsp_types::TextEdit::new(range, data.entry.name.text.clone());
         result.text_edit = Some(text_edit.into());
         let resolve_info = serde_json::to_value(ResolveInfo::Citation {
@@ -241,7 +275,7 @@ impl<'a> ItemBuilder<'a> {
         &self,
         result: &mut lsp_types::CompletionItem,
         range: lsp_types::Range,
-        data: EntryTypeData<'a>,
+        data: EntryTypeData<'_>,
     ) {
         result.label = data.0.name.into();
         result.kind = Some(Structure::Entry(data.0.category).completion_kind());
@@ -393,11 +427,33 @@ impl<'a> ItemBuilder<'a> {
         result.data = Some(serde_json::to_value(ResolveInfo::DocumentClass).unwrap());
     }
 
+    fn convert_beamer_theme(
+        &self,
+        result: &mut lsp_types::CompletionItem,
+        range: lsp_types::Range,
+        name: &str,
+    ) {
+        result.label = name.into();
+        result.kind = Some(Structure::Package.completion_kind());
+        result.text_edit = Some(lsp_types::TextEdit::new(range, name.into()).into());
+    }
+
+    fn convert_font(
+        &self,
+        result: &mut lsp_types::CompletionItem,
+        range: lsp_types::Range,
+        name: &str,
+    ) {
+        result.label = name.into();
+        result.kind = Some(Structure::File.completion_kind());
+        result.text_edit = Some(lsp_types::TextEdit::new(range, name.into()).into());
+    }
+
     fn convert_field(
         &self,
         result: &mut lsp_types::CompletionItem,
         range: lsp_types::Range,
-        data: FieldTypeData<'a>,
+        data: FieldTypeData<'_>,
     ) {
         result.label = data.0.name.into();
         result.kind = Some(Structure::Field.completion_kind());
@@ -412,6 +468,29 @@ impl<'a> ItemBuilder<'a> {
         result.text_edit = Some(text_edit.into());
     }
 
+    fn convert_field_value(
+        &self,
+        result: &mut lsp_types::CompletionItem,
+        range: lsp_types::Range,
+        data: FieldValueData,
+    ) {
+        match data.string_name {
+            Some(name) => {
+                result.label = name;
+                result.detail = Some(data.value);
+                result.kind =
+                    Some(Structure::Entry(BibtexEntryTypeCategory::String).completion_kind());
+            }
+            None => {
+                result.label = data.value;
+                result.kind = Some(Structure::FieldValue.completion_kind());
+            }
+        }
+
+        result.filter_text = Some(data.keywords);
+        result.text_edit = Some(lsp_types::TextEdit::new(range, data.insert_text).into());
+    }
+
     fn convert_tikz_library(
         &self,
         result: &mut lsp_types::CompletionItem,
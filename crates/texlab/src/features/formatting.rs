@@ -1,12 +1,20 @@
 mod bibtex_internal;
 mod latexindent;
+mod sentences;
+mod tables;
 mod texfmt;
 
-use base_db::{Formatter, Workspace};
+use base_db::{Document, Formatter, Workspace};
 use distro::Language;
+use rowan::{TextLen, TextRange};
+
+use crate::util::line_index_ext::LineIndexExt;
 
 use self::{
-    bibtex_internal::format_bibtex_internal, latexindent::format_with_latexindent,
+    bibtex_internal::{format_bibtex_internal, format_bibtex_internal_ranges},
+    latexindent::{format_ranges_with_latexindent, format_with_latexindent},
+    sentences::format_sentences,
+    tables::format_tables,
     texfmt::format_with_texfmt,
 };
 
@@ -19,7 +27,10 @@ pub fn format_source_code(
     match document.language {
         Language::Tex => match workspace.config().formatting.tex_formatter {
             Formatter::Null => None,
-            Formatter::Server => None,
+            Formatter::Server => {
+                let ranges = [TextRange::up_to(document.text.text_len())];
+                format_latex_internal(workspace, document, &ranges)
+            }
             Formatter::LatexIndent => format_with_latexindent(workspace, document),
             Formatter::TexFmt => format_with_texfmt(workspace, document),
         },
@@ -29,7 +40,8 @@ pub fn format_source_code(
             Formatter::LatexIndent => format_with_latexindent(workspace, document),
             Formatter::TexFmt => format_with_texfmt(workspace, document),
         },
-        Language::Aux
+        Language::CslJson
+        | Language::Aux
         | Language::Log
         | Language::Root
         | Language::Latexmkrc
@@ -37,3 +49,72 @@ pub fn format_source_code(
         | Language::FileList => None,
     }
 }
+
+/// Formats the parts of a document that intersect with `ranges`.
+pub fn format_source_code_ranges(
+    workspace: &Workspace,
+    uri: &url::Url,
+    ranges: &[lsp_types::Range],
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let document = workspace.lookup(uri)?;
+    let ranges = ranges
+        .iter()
+        .map(|range| document.line_index.offset_lsp_range(*range))
+        .collect::<Option<Vec<_>>>()?;
+
+    match document.language {
+        Language::Tex => match workspace.config().formatting.tex_formatter {
+            Formatter::Server => format_latex_internal(workspace, document, &ranges),
+            Formatter::LatexIndent => format_ranges_with_latexindent(workspace, document, &ranges),
+            Formatter::Null | Formatter::TexFmt => None,
+        },
+        Language::Bib => match workspace.config().formatting.bib_formatter {
+            Formatter::Server => {
+                format_bibtex_internal_ranges(workspace, document, &ranges, options)
+            }
+            Formatter::Null | Formatter::LatexIndent | Formatter::TexFmt => None,
+        },
+        Language::CslJson
+        | Language::Aux
+        | Language::Log
+        | Language::Root
+        | Language::Latexmkrc
+        | Language::Tectonic
+        | Language::FileList => None,
+    }
+}
+
+/// The server formatter aligns tables and optionally puts each sentence on its own line.
+fn format_latex_internal(
+    workspace: &Workspace,
+    document: &Document,
+    ranges: &[TextRange],
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let mut edits = format_tables(document, ranges);
+    if workspace.config().formatting.semantic_line_breaks {
+        // The aligned tables replace their whole body, so any sentence edit within them would overlap.
+        let sentences: Vec<_> = format_sentences(workspace, document, ranges)
+            .into_iter()
+            .filter(|(range, _)| {
+                !edits.iter().any(|(table, _)| {
+                    table
+                        .intersect(*range)
+                        .is_some_and(|intersection| !intersection.is_empty() || range.is_empty())
+                })
+            })
+            .collect();
+
+        edits.extend(sentences);
+        edits.sort_by_key(|(range, _)| range.start());
+    }
+
+    let line_index = &document.line_index;
+    edits
+        .into_iter()
+        .map(|(range, text)| {
+            let range = line_index.line_col_lsp_range(range)?;
+            Some(lsp_types::TextEdit::new(range, text))
+        })
+        .collect()
+}
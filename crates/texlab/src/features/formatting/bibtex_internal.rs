@@ -1,5 +1,6 @@
-use base_db::{Document, Workspace};
-use rowan::TextLen;
+use base_db::{BibtexDelimiter, BibtexSortKey, Document, KeyCase, Workspace};
+use rowan::{TextLen, TextRange};
+use syntax::bibtex;
 
 use crate::util::line_index_ext::LineIndexExt;
 
@@ -9,14 +10,148 @@ pub fn format_bibtex_internal(
     options: &lsp_types::FormattingOptions,
 ) -> Option<Vec<lsp_types::TextEdit>> {
     let data = document.data.as_bib()?;
-    let options = bibfmt::Options {
-        insert_spaces: options.insert_spaces,
-        line_length: workspace.config().formatting.line_length,
-        tab_size: options.tab_size as usize,
-    };
-
+    let options = bibfmt_options(workspace, options);
     let output = bibfmt::format(&data.root_node(), &document.line_index, &options);
     let end = document.line_index.line_col_lsp(document.text.text_len())?;
     let range = lsp_types::Range::new(lsp_types::Position::new(0, 0), end);
     Some(vec![lsp_types::TextEdit::new(range, output)])
 }
+
+/// Formats the entries that intersect with `ranges` and leaves the rest of the file untouched.
+pub fn format_bibtex_internal_ranges(
+    workspace: &Workspace,
+    document: &Document,
+    ranges: &[TextRange],
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let data = document.data.as_bib()?;
+    let options = bibfmt_options(workspace, options);
+    let line_index = &document.line_index;
+    data.root_node()
+        .children()
+        .filter(|node| node.kind() != bibtex::JUNK)
+        .filter(|node| {
+            ranges.iter().any(|range| {
+                node.text_range()
+                    .intersect(*range)
+                    .is_some_and(|intersection| !intersection.is_empty() || range.is_empty())
+            })
+        })
+        .filter_map(|node| {
+            let new_text = bibfmt::format_node(&node, line_index, &options);
+            Some(new_text)
+                .filter(|new_text| node.text() != new_text.as_str())
+                .map(|new_text| (node.text_range(), new_text))
+        })
+        .map(|(range, new_text)| {
+            let range = line_index.line_col_lsp_range(range)?;
+            Some(lsp_types::TextEdit::new(range, new_text))
+        })
+        .collect()
+}
+
+fn bibfmt_options(
+    workspace: &Workspace,
+    options: &lsp_types::FormattingOptions,
+) -> bibfmt::Options {
+    let config = &workspace.config().formatting;
+    bibfmt::Options {
+        insert_spaces: options.insert_spaces,
+        line_length: config.line_length,
+        tab_size: options.tab_size as usize,
+        field_order: config.bibtex.field_order.clone(),
+        entry_type_case: case(config.bibtex.entry_type_case),
+        field_name_case: case(config.bibtex.field_name_case),
+        delimiter: config.bibtex.delimiter.map(|delimiter| match delimiter {
+            BibtexDelimiter::Braces => bibfmt::Delimiter::Braces,
+            BibtexDelimiter::Quotes => bibfmt::Delimiter::Quotes,
+        }),
+        align_equals: config.bibtex.align_equals,
+        trailing_comma: config.bibtex.trailing_comma,
+        remove_empty_fields: config.bibtex.remove_empty_fields,
+        remove_fields: config.bibtex.remove_fields.clone(),
+        sort_entries: config.bibtex.sort_entries.map(|key| match key {
+            BibtexSortKey::Key => bibfmt::SortKey::Key,
+            BibtexSortKey::Year => bibfmt::SortKey::Year,
+            BibtexSortKey::Author => bibfmt::SortKey::Author,
+        }),
+    }
+}
+
+fn case(case: KeyCase) -> bibfmt::Case {
+    match case {
+        KeyCase::Preserve => bibfmt::Case::Preserve,
+        KeyCase::Lower => bibfmt::Case::Lower,
+        KeyCase::Upper => bibfmt::Case::Upper,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base_db::{Owner, Workspace};
+    use distro::Language;
+    use line_index::LineCol;
+    use rowan::{TextRange, TextSize};
+    use url::Url;
+
+    use super::format_bibtex_internal_ranges;
+
+    const TEXT: &str = "@article{foo,title={Foo}}\n\n@article{bar,title={Bar}}\n";
+
+    fn format(start: usize, end: usize) -> Vec<(lsp_types::Range, String)> {
+        let mut workspace = Workspace::default();
+        let uri = Url::parse("file:///texlab/main.bib").unwrap();
+        let cursor = LineCol { line: 0, col: 0 };
+        workspace.open(
+            uri.clone(),
+            TEXT.into(),
+            Language::Bib,
+            Owner::Client,
+            cursor,
+        );
+
+        let document = workspace.lookup(&uri).unwrap();
+        let range = TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32));
+        let options = lsp_types::FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        };
+
+        format_bibtex_internal_ranges(&workspace, document, &[range], &options)
+            .unwrap()
+            .into_iter()
+            .map(|edit| (edit.range, edit.new_text))
+            .collect()
+    }
+
+    #[test]
+    fn test_range() {
+        let start = TEXT.find("bar").unwrap();
+        let edits = format(start, start + 3);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].0.start, lsp_types::Position::new(2, 0));
+        assert!(edits[0].1.contains("Bar"));
+    }
+
+    #[test]
+    fn test_range_spanning_entries() {
+        let edits = format(3, TEXT.find("bar").unwrap());
+        assert_eq!(edits.len(), 2);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let start = TEXT.find("Foo").unwrap();
+        let edits = format(start, start);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].0.start, lsp_types::Position::new(0, 0));
+        assert!(edits[0].1.contains("Foo"));
+    }
+
+    #[test]
+    fn test_cursor_between_entries() {
+        let start = TEXT.find("\n\n").unwrap() + 1;
+        assert!(format(start, start).is_empty());
+    }
+}
@@ -6,7 +6,8 @@ use std::{
 
 use base_db::{Document, LatexIndentConfig, Workspace};
 use distro::Language;
-use rowan::TextLen;
+use rowan::{TextLen, TextRange, TextSize, ast::AstNode};
+use syntax::latex;
 use tempfile::tempdir;
 
 use crate::util::line_index_ext::LineIndexExt;
@@ -15,17 +16,76 @@ pub fn format_with_latexindent(
     workspace: &Workspace,
     document: &Document,
 ) -> Option<Vec<lsp_types::TextEdit>> {
+    let old_text = &document.text;
+    let new_text = run_latexindent(workspace, old_text, document.language)?;
+    if new_text == *old_text {
+        // No edits needed
+        return Some(Vec::new());
+    } else {
+        let line_index = &document.line_index;
+        let start = lsp_types::Position::new(0, 0);
+        let end = line_index.line_col_lsp(old_text.text_len())?;
+        Some(vec![lsp_types::TextEdit {
+            range: lsp_types::Range::new(start, end),
+            new_text,
+        }])
+    }
+}
+
+/// Formats the lines that intersect with `ranges`.
+/// The ranges are extended so that they do not cut through an environment.
+pub fn format_ranges_with_latexindent(
+    workspace: &Workspace,
+    document: &Document,
+    ranges: &[TextRange],
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let root = document.data.as_tex()?.root_node();
+    let mut ranges: Vec<_> = ranges
+        .iter()
+        .map(|&range| expand_range(&document.text, &root, range))
+        .collect();
+
+    ranges.sort_by_key(|range| range.start());
+    ranges.dedup_by(|next, previous| {
+        let overlaps = next.start() <= previous.end();
+        if overlaps {
+            *previous = previous.cover(*next);
+        }
+
+        overlaps
+    });
+
+    let mut edits = Vec::new();
+    for range in ranges {
+        let old_text = &document.text[range];
+        let indent = common_indentation(old_text);
+        let output = run_latexindent(
+            workspace,
+            &remove_indentation(old_text, indent),
+            Language::Tex,
+        )?;
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        let new_text = add_indentation(output, indent);
+
+        if new_text != old_text {
+            let range = document.line_index.line_col_lsp_range(range)?;
+            edits.push(lsp_types::TextEdit::new(range, new_text));
+        }
+    }
+
+    Some(edits)
+}
+
+fn run_latexindent(workspace: &Workspace, text: &str, language: Language) -> Option<String> {
     let config = workspace.config();
     let target_dir = tempdir().ok()?;
 
-    let target_file = target_dir
-        .path()
-        .join(if document.language == Language::Bib {
-            "file.bib"
-        } else {
-            "file.tex"
-        });
-    std::fs::write(&target_file, &document.text).ok()?;
+    let target_file = target_dir.path().join(if language == Language::Bib {
+        "file.bib"
+    } else {
+        "file.tex"
+    });
+    std::fs::write(&target_file, text).ok()?;
 
     let args = build_arguments(&config.formatting.latex_indent, &target_file);
 
@@ -43,24 +103,90 @@ pub fn format_with_latexindent(
         .output()
         .ok()?;
 
-    let old_text = &document.text;
     let new_text = String::from_utf8_lossy(&output.stdout).into_owned();
-    if new_text.is_empty() {
-        None
-    } else if new_text == *old_text {
-        // No edits needed
-        return Some(Vec::new());
-    } else {
-        let line_index = &document.line_index;
-        let start = lsp_types::Position::new(0, 0);
-        let end = line_index.line_col_lsp(old_text.text_len())?;
-        Some(vec![lsp_types::TextEdit {
-            range: lsp_types::Range::new(start, end),
-            new_text,
-        }])
+    Some(new_text).filter(|text| !text.is_empty())
+}
+
+/// Extends `range` to whole lines and to the environments that it partially covers.
+fn expand_range(text: &str, root: &latex::SyntaxNode, mut range: TextRange) -> TextRange {
+    loop {
+        let start = text[..usize::from(range.start())]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+
+        let end = text[usize::from(range.end())..]
+            .find('\n')
+            .map_or(text.len(), |index| usize::from(range.end()) + index);
+
+        let mut expanded = TextRange::new(
+            TextSize::try_from(start).unwrap(),
+            TextSize::try_from(end).unwrap(),
+        );
+
+        for environment in root.descendants().filter_map(latex::Environment::cast) {
+            let environment_range = latex::small_range(&environment);
+            let is_cut = environment_range
+                .intersect(expanded)
+                .is_some_and(|intersection| {
+                    !intersection.is_empty()
+                        && !expanded.contains_range(environment_range)
+                        && !environment_range.contains_range(expanded)
+                });
+
+            if is_cut {
+                expanded = expanded.cover(environment_range);
+            }
+        }
+
+        if expanded == range {
+            return range;
+        }
+
+        range = expanded;
     }
 }
 
+/// Finds the leading whitespace that all non-blank lines of `text` share.
+fn common_indentation(text: &str) -> &str {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let Some(first) = lines.next() else {
+        return "";
+    };
+
+    let mut indent = &first[..first.len() - first.trim_start().len()];
+    for line in lines {
+        let length = indent
+            .char_indices()
+            .zip(line.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((index, c), _)| index + c.len_utf8());
+
+        indent = &indent[..length];
+    }
+
+    indent
+}
+
+fn remove_indentation(text: &str, indent: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| line.strip_prefix(indent).unwrap_or(line))
+        .collect()
+}
+
+/// Indents the non-blank lines of `text` and removes trailing whitespace from the blank ones.
+fn add_indentation(text: &str, indent: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            if line.trim().is_empty() {
+                line.trim_start_matches([' ', '\t']).to_string()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect()
+}
+
 fn build_arguments(config: &LatexIndentConfig, target_file: &Path) -> Vec<String> {
     let mut args = Vec::new();
 
@@ -82,3 +208,69 @@ fn build_arguments(config: &LatexIndentConfig, target_file: &Path) -> Vec<String
     args.push(target_file.display().to_string());
     args
 }
+
+#[cfg(test)]
+mod tests {
+    use parser::{SyntaxConfig, parse_latex};
+    use rowan::{TextRange, TextSize};
+    use syntax::latex;
+
+    use super::{add_indentation, common_indentation, expand_range, remove_indentation};
+
+    fn expand(text: &str, start: u32, end: u32) -> &str {
+        let root = latex::SyntaxNode::new_root(parse_latex(text, &SyntaxConfig::default()));
+        let range = TextRange::new(TextSize::from(start), TextSize::from(end));
+        &text[expand_range(text, &root, range)]
+    }
+
+    #[test]
+    fn test_expand_range_lines() {
+        let text = "Foo\nBar Baz\nQux\n";
+        assert_eq!(expand(text, 5, 6), "Bar Baz");
+        assert_eq!(expand(text, 5, 5), "Bar Baz");
+    }
+
+    #[test]
+    fn test_expand_range_environment() {
+        let text = "Foo\n\\begin{itemize}\n  \\item Bar\n\\end{itemize}\nBaz\n";
+        let start = text.find("Foo").unwrap() as u32;
+        let end = text.find("Bar").unwrap() as u32;
+        assert_eq!(
+            expand(text, start, end),
+            "Foo\n\\begin{itemize}\n  \\item Bar\n\\end{itemize}"
+        );
+        assert_eq!(expand(text, end, end + 3), "  \\item Bar");
+    }
+
+    #[test]
+    fn test_expand_range_nested_environment() {
+        let text = "\\begin{document}\n\\begin{center}\nFoo\n\\end{center}\nBar\n\\end{document}\n";
+        let start = text.find("Foo").unwrap() as u32;
+        let end = text.find("Bar").unwrap() as u32;
+        assert_eq!(
+            expand(text, start, end),
+            "\\begin{center}\nFoo\n\\end{center}\nBar"
+        );
+    }
+
+    #[test]
+    fn test_indentation() {
+        let text = "    \\begin{center}\n\n      Foo\n    \\end{center}";
+        let indent = common_indentation(text);
+        assert_eq!(indent, "    ");
+
+        let dedented = remove_indentation(text, indent);
+        assert_eq!(dedented, "\\begin{center}\n\n  Foo\n\\end{center}");
+        assert_eq!(
+            add_indentation("\\begin{center}\n  \n\tFoo\n\\end{center}", indent),
+            "    \\begin{center}\n\n    \tFoo\n    \\end{center}"
+        );
+    }
+
+    #[test]
+    fn test_common_indentation_mixed() {
+        assert_eq!(common_indentation("\t  Foo\n\t Bar\n"), "\t ");
+        assert_eq!(common_indentation("Foo\n  Bar\n"), "");
+        assert_eq!(common_indentation("\n\n"), "");
+    }
+}
@@ -0,0 +1,17 @@
+use base_db::{Document, Workspace};
+use rowan::TextRange;
+
+pub fn format_sentences(
+    workspace: &Workspace,
+    document: &Document,
+    ranges: &[TextRange],
+) -> Vec<(TextRange, String)> {
+    let mut edits: Vec<_> = ranges
+        .iter()
+        .flat_map(|&range| code_actions::break_sentences(workspace, document, range))
+        .collect();
+
+    edits.sort_by_key(|(range, _)| range.start());
+    edits.dedup_by_key(|(range, _)| *range);
+    edits
+}
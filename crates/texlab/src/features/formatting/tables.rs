@@ -0,0 +1,14 @@
+use base_db::Document;
+use rowan::TextRange;
+
+pub fn format_tables(document: &Document, ranges: &[TextRange]) -> Vec<(TextRange, String)> {
+    let mut edits: Vec<_> = ranges
+        .iter()
+        .flat_map(|&range| commands::align_tables(document, range))
+        .map(|edit| (edit.range, edit.text))
+        .collect();
+
+    edits.sort_by_key(|(range, _)| range.start());
+    edits.dedup_by_key(|(range, _)| *range);
+    edits
+}
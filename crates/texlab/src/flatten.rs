@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use commands::{FlattenCommand, FlattenOptions};
+use url::Url;
+
+use crate::check::load_workspace;
+
+/// Flattens the project of the given file and returns the path of the written document.
+pub fn flatten_file(
+    path: &Path,
+    output_path: PathBuf,
+    config: Option<&Path>,
+    options: FlattenOptions,
+) -> Result<PathBuf> {
+    let path = path.canonicalize()?;
+    let Some(dir) = path.parent() else {
+        anyhow::bail!("invalid path: {}", path.display())
+    };
+
+    let workspace = load_workspace(dir, config)?;
+    let Ok(uri) = Url::from_file_path(&path) else {
+        anyhow::bail!("invalid path: {}", path.display())
+    };
+
+    let command = FlattenCommand::new(&workspace, &uri, output_path, options)?;
+    Ok(command.run()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use commands::FlattenOptions;
+
+    use super::flatten_file;
+
+    #[test]
+    fn test_flatten_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        std::fs::write(
+            dir.join("main.tex"),
+            "\\documentclass{article}\n\\begin{document}\n\\input{intro}\n\\end{document}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("intro.tex"), "Intro\n").unwrap();
+
+        let output_path = dir.join("output").join("flat.tex");
+        std::fs::create_dir(dir.join("output")).unwrap();
+        let path = flatten_file(
+            &dir.join("intro.tex"),
+            output_path.clone(),
+            None,
+            FlattenOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(path, output_path);
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "\\documentclass{article}\n\\begin{document}\nIntro\n\\end{document}\n"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use base_db::{Owner, Workspace};
+use distro::Language;
+use line_index::LineCol;
+use url::Url;
+
+use crate::{
+    features::formatting::format_source_code,
+    util::{from_proto, line_index_ext::LineIndexExt},
+};
+
+#[derive(Debug)]
+pub struct FormatOptions {
+    /// A JSON file with the same settings that an editor would send.
+    pub config: Option<PathBuf>,
+
+    pub tab_size: u32,
+    pub insert_spaces: bool,
+
+    /// Overrides the line length from the settings.
+    pub line_length: Option<usize>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            config: None,
+            tab_size: 4,
+            insert_spaces: true,
+            line_length: None,
+        }
+    }
+}
+
+/// Formats a file with the formatter that an editor would use for it.
+/// Returns `None` if no formatter is configured for the kind of file.
+pub fn format_file(path: &Path, options: &FormatOptions) -> Result<Option<String>> {
+    let Some(language) = Language::from_path(path) else {
+        anyhow::bail!("unknown file type: {}", path.display())
+    };
+
+    let path = path.canonicalize()?;
+    let Ok(uri) = Url::from_file_path(&path) else {
+        anyhow::bail!("invalid path: {}", path.display())
+    };
+
+    let mut workspace = Workspace::default();
+    let mut config = match &options.config {
+        Some(path) => from_proto::config_file(path)?,
+        None => Default::default(),
+    };
+
+    config.formatting.line_length = options.line_length.unwrap_or(config.formatting.line_length);
+
+    workspace.set_config(config);
+
+    let text = std::fs::read_to_string(&path)?;
+    let cursor = LineCol { line: 0, col: 0 };
+    workspace.open(uri.clone(), text, language, Owner::Client, cursor);
+
+    let formatting_options = lsp_types::FormattingOptions {
+        tab_size: options.tab_size,
+        insert_spaces: options.insert_spaces,
+        ..Default::default()
+    };
+
+    let Some(mut edits) = format_source_code(&workspace, &uri, &formatting_options) else {
+        return Ok(None);
+    };
+
+    let document = workspace.lookup(&uri).unwrap();
+    let mut output = document.text.clone();
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+    for edit in edits {
+        let Some(range) = document.line_index.offset_lsp_range(edit.range) else {
+            anyhow::bail!("formatter returned an invalid range");
+        };
+
+        output.replace_range(std::ops::Range::<usize>::from(range), &edit.new_text);
+    }
+
+    Ok(Some(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FormatOptions, format_file};
+
+    #[test]
+    fn test_bibtex() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.bib");
+        std::fs::write(&path, "@article{foo,author={Foo Bar},title = {Baz}}").unwrap();
+
+        let output = format_file(&path, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            output.as_deref(),
+            Some("@article{foo,\n    author = {Foo Bar},\n    title = {Baz},\n}")
+        );
+    }
+
+    #[test]
+    fn test_unknown_file_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "Foo").unwrap();
+        assert!(format_file(&path, &FormatOptions::default()).is_err());
+    }
+}
@@ -1,5 +1,9 @@
+pub mod archive;
+pub mod check;
 mod client;
 pub(crate) mod features;
+pub mod flatten;
+pub mod format;
 mod server;
 pub(crate) mod util;
 
@@ -2,9 +2,15 @@ use std::{fs::OpenOptions, io, path::PathBuf, str::FromStr};
 
 use anyhow::Result;
 use clap::{ArgAction, Parser, Subcommand};
+use commands::FlattenOptions;
 use log::LevelFilter;
 use lsp_server::Connection;
-use texlab::Server;
+use texlab::{
+    Server, archive,
+    check::{self, CheckOptions, ReportFormat, Severity},
+    flatten,
+    format::{self, FormatOptions},
+};
 use url::Url;
 
 /// An implementation of the Language Server Protocol for LaTeX
@@ -36,6 +42,48 @@ enum Command {
     ///
     /// This command can be used to implement inverse search in an editor-agnostic way.
     InverseSearch(InverseSearchOpts),
+
+    /// Prints the diagnostics of a project without running an editor.
+    ///
+    /// Exits with a non-zero code if any diagnostic is at least as severe as --fail-on.
+    Check(CheckOpts),
+
+    /// Formats BibTeX and TeX files with the formatters configured for the editor.
+    ///
+    /// Exits with a non-zero code in --check mode if any file is not formatted.
+    Format(FormatOpts),
+
+    /// Merges a project into a single document by inlining all included files.
+    Flatten(FlattenOpts),
+
+    /// Collects the files that are needed to compile a project into an archive for submission.
+    ///
+    /// Writes a .tar.gz file or copies the files into a directory, depending on --output.
+    Archive(ArchiveOpts),
+}
+
+/// Options for the check subcommand.
+#[derive(Debug, Parser)]
+struct CheckOpts {
+    /// The directory that contains the project.
+    #[clap(name = "DIR", value_parser, default_value = ".")]
+    dir: PathBuf,
+
+    /// A JSON file with the server settings to use.
+    #[clap(long, value_parser)]
+    config: Option<PathBuf>,
+
+    /// Also run ChkTeX on every TeX document.
+    #[clap(long)]
+    chktex: bool,
+
+    /// The format of the report.
+    #[clap(long, value_enum, default_value_t)]
+    format: ReportFormat,
+
+    /// The minimum severity that makes the check fail.
+    #[clap(long, value_enum, default_value_t)]
+    fail_on: Severity,
 }
 
 /// Options for the inverse search subcommand.
@@ -58,6 +106,82 @@ struct InverseSearchOpts {
     line1: Option<u32>,
 }
 
+/// Options for the format subcommand.
+#[derive(Debug, Parser)]
+struct FormatOpts {
+    /// The files to format.
+    #[clap(name = "FILE", value_parser, required = true)]
+    files: Vec<PathBuf>,
+
+    /// Only check whether the files are formatted without changing them.
+    #[clap(long)]
+    check: bool,
+
+    /// Print the formatted files instead of writing them.
+    #[clap(long, conflicts_with = "check")]
+    stdout: bool,
+
+    /// A JSON file with the server settings to use.
+    #[clap(long, value_parser)]
+    config: Option<PathBuf>,
+
+    /// The number of spaces per indentation level.
+    #[clap(long, default_value_t = 4)]
+    tab_size: u32,
+
+    /// Indent with tabs instead of spaces.
+    #[clap(long)]
+    use_tabs: bool,
+
+    /// The maximum line length (overrides the settings).
+    #[clap(long)]
+    line_length: Option<usize>,
+}
+
+/// Options for the flatten subcommand.
+#[derive(Debug, Parser)]
+struct FlattenOpts {
+    /// A document of the project to flatten.
+    #[clap(name = "FILE", value_parser)]
+    input: PathBuf,
+
+    /// The path of the flattened document.
+    #[clap(short, long, value_parser)]
+    output: PathBuf,
+
+    /// Replace \\bibliography with the generated .bbl file.
+    #[clap(long)]
+    inline_bibliography: bool,
+
+    /// Remove comments and \\iffalse blocks.
+    #[clap(long)]
+    strip_comments: bool,
+
+    /// A JSON file with the server settings to use.
+    #[clap(long, value_parser)]
+    config: Option<PathBuf>,
+}
+
+/// Options for the archive subcommand.
+#[derive(Debug, Parser)]
+struct ArchiveOpts {
+    /// A document of the project to archive.
+    #[clap(name = "FILE", value_parser)]
+    input: PathBuf,
+
+    /// A .tar.gz file or a directory (defaults to `<root>-submission.tar.gz`).
+    #[clap(short, long, value_parser)]
+    output: Option<PathBuf>,
+
+    /// Only list the files without writing the archive.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// A JSON file with the server settings to use.
+    #[clap(long, value_parser)]
+    config: Option<PathBuf>,
+}
+
 fn main() -> Result<()> {
     let opts = Opts::parse();
     setup_logger(&opts);
@@ -69,12 +193,10 @@ fn main() -> Result<()> {
             threads.join()?;
         }
         Command::InverseSearch(opts) => {
-            let Some(uri) = opts
-                .input
-                .canonicalize()
-                .ok()
-                .and_then(|path| Url::from_file_path(path).ok())
-            else {
+            let Some((path, uri)) = opts.input.canonicalize().ok().and_then(|path| {
+                let uri = Url::from_file_path(&path).ok()?;
+                Some((path, uri))
+            }) else {
                 eprintln!("Failed to convert input path to a URI.");
                 std::process::exit(-1);
             };
@@ -99,14 +221,90 @@ fn main() -> Result<()> {
                 lsp_types::Position::new(line, 0),
             );
 
-            if let Err(why) = ipc::send_request(params) {
+            if let Err(why) = ipc::send_request(&path, params) {
                 eprintln!(
-                    "Failed to send inverse search request to the main instance. Is the server running?"
+                    "Failed to send inverse search request to a running instance. Is the server running?"
                 );
                 eprintln!("Details: {why:?}");
                 std::process::exit(-1);
             }
         }
+        Command::Check(opts) => {
+            let options = CheckOptions {
+                dir: opts.dir,
+                config: opts.config,
+                chktex: opts.chktex,
+            };
+
+            let report = check::check(&options)?;
+            report.write(opts.format, &mut io::stdout().lock())?;
+            if report.count(opts.fail_on) > 0 {
+                std::process::exit(1);
+            }
+        }
+        Command::Format(opts) => {
+            let options = FormatOptions {
+                config: opts.config,
+                tab_size: opts.tab_size,
+                insert_spaces: !opts.use_tabs,
+                line_length: opts.line_length,
+            };
+
+            let mut unformatted = false;
+            for path in &opts.files {
+                let Some(output) = format::format_file(path, &options)? else {
+                    eprintln!("No formatter configured for {}", path.display());
+                    continue;
+                };
+
+                if opts.stdout {
+                    print!("{output}");
+                } else if output != std::fs::read_to_string(path)? {
+                    if opts.check {
+                        println!("Would reformat: {}", path.display());
+                        unformatted = true;
+                    } else {
+                        std::fs::write(path, output)?;
+                    }
+                }
+            }
+
+            if unformatted {
+                std::process::exit(1);
+            }
+        }
+        Command::Flatten(opts) => {
+            let options = FlattenOptions {
+                inline_bibliography: opts.inline_bibliography,
+                strip_comments: opts.strip_comments,
+            };
+
+            let path =
+                flatten::flatten_file(&opts.input, opts.output, opts.config.as_deref(), options)?;
+
+            println!("{}", path.display());
+        }
+        Command::Archive(opts) => {
+            let command =
+                archive::prepare_archive(&opts.input, opts.output, opts.config.as_deref())?;
+
+            for path in command.files() {
+                println!("{}", path.display());
+            }
+
+            for name in command.missing() {
+                eprintln!("Missing: {name}");
+            }
+
+            for path in command.unreferenced() {
+                eprintln!("Unreferenced: {}", path.display());
+            }
+
+            if !opts.dry_run {
+                let path = command.run()?;
+                eprintln!("Wrote {}", path.display());
+            }
+        }
     }
 
     Ok(())
@@ -1,3 +1,4 @@
+mod build_status;
 mod dispatch;
 mod extensions;
 pub mod options;
@@ -12,7 +13,10 @@ use std::{
 
 use anyhow::Result;
 use base_db::{Owner, Workspace, deps};
-use commands::{BuildCommand, CleanCommand, CleanTarget, ForwardSearch};
+use commands::{
+    ArchiveCommand, BuildCommand, CleanCommand, CleanTarget, FlattenCommand, FlattenOptions,
+    ForwardSearch, SyncTexForward, SyncTexInverse,
+};
 use crossbeam_channel::{Receiver, Sender};
 use distro::{Distro, Language};
 use line_index::LineCol;
@@ -21,7 +25,7 @@ use lsp_types::{notification::*, request::*, *};
 use notify::event::ModifyKind;
 use notify_debouncer_full::{DebouncedEvent, Debouncer, RecommendedCache};
 use parking_lot::{Mutex, RwLock};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
 use threadpool::ThreadPool;
@@ -30,16 +34,20 @@ use url::Url;
 use crate::{
     client::LspClient,
     features::{
-        completion, definition, folding, formatting, highlight, hover, inlay_hint, link, reference,
-        rename, symbols,
+        code_action, completion, definition, folding, formatting, highlight, hover, inlay_hint,
+        link, reference, rename, symbols,
     },
     util::{ClientFlags, from_proto, line_index_ext::LineIndexExt, normalize_uri, to_proto},
 };
 
 use self::{
+    build_status::BuildStatusReporter,
     extensions::{
-        BuildParams, BuildRequest, BuildResult, BuildStatus, EnvironmentLocation,
-        ForwardSearchRequest, ForwardSearchResult, ForwardSearchStatus, TextWithRange,
+        ArchiveParams, ArchiveResult, BuildParams, BuildRequest, BuildResult, BuildStatus,
+        CleanParams, DocumentRangesFormattingParams, DocumentStatisticsResult, EnvironmentLocation,
+        FlattenParams, ForwardSearchRequest, ForwardSearchResult, ForwardSearchStatus, PdfBox,
+        RangesFormattingRequest, SectionStatisticsResult, SyncTexForwardRequest,
+        SyncTexForwardResult, SyncTexInverseParams, SyncTexInverseRequest, TextWithRange,
     },
     options::{Options, StartupOptions},
     progress::ProgressReporter,
@@ -52,6 +60,7 @@ enum InternalMessage {
     FileEvent(Vec<DebouncedEvent>),
     Diagnostics,
     ChktexFinished(Url, Vec<diagnostics::Diagnostic>),
+    BuildOutput(Url, Url, Vec<syntax::BuildError>),
     ForwardSearch(Url, Option<Position>),
     InverseSearch(TextDocumentPositionParams),
 }
@@ -74,6 +83,8 @@ pub struct Server {
     watcher: FileWatcher,
     pool: ThreadPool,
     pending_builds: Arc<Mutex<FxHashSet<u32>>>,
+    continuous_builds: Arc<Mutex<FxHashMap<Url, u32>>>,
+    ipc_server: Option<ipc::ServerHandle>,
 }
 
 impl Server {
@@ -106,7 +117,12 @@ impl Server {
             }),
         };
 
-        connection.initialize_finish(id, serde_json::to_value(result)?)?;
+        let mut result = serde_json::to_value(result)?;
+        // `lsp_types` does not know about the `rangesSupport` flag of `textDocument/rangesFormatting` yet.
+        result["capabilities"]["documentRangeFormattingProvider"] =
+            serde_json::json!({ "rangesSupport": true });
+
+        connection.initialize_finish(id, result)?;
 
         let server = Self {
             connection: Arc::new(connection),
@@ -123,6 +139,8 @@ impl Server {
             watcher,
             pool: threadpool::Builder::new().build(),
             pending_builds: Default::default(),
+            continuous_builds: Default::default(),
+            ipc_server: None,
         };
 
         let options = serde_json::from_value(params.initialization_options.unwrap_or_default())
@@ -172,15 +190,30 @@ impl Server {
                 work_done_progress_options: WorkDoneProgressOptions::default(),
             })),
             document_highlight_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![
+                    CodeActionKind::REFACTOR_EXTRACT,
+                    CodeActionKind::REFACTOR_INLINE,
+                    CodeActionKind::REFACTOR_REWRITE,
+                ]),
+                ..CodeActionOptions::default()
+            })),
             document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "texlab.cleanAuxiliary".into(),
                     "texlab.cleanArtifacts".into(),
                     "texlab.changeEnvironment".into(),
+                    "texlab.alignTable".into(),
                     "texlab.findEnvironments".into(),
                     "texlab.showDependencyGraph".into(),
                     "texlab.cancelBuild".into(),
+                    "texlab.createArchive".into(),
+                    "texlab.documentStatistics".into(),
+                    "texlab.flattenProject".into(),
+                    "texlab.startContinuousBuild".into(),
+                    "texlab.stopContinuousBuild".into(),
                 ],
                 ..Default::default()
             }),
@@ -189,6 +222,8 @@ impl Server {
                 [
                     ("textDocumentBuild".to_string(), Value::Bool(true)),
                     ("textDocumentForwardSearch".to_string(), Value::Bool(true)),
+                    ("textDocumentSyncTexForward".to_string(), Value::Bool(true)),
+                    ("textDocumentSyncTexInverse".to_string(), Value::Bool(true)),
                 ]
                 .into_iter(),
             ))),
@@ -455,11 +490,12 @@ impl Server {
     fn did_save(&mut self, params: DidSaveTextDocumentParams) -> Result<()> {
         let uri = from_proto::url(&params.text_document.uri);
 
-        if self.workspace.read().config().build.on_save {
+        if self.workspace.read().config().build.on_save && !self.is_building_continuously(&uri) {
             let text_document = params.text_document;
             let params = BuildParams {
                 text_document,
                 position: None,
+                profile: None,
             };
 
             self.build(None, params)?;
@@ -481,6 +517,40 @@ impl Server {
         Ok(())
     }
 
+    fn project_root(&self, uri: &Url) -> Url {
+        let workspace = self.workspace.read();
+        let Some(document) = workspace.lookup(uri) else {
+            return uri.clone();
+        };
+
+        let root = deps::parents(&workspace, document)
+            .into_iter()
+            .next()
+            .unwrap_or(document);
+
+        root.uri.clone()
+    }
+
+    fn is_building_continuously(&self, uri: &Url) -> bool {
+        let root = self.project_root(uri);
+        self.continuous_builds.lock().contains_key(&root)
+    }
+
+    fn update_build_output(&mut self, uri: &Url, log_uri: Url, errors: &[syntax::BuildError]) {
+        let workspace = self.workspace.read();
+        let Some(document) = workspace.lookup(uri) else {
+            return;
+        };
+
+        let root = deps::parents(&workspace, document)
+            .into_iter()
+            .next()
+            .unwrap_or(document);
+
+        self.diagnostic_manager
+            .update_build_output(&workspace, log_uri, root, errors);
+    }
+
     fn run_chktex(&mut self, uri: &Url) -> Option<()> {
         let workspace = self.workspace.read();
 
@@ -614,15 +684,35 @@ impl Server {
         Ok(())
     }
 
+    fn range_formatting(&self, id: RequestId, params: DocumentRangeFormattingParams) -> Result<()> {
+        let uri = from_proto::url(&params.text_document.uri);
+        self.run_query(id, move |db| {
+            formatting::format_source_code_ranges(db, &uri, &[params.range], &params.options)
+        });
+
+        Ok(())
+    }
+
+    fn ranges_formatting(
+        &self,
+        id: RequestId,
+        params: DocumentRangesFormattingParams,
+    ) -> Result<()> {
+        let uri = from_proto::url(&params.text_document.uri);
+        self.run_query(id, move |db| {
+            formatting::format_source_code_ranges(db, &uri, &params.ranges, &params.options)
+        });
+
+        Ok(())
+    }
+
     fn execute_command(&self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
         match params.command.as_str() {
             "texlab.cleanAuxiliary" => {
-                let command = self.prepare_clean_command(params, CleanTarget::Auxiliary);
-                self.run_fallible(id, || command?.run());
+                self.clean(id, params, CleanTarget::Auxiliary);
             }
             "texlab.cleanArtifacts" => {
-                let command = self.prepare_clean_command(params, CleanTarget::Artifacts);
-                self.run_fallible(id, || command?.run());
+                self.clean(id, params, CleanTarget::Artifacts);
             }
             "texlab.changeEnvironment" => {
                 let client = self.client.clone();
@@ -631,6 +721,13 @@ impl Server {
                     client.send_request::<ApplyWorkspaceEdit>(params?)
                 });
             }
+            "texlab.alignTable" => {
+                let client = self.client.clone();
+                let params = self.align_table(params);
+                self.run_fallible(id, move || {
+                    client.send_request::<ApplyWorkspaceEdit>(params?)
+                });
+            }
             "texlab.findEnvironments" => {
                 let result = self.find_environments(params);
                 self.run_fallible(id, move || result);
@@ -651,6 +748,29 @@ impl Server {
                     Ok(())
                 });
             }
+            "texlab.createArchive" => {
+                self.create_archive(id, params);
+            }
+            "texlab.documentStatistics" => {
+                let result = self.document_statistics(params);
+                self.run_fallible(id, move || result);
+            }
+            "texlab.flattenProject" => {
+                let command = self.prepare_flatten_command(params);
+                self.run_fallible(id, move || Ok(command?.run()?));
+            }
+            "texlab.startContinuousBuild" => {
+                let result = self
+                    .parse_command_params(params.arguments)
+                    .and_then(|params| self.start_continuous_build(params));
+                self.run_fallible(id, move || result);
+            }
+            "texlab.stopContinuousBuild" => {
+                let result = self
+                    .parse_command_params(params.arguments)
+                    .map(|params| self.stop_continuous_build(params));
+                self.run_fallible(id, move || result);
+            }
             _ => {
                 self.client
                     .send_error(
@@ -699,9 +819,13 @@ impl Server {
         let fwd_search_after = workspace.config().build.forward_search_after;
 
         let (sender, receiver) = crossbeam_channel::unbounded();
-        self.redirect_build_log(receiver);
 
-        let command = BuildCommand::new(&workspace, &uri);
+        let command = BuildCommand::new(&workspace, &uri, params.profile.as_deref());
+        drop(workspace);
+        if let Ok(command) = &command {
+            self.remember_build_profile(command.root_uri(), params.profile.clone());
+        }
+
         let internal = self.internal_tx.clone();
         let progress = self.client_flags.progress;
         let pending_builds = Arc::clone(&self.pending_builds);
@@ -720,11 +844,29 @@ impl Server {
                 None
             };
 
+            let mut status_reporter = BuildStatusReporter::new(
+                client.clone(),
+                internal.clone(),
+                params.text_document.clone(),
+                command
+                    .as_ref()
+                    .ok()
+                    .and_then(|command| command.log_uri().cloned()),
+            );
+
             let status = command
                 .and_then(|command| {
                     let mut process = command.spawn(sender)?;
                     let pid = process.id();
                     pending_builds.lock().insert(pid);
+
+                    let typ = MessageType::LOG;
+                    for message in receiver {
+                        status_reporter.process_line(&message, progress_reporter.as_ref());
+                        let _ = client
+                            .send_notification::<LogMessage>(LogMessageParams { message, typ });
+                    }
+
                     let result = process.wait();
 
                     let status = if pending_builds.lock().remove(&pid) {
@@ -744,6 +886,7 @@ impl Server {
                     BuildStatus::Failure
                 });
 
+            status_reporter.finish(status);
             drop(progress_reporter);
             drop(guard);
 
@@ -760,16 +903,87 @@ impl Server {
         Ok(())
     }
 
-    fn redirect_build_log(&self, receiver: Receiver<String>) {
+    fn start_continuous_build(&self, params: BuildParams) -> Result<()> {
+        let uri = from_proto::url(&params.text_document.uri);
+        let workspace = self.workspace.read();
+        let fwd_search_after = workspace.config().build.forward_search_after;
+        let command =
+            BuildCommand::new(&workspace, &uri, params.profile.as_deref())?.continuous()?;
+
+        drop(workspace);
+
+        let root = command.root_uri().clone();
+        let mut continuous_builds = self.continuous_builds.lock();
+        if continuous_builds.contains_key(&root) {
+            return Ok(());
+        }
+
+        let mut status_reporter = BuildStatusReporter::new(
+            self.client.clone(),
+            self.internal_tx.clone(),
+            params.text_document.clone(),
+            command.log_uri().cloned(),
+        );
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut process = command.spawn(sender)?;
+        let pid = process.id();
+        continuous_builds.insert(root.clone(), pid);
+        drop(continuous_builds);
+        self.remember_build_profile(&root, params.profile.clone());
+
         let client = self.client.clone();
-        self.pool.execute(move || {
+        let internal = self.internal_tx.clone();
+        let continuous_builds = Arc::clone(&self.continuous_builds);
+
+        // The watcher runs until it is stopped, so it must not occupy a thread of the pool.
+        std::thread::spawn(move || {
             let typ = MessageType::LOG;
             for message in receiver {
-                client
-                    .send_notification::<LogMessage>(LogMessageParams { message, typ })
-                    .unwrap();
+                let status = status_reporter.process_line(&message, None);
+                if fwd_search_after && status.is_some() {
+                    let _ =
+                        internal.send(InternalMessage::ForwardSearch(uri.clone(), params.position));
+                }
+
+                let _ = client.send_notification::<LogMessage>(LogMessageParams { message, typ });
             }
+
+            let result = process.wait();
+            let mut continuous_builds = continuous_builds.lock();
+            let status = if continuous_builds.get(&root) == Some(&pid) {
+                continuous_builds.remove(&root);
+                log::error!("Continuous build of \"{root}\" exited unexpectedly: {result:?}");
+                BuildStatus::Failure
+            } else {
+                BuildStatus::Cancelled
+            };
+
+            drop(continuous_builds);
+            status_reporter.finish(status);
         });
+
+        Ok(())
+    }
+
+    /// Lets the other commands use the output directories of the profile that was requested for a build.
+    fn remember_build_profile(&self, root: &Url, profile: Option<String>) {
+        self.workspace
+            .write()
+            .set_build_profile(root.clone(), profile);
+    }
+
+    fn stop_continuous_build(&self, params: TextDocumentIdentifier) {
+        let root = self.project_root(&from_proto::url(&params.uri));
+        if let Some(pid) = self.continuous_builds.lock().remove(&root) {
+            let _ = BuildCommand::cancel(pid);
+        }
+    }
+
+    fn stop_continuous_builds(&self) {
+        for (_, pid) in self.continuous_builds.lock().drain() {
+            let _ = BuildCommand::cancel(pid);
+        }
     }
 
     fn forward_search(
@@ -807,9 +1021,73 @@ impl Server {
         Ok(())
     }
 
-    fn code_actions(&self, id: RequestId, _params: CodeActionParams) -> Result<()> {
-        self.client
-            .send_response(lsp_server::Response::new_ok(id, Vec::<CodeAction>::new()))?;
+    fn synctex_forward(&self, id: RequestId, params: TextDocumentPositionParams) -> Result<()> {
+        let mut uri = from_proto::url(&params.text_document.uri);
+        normalize_uri(&mut uri);
+
+        let command = SyncTexForward::new(&self.workspace.read(), &uri, Some(params.position.line));
+        self.run_fallible(id, move || {
+            let result = command.and_then(SyncTexForward::run)?;
+            let Ok(pdf) = Url::from_file_path(&result.pdf_path) else {
+                return Ok(None);
+            };
+
+            let boxes = result
+                .boxes
+                .into_iter()
+                .map(|rect| PdfBox {
+                    page: rect.page,
+                    x: rect.x,
+                    y: rect.y,
+                    width: rect.width,
+                    height: rect.height,
+                })
+                .collect();
+
+            let pdf = to_proto::uri(&pdf);
+            Ok(Some(SyncTexForwardResult { pdf, boxes }))
+        });
+
+        Ok(())
+    }
+
+    fn synctex_inverse(&self, id: RequestId, params: SyncTexInverseParams) -> Result<()> {
+        let uri = from_proto::url(&params.text_document.uri);
+        let Ok(pdf_path) = uri.to_file_path() else {
+            anyhow::bail!("PDF document \"{uri}\" does not exist on the local file system");
+        };
+
+        let command = SyncTexInverse::new(
+            &self.workspace.read(),
+            &pdf_path,
+            params.page,
+            params.x,
+            params.y,
+        );
+
+        self.run_fallible(id, move || {
+            let Some(location) = command.and_then(SyncTexInverse::run)? else {
+                return Ok(None);
+            };
+
+            let Ok(uri) = Url::from_file_path(&location.path) else {
+                return Ok(None);
+            };
+
+            let position = Position::new(location.line.saturating_sub(1), 0);
+            let range = Range::new(position, position);
+            Ok(Some(Location::new(to_proto::uri(&uri), range)))
+        });
+
+        Ok(())
+    }
+
+    fn code_actions(&self, id: RequestId, params: CodeActionParams) -> Result<()> {
+        let client_flags = Arc::clone(&self.client_flags);
+        self.run_query(id, move |workspace| {
+            code_action::find_all(workspace, params, &client_flags).unwrap_or_default()
+        });
+
         Ok(())
     }
 
@@ -872,19 +1150,111 @@ impl Server {
         }
     }
 
+    fn clean(&self, id: RequestId, params: ExecuteCommandParams, target: CleanTarget) {
+        let result = self
+            .parse_command_params::<CleanParams>(params.arguments)
+            .and_then(|params| {
+                let command = self.prepare_clean_command(&params.text_document, target)?;
+                Ok((command, params.dry_run))
+            });
+
+        self.run_fallible(id, move || match result? {
+            (command, true) => Ok(command.files().to_vec()),
+            (command, false) => command.run(),
+        });
+    }
+
     fn prepare_clean_command(
         &self,
-        params: ExecuteCommandParams,
+        text_document: &TextDocumentIdentifier,
         target: CleanTarget,
     ) -> Result<CleanCommand> {
+        let workspace = self.workspace.read();
+        let uri = from_proto::url(&text_document.uri);
+        let Some(document) = workspace.lookup(&uri) else {
+            anyhow::bail!("Document {uri} is not opened!")
+        };
+
+        CleanCommand::new(&workspace, document, target)
+    }
+
+    fn create_archive(&self, id: RequestId, params: ExecuteCommandParams) {
+        let result = self
+            .parse_command_params::<ArchiveParams>(params.arguments)
+            .and_then(|params| {
+                let workspace = self.workspace.read();
+                let uri = from_proto::url(&params.text_document.uri);
+                let command = ArchiveCommand::new(&workspace, &uri, params.output)?;
+                Ok((command, params.dry_run))
+            });
+
+        self.run_fallible(id, move || {
+            let (command, dry_run) = result?;
+            let mut result = ArchiveResult {
+                output: None,
+                files: command.files().map(Path::to_path_buf).collect(),
+                missing: command.missing().to_vec(),
+                unreferenced: command.unreferenced().to_vec(),
+            };
+
+            if !dry_run {
+                result.output = Some(command.run()?);
+            }
+
+            Ok(result)
+        });
+    }
+
+    fn document_statistics(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<DocumentStatisticsResult> {
         let workspace = self.workspace.read();
         let params = self.parse_command_params::<TextDocumentIdentifier>(params.arguments)?;
         let uri = from_proto::url(&params.uri);
+
         let Some(document) = workspace.lookup(&uri) else {
             anyhow::bail!("Document {uri} is not opened!")
         };
 
-        CleanCommand::new(&workspace, document, target)
+        let statistics = commands::document_statistics(&workspace, document);
+        let sections = statistics
+            .sections
+            .into_iter()
+            .filter_map(|section| {
+                let line_index = &workspace.lookup(&section.uri)?.line_index;
+                Some(SectionStatisticsResult {
+                    name: section.name,
+                    location: Location::new(
+                        to_proto::uri(&section.uri),
+                        line_index.line_col_lsp_range(section.range)?,
+                    ),
+                    statistics: section.statistics.into(),
+                })
+            })
+            .collect();
+
+        Ok(DocumentStatisticsResult {
+            total: statistics.total.into(),
+            sections,
+        })
+    }
+
+    fn prepare_flatten_command(&self, params: ExecuteCommandParams) -> Result<FlattenCommand> {
+        let workspace = self.workspace.read();
+        let params = self.parse_command_params::<FlattenParams>(params.arguments)?;
+        let uri = from_proto::url(&params.text_document.uri);
+        let options = FlattenOptions {
+            inline_bibliography: params.inline_bibliography,
+            strip_comments: params.strip_comments,
+        };
+
+        Ok(FlattenCommand::new(
+            &workspace,
+            &uri,
+            params.output,
+            options,
+        )?)
     }
 
     fn change_environment(&self, params: ExecuteCommandParams) -> Result<ApplyWorkspaceEditParams> {
@@ -932,6 +1302,41 @@ impl Server {
         Ok(ApplyWorkspaceEditParams { label, edit })
     }
 
+    fn align_table(&self, params: ExecuteCommandParams) -> Result<ApplyWorkspaceEditParams> {
+        let workspace = self.workspace.read();
+        let params = self.parse_command_params::<TextDocumentPositionParams>(params.arguments)?;
+        let uri = from_proto::url(&params.text_document.uri);
+
+        let Some(document) = workspace.lookup(&uri) else {
+            anyhow::bail!("Document {uri} is not opened!")
+        };
+
+        let line_index = &document.line_index;
+        let Some(position) = line_index.offset_lsp(params.position) else {
+            anyhow::bail!("Invalid position for document {uri}!")
+        };
+
+        let edits = commands::align_table(document, position)
+            .into_iter()
+            .filter_map(|edit| {
+                let range = line_index.line_col_lsp_range(edit.range)?;
+                Some(TextEdit::new(range, edit.text))
+            })
+            .collect();
+
+        let mut changes = HashMap::new();
+        changes.insert(to_proto::uri(&document.uri), edits);
+
+        let edit = WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        };
+
+        let label = Some("align table".into());
+        Ok(ApplyWorkspaceEditParams { label, edit })
+    }
+
     fn find_environments(&self, params: ExecuteCommandParams) -> Result<Vec<EnvironmentLocation>> {
         let workspace = self.workspace.read();
         let params = self.parse_command_params::<TextDocumentPositionParams>(params.arguments)?;
@@ -998,9 +1403,15 @@ impl Server {
 
     fn setup_ipc_server(&mut self) {
         let sender = self.internal_tx.clone();
-        let _ = ipc::spawn_server(move |params: TextDocumentPositionParams| {
+        let folders = self.workspace.read().folders().to_vec();
+        let result = ipc::spawn_server(folders, move |params: TextDocumentPositionParams| {
             let _ = sender.send(InternalMessage::InverseSearch(params));
         });
+
+        match result {
+            Ok(handle) => self.ipc_server = Some(handle),
+            Err(why) => log::warn!("Unable to start the inverse search server: {why}"),
+        }
     }
 
     fn process_messages(&mut self) -> Result<()> {
@@ -1039,10 +1450,14 @@ impl Server {
                                     self.document_highlight(id, params)
                                 })?
                                 .on::<Formatting, _>(|id, params| self.formatting(id, params))?
+                                .on::<RangeFormatting, _>(|id, params| self.range_formatting(id, params))?
+                                .on::<RangesFormattingRequest, _>(|id, params| self.ranges_formatting(id, params))?
                                 .on::<BuildRequest, _>(|id, params| self.build(Some(id), params))?
                                 .on::<ForwardSearchRequest, _>(|id, params| {
                                     self.forward_search(Some(id), from_proto::url(&params.text_document.uri), Some(params.position))
                                 })?
+                                .on::<SyncTexForwardRequest, _>(|id, params| self.synctex_forward(id, params))?
+                                .on::<SyncTexInverseRequest, _>(|id, params| self.synctex_inverse(id, params))?
                                 .on::<ExecuteCommand,_>(|id, params| self.execute_command(id, params))?
                                 .on::<SemanticTokensRangeRequest, _>(|id, params| {
                                     self.semantic_tokens_range(id, params)
@@ -1105,6 +1520,10 @@ impl Server {
                             self.diagnostic_manager.update_chktex(uri, diagnostics);
                             self.publish_diagnostics()?;
                         }
+                        InternalMessage::BuildOutput(uri, log_uri, errors) => {
+                            self.update_build_output(&uri, log_uri, &errors);
+                            self.publish_diagnostics()?;
+                        }
                         InternalMessage::ForwardSearch(uri, position) => {
                             self.forward_search(None, uri, position)?;
                         }
@@ -1147,7 +1566,9 @@ impl Server {
         self.register_configuration();
         self.pull_options();
         self.setup_ipc_server();
-        self.process_messages()?;
+        let result = self.process_messages();
+        self.stop_continuous_builds();
+        result?;
         self.pool.join();
         Ok(())
     }
@@ -1215,14 +1636,16 @@ fn handle_path_change(
             !affected_uris.is_empty()
         }
         PathChangeKind::CreatedOrModified => {
-            if !workspace
-                .lookup_file(path)
-                .map_or(true, |doc| doc.owner == Owner::Server)
-            {
+            let document = workspace.lookup_file(path);
+            if !document.map_or(true, |doc| doc.owner == Owner::Server) {
                 return false;
             }
 
-            let Some(language) = Language::from_path(path) else {
+            // Linked JSON bibliographies are not recognized by their extension alone.
+            let Some(language) = document
+                .map(|doc| doc.language)
+                .or_else(|| Language::from_path(path))
+            else {
                 return false;
             };
 
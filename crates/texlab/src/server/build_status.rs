@@ -0,0 +1,152 @@
+use commands::{BuildEvent, BuildMonitor, BuildPass};
+use crossbeam_channel::Sender;
+use lsp_types::TextDocumentIdentifier;
+use syntax::BuildErrorLevel;
+use url::Url;
+
+use crate::{LspClient, util::from_proto};
+
+use super::{
+    InternalMessage,
+    extensions::{BuildState, BuildStatus, BuildStatusNotification, BuildStatusParams},
+    progress::ProgressReporter,
+};
+
+/// Forwards the progress of a running build to the client
+/// and the errors found in its output to the diagnostics.
+pub struct BuildStatusReporter {
+    client: LspClient,
+    internal: Sender<InternalMessage>,
+    text_document: TextDocumentIdentifier,
+    log_uri: Option<Url>,
+    monitor: BuildMonitor,
+    pass: Option<(BuildPass, u32)>,
+    page: Option<u32>,
+}
+
+impl BuildStatusReporter {
+    pub fn new(
+        client: LspClient,
+        internal: Sender<InternalMessage>,
+        text_document: TextDocumentIdentifier,
+        log_uri: Option<Url>,
+    ) -> Self {
+        Self {
+            client,
+            internal,
+            text_document,
+            log_uri,
+            monitor: BuildMonitor::default(),
+            pass: None,
+            page: None,
+        }
+    }
+
+    /// Processes a line of the compiler output and returns the status of a continuous build round
+    /// if the line has completed one.
+    pub fn process_line(
+        &mut self,
+        line: &str,
+        progress: Option<&ProgressReporter>,
+    ) -> Option<BuildStatus> {
+        let mut events = self.monitor.process_line(line);
+        let round_finished = events.contains(&BuildEvent::Finished);
+        events.retain(|event| *event != BuildEvent::Finished);
+        self.report(events, progress);
+        if !round_finished {
+            return None;
+        }
+
+        let status = if self.monitor.count(BuildErrorLevel::Error) > 0 {
+            BuildStatus::Error
+        } else {
+            BuildStatus::Success
+        };
+
+        self.finish_round(status);
+        Some(status)
+    }
+
+    pub fn finish(mut self, status: BuildStatus) {
+        self.finish_round(status);
+    }
+
+    fn finish_round(&mut self, status: BuildStatus) {
+        let events = self.monitor.finish();
+        self.report(events, None);
+        self.notify(BuildState::Finished, Some(status));
+        self.monitor = BuildMonitor::default();
+        self.pass = None;
+        self.page = None;
+    }
+
+    fn report(&mut self, events: Vec<BuildEvent>, progress: Option<&ProgressReporter>) {
+        if events.is_empty() {
+            return;
+        }
+
+        for event in events {
+            match event {
+                BuildEvent::Pass { pass, run } => {
+                    self.pass = Some((pass, run));
+                    self.page = None;
+                }
+                BuildEvent::Page(page) => {
+                    self.page = Some(page);
+                }
+                BuildEvent::Finished => {}
+                BuildEvent::Errors(errors) => {
+                    let Some(log_uri) = self.log_uri.clone() else {
+                        continue;
+                    };
+
+                    let uri = from_proto::url(&self.text_document.uri);
+                    let _ = self
+                        .internal
+                        .send(InternalMessage::BuildOutput(uri, log_uri, errors));
+                }
+            }
+        }
+
+        if let Some(progress) = progress {
+            progress.report(self.progress_message());
+        }
+
+        self.notify(BuildState::Running, None);
+    }
+
+    fn progress_message(&self) -> String {
+        let Some((pass, run)) = self.pass else {
+            return String::new();
+        };
+
+        let name = match pass {
+            BuildPass::Latex => "LaTeX",
+            BuildPass::Bibtex => "BibTeX",
+            BuildPass::Biber => "Biber",
+            BuildPass::Makeindex => "Index",
+        };
+
+        match self.page {
+            Some(page) => format!("{name} (run {run}), page {page}"),
+            None => format!("{name} (run {run})"),
+        }
+    }
+
+    fn notify(&self, state: BuildState, status: Option<BuildStatus>) {
+        let params = BuildStatusParams {
+            text_document: self.text_document.clone(),
+            state,
+            pass: self.pass.map(|(pass, _)| pass.into()),
+            run: self.pass.map(|(_, run)| run),
+            page: self.page,
+            errors: self.monitor.count(BuildErrorLevel::Error) as u32,
+            warnings: self.monitor.count(BuildErrorLevel::Warning) as u32,
+            status,
+        };
+
+        let _ = self
+            .client
+            .send_notification::<BuildStatusNotification>(params);
+    }
+}
@@ -1,7 +1,12 @@
 #![allow(non_camel_case_types)]
 
-use commands::ForwardSearchError;
-use lsp_types::{Position, Range, TextDocumentIdentifier, TextDocumentPositionParams};
+use std::path::PathBuf;
+
+use commands::{BuildPass, ForwardSearchError, Statistics};
+use lsp_types::{
+    FormattingOptions, Location, Position, Range, TextDocumentIdentifier,
+    TextDocumentPositionParams, TextEdit, Uri, WorkDoneProgressParams,
+};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -15,6 +20,57 @@ impl lsp_types::request::Request for BuildRequest {
     const METHOD: &'static str = "textDocument/build";
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanParams {
+    #[serde(flatten)]
+    pub text_document: TextDocumentIdentifier,
+
+    /// Only report the files that would be removed instead of deleting them.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenParams {
+    #[serde(flatten)]
+    pub text_document: TextDocumentIdentifier,
+
+    /// The path of the flattened document.
+    pub output: PathBuf,
+
+    #[serde(default)]
+    pub inline_bibliography: bool,
+
+    #[serde(default)]
+    pub strip_comments: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveParams {
+    #[serde(flatten)]
+    pub text_document: TextDocumentIdentifier,
+
+    /// A `.tar.gz` file or a directory. Defaults to `<root>-submission.tar.gz` next to the root document.
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+
+    /// Only report the files that would be archived.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+    pub output: Option<PathBuf>,
+    pub files: Vec<PathBuf>,
+    pub missing: Vec<String>,
+    pub unreferenced: Vec<PathBuf>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildParams {
@@ -22,6 +78,10 @@ pub struct BuildParams {
 
     #[serde(default)]
     pub position: Option<Position>,
+
+    /// The name of the build profile to use instead of the default one.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -39,6 +99,74 @@ pub enum BuildStatus {
     Cancelled = 3,
 }
 
+pub struct BuildStatusNotification;
+
+impl lsp_types::notification::Notification for BuildStatusNotification {
+    type Params = BuildStatusParams;
+
+    const METHOD: &'static str = "texlab/buildStatus";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStatusParams {
+    pub text_document: TextDocumentIdentifier,
+    pub state: BuildState,
+    pub pass: Option<BuildPassKind>,
+    pub run: Option<u32>,
+    pub page: Option<u32>,
+    pub errors: u32,
+    pub warnings: u32,
+    pub status: Option<BuildStatus>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildState {
+    Running,
+    Finished,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildPassKind {
+    Latex,
+    Bibtex,
+    Biber,
+    Makeindex,
+}
+
+impl From<BuildPass> for BuildPassKind {
+    fn from(pass: BuildPass) -> Self {
+        match pass {
+            BuildPass::Latex => Self::Latex,
+            BuildPass::Bibtex => Self::Bibtex,
+            BuildPass::Biber => Self::Biber,
+            BuildPass::Makeindex => Self::Makeindex,
+        }
+    }
+}
+
+pub struct RangesFormattingRequest;
+
+impl lsp_types::request::Request for RangesFormattingRequest {
+    type Params = DocumentRangesFormattingParams;
+
+    type Result = Option<Vec<TextEdit>>;
+
+    const METHOD: &'static str = "textDocument/rangesFormatting";
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentRangesFormattingParams {
+    pub text_document: TextDocumentIdentifier,
+    pub ranges: Vec<Range>,
+    pub options: FormattingOptions,
+    #[serde(flatten)]
+    pub work_done_progress_params: WorkDoneProgressParams,
+}
+
 pub struct ForwardSearchRequest;
 
 impl lsp_types::request::Request for ForwardSearchRequest {
@@ -77,6 +205,54 @@ pub struct ForwardSearchResult {
     pub status: ForwardSearchStatus,
 }
 
+pub struct SyncTexForwardRequest;
+
+impl lsp_types::request::Request for SyncTexForwardRequest {
+    type Params = TextDocumentPositionParams;
+
+    type Result = Option<SyncTexForwardResult>;
+
+    const METHOD: &'static str = "textDocument/syncTexForward";
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncTexForwardResult {
+    pub pdf: Uri,
+    pub boxes: Vec<PdfBox>,
+}
+
+/// A rectangle in PDF points relative to the top-left corner of a one-based page.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfBox {
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+pub struct SyncTexInverseRequest;
+
+impl lsp_types::request::Request for SyncTexInverseRequest {
+    type Params = SyncTexInverseParams;
+
+    type Result = Option<Location>;
+
+    const METHOD: &'static str = "textDocument/syncTexInverse";
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncTexInverseParams {
+    /// The PDF document.
+    pub text_document: TextDocumentIdentifier,
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EnvironmentLocation {
@@ -84,6 +260,50 @@ pub struct EnvironmentLocation {
     pub full_range: Range,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentStatisticsResult {
+    pub total: StatisticsCounts,
+    pub sections: Vec<SectionStatisticsResult>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionStatisticsResult {
+    /// The name of the section or `null` for the text before the first heading.
+    pub name: Option<String>,
+    pub location: Location,
+    pub statistics: StatisticsCounts,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsCounts {
+    pub text_words: usize,
+    pub header_words: usize,
+    pub caption_words: usize,
+    pub inline_formulas: usize,
+    pub display_formulas: usize,
+    pub floats: usize,
+    pub citations: usize,
+    pub labels: usize,
+}
+
+impl From<Statistics> for StatisticsCounts {
+    fn from(statistics: Statistics) -> Self {
+        Self {
+            text_words: statistics.text_words,
+            header_words: statistics.header_words,
+            caption_words: statistics.caption_words,
+            inline_formulas: statistics.inline_formulas,
+            display_formulas: statistics.display_formulas,
+            floats: statistics.floats,
+            citations: statistics.citations,
+            labels: statistics.labels,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextWithRange {
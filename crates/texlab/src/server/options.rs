@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use regex::Regex;
@@ -10,6 +12,8 @@ pub struct Options {
     pub bibtex_formatter: BibtexFormatter,
     pub latex_formatter: LatexFormatter,
     pub formatter_line_length: Option<i32>,
+    pub formatter_semantic_line_breaks: bool,
+    pub bibtex_formatting: BibtexFormattingOptions,
     pub diagnostics: DiagnosticsOptions,
     pub diagnostics_delay: Option<u64>,
     pub build: BuildOptions,
@@ -20,6 +24,8 @@ pub struct Options {
     pub forward_search: ForwardSearchOptions,
     pub completion: CompletionOptions,
     pub inlay_hints: InlayHintOptions,
+    pub code_actions: CodeActionOptions,
+    pub citation: CitationOptions,
     pub experimental: ExperimentalOptions,
 }
 
@@ -53,6 +59,44 @@ impl Default for LatexFormatter {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct BibtexFormattingOptions {
+    pub field_order: Vec<String>,
+    pub entry_type_case: Option<KeyCase>,
+    pub field_name_case: Option<KeyCase>,
+    pub delimiters: Option<BibtexDelimiter>,
+    pub align_equals: bool,
+    pub trailing_comma: Option<bool>,
+    pub remove_empty_fields: bool,
+    pub remove_fields: Vec<String>,
+    pub sort_entries: Option<BibtexSortKey>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyCase {
+    Preserve,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BibtexDelimiter {
+    Braces,
+    Quotes,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BibtexSortKey {
+    Key,
+    Year,
+    Author,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
@@ -75,6 +119,21 @@ pub struct BuildOptions {
     pub pdf_directory: Option<String>,
     pub filename: Option<String>,
     pub use_file_list: bool,
+    pub profiles: Vec<BuildProfileOptions>,
+    pub default_profile: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct BuildProfileOptions {
+    pub name: String,
+    pub executable: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub env: BTreeMap<String, String>,
+    pub aux_directory: Option<String>,
+    pub log_directory: Option<String>,
+    pub pdf_directory: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -126,6 +185,20 @@ pub struct InlayHintOptions {
     pub max_length: Option<usize>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct CodeActionOptions {
+    pub extract_directory: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct CitationOptions {
+    pub style: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
@@ -161,6 +234,7 @@ pub struct ExperimentalOptions {
     pub label_definition_prefixes: Vec<(String, String)>,
     pub label_reference_commands: Vec<String>,
     pub label_reference_prefixes: Vec<(String, String)>,
+    pub label_reference_kinds: Vec<(String, Vec<String>)>,
     pub label_reference_range_commands: Vec<String>,
 }
 
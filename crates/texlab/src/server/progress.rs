@@ -1,7 +1,7 @@
 use lsp_types::{
     NumberOrString, ProgressParams, ProgressParamsValue, WorkDoneProgress, WorkDoneProgressBegin,
-    WorkDoneProgressCreateParams, WorkDoneProgressEnd, notification::Progress,
-    request::WorkDoneProgressCreate,
+    WorkDoneProgressCreateParams, WorkDoneProgressEnd, WorkDoneProgressReport,
+    notification::Progress, request::WorkDoneProgressCreate,
 };
 
 use crate::LspClient;
@@ -42,6 +42,19 @@ impl ProgressReporter {
         Self { client, token }
     }
 
+    pub fn report(&self, message: String) {
+        let _ = self.client.send_notification::<Progress>(ProgressParams {
+            token: NumberOrString::Number(self.token),
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(
+                WorkDoneProgressReport {
+                    message: Some(message),
+                    cancellable: Some(false),
+                    percentage: None,
+                },
+            )),
+        });
+    }
+
     pub fn new_inputs_progress(client: LspClient, token: i32) -> Self {
         let _ = client.send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
             token: NumberOrString::Number(token),
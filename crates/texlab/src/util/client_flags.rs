@@ -41,4 +41,11 @@ pub struct ClientFlags {
 
     /// If `true`, the server can return `LocationLink` instead of `Location`.
     pub location_link_support: bool,
+
+    /// If `true`, the server can create files using `WorkspaceEdit` resource operations.
+    pub create_files: bool,
+
+    /// If `true`, the server can send snippets in the edits of code actions.
+    /// This uses the `snippetTextEdit` extension that is also supported by `rust-analyzer`.
+    pub snippet_text_edits: bool,
 }
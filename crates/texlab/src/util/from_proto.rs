@@ -1,8 +1,10 @@
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
 use base_db::{
-    Config, FeatureParams, Formatter, SymbolEnvironmentConfig, SynctexConfig, Workspace,
+    BuildProfile, Config, FeatureParams, Formatter, SymbolEnvironmentConfig, SynctexConfig,
+    Workspace,
 };
+use code_actions::CodeActionParams;
 use completion::CompletionParams;
 use definition::DefinitionParams;
 use highlights::HighlightParams;
@@ -16,7 +18,8 @@ use titlecase::titlecase;
 use crate::{
     features::completion::ResolveInfo,
     server::options::{
-        BibtexFormatter, CompletionMatcher, HoverSymbolOptions, LatexFormatter, Options,
+        BibtexDelimiter, BibtexFormatter, BibtexSortKey, CompletionMatcher, HoverSymbolOptions,
+        KeyCase, LatexFormatter, Options,
     },
     util::normalize_uri,
 };
@@ -116,6 +119,21 @@ pub fn client_flags(
         .and_then(|cap| cap.link_support)
         .unwrap_or(false);
 
+    let create_files = capabilities
+        .workspace
+        .as_ref()
+        .and_then(|cap| cap.workspace_edit.as_ref())
+        .filter(|cap| cap.document_changes == Some(true))
+        .and_then(|cap| cap.resource_operations.as_ref())
+        .is_some_and(|operations| operations.contains(&lsp_types::ResourceOperationKind::Create));
+
+    let snippet_text_edits = capabilities
+        .experimental
+        .as_ref()
+        .and_then(|cap| cap.get("snippetTextEdit"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+
     ClientFlags {
         hierarchical_document_symbols,
         completion_markdown,
@@ -129,6 +147,8 @@ pub fn client_flags(
         progress,
         show_document,
         location_link_support,
+        create_files,
+        snippet_text_edits,
     }
 }
 
@@ -221,6 +241,20 @@ pub fn reference_params(
     })
 }
 
+pub fn code_action_params<'a>(
+    workspace: &'a Workspace,
+    params: lsp_types::CodeActionParams,
+    client_flags: &ClientFlags,
+) -> Option<CodeActionParams<'a>> {
+    let feature = feature_params(workspace, params.text_document)?;
+    let range = feature.document.line_index.offset_lsp_range(params.range)?;
+    Some(CodeActionParams {
+        feature,
+        range,
+        create_files: client_flags.create_files,
+    })
+}
+
 pub fn feature_params(
     workspace: &'_ Workspace,
     text_document: lsp_types::TextDocumentIdentifier,
@@ -272,6 +306,25 @@ pub fn config(value: Options) -> Config {
 
     config.build.output_filename = value.build.filename;
 
+    config.build.profiles = value
+        .build
+        .profiles
+        .into_iter()
+        .map(|profile| BuildProfile {
+            name: profile.name,
+            program: profile
+                .executable
+                .unwrap_or_else(|| config.build.program.clone()),
+            args: profile.args.unwrap_or_else(|| config.build.args.clone()),
+            env: profile.env.into_iter().collect(),
+            aux_dir: profile.aux_directory,
+            log_dir: profile.log_directory,
+            pdf_dir: profile.pdf_directory,
+        })
+        .collect();
+
+    config.build.default_profile = value.build.default_profile;
+
     config.diagnostics.allowed_patterns = value
         .diagnostics
         .allowed_patterns
@@ -313,6 +366,37 @@ pub fn config(value: Options) -> Config {
         .formatter_line_length
         .map_or(80, |len| if len < 0 { usize::MAX } else { len as usize });
 
+    config.formatting.semantic_line_breaks = value.formatter_semantic_line_breaks;
+    if config.formatting.semantic_line_breaks
+        && !matches!(config.formatting.tex_formatter, Formatter::Server)
+    {
+        log::warn!(
+            "Ignoring \"formatterSemanticLineBreaks\" because it requires \"latexFormatter\": \"texlab\""
+        );
+    }
+
+    let bibtex = value.bibtex_formatting;
+    config.formatting.bibtex.field_order = bibtex.field_order;
+    config.formatting.bibtex.entry_type_case = bibtex
+        .entry_type_case
+        .map_or(base_db::KeyCase::Lower, key_case);
+    config.formatting.bibtex.field_name_case = bibtex
+        .field_name_case
+        .map_or(base_db::KeyCase::Preserve, key_case);
+    config.formatting.bibtex.delimiter = bibtex.delimiters.map(|delimiter| match delimiter {
+        BibtexDelimiter::Braces => base_db::BibtexDelimiter::Braces,
+        BibtexDelimiter::Quotes => base_db::BibtexDelimiter::Quotes,
+    });
+    config.formatting.bibtex.align_equals = bibtex.align_equals;
+    config.formatting.bibtex.trailing_comma = bibtex.trailing_comma.unwrap_or(true);
+    config.formatting.bibtex.remove_empty_fields = bibtex.remove_empty_fields;
+    config.formatting.bibtex.remove_fields = bibtex.remove_fields;
+    config.formatting.bibtex.sort_entries = bibtex.sort_entries.map(|key| match key {
+        BibtexSortKey::Key => base_db::BibtexSortKey::Key,
+        BibtexSortKey::Year => base_db::BibtexSortKey::Year,
+        BibtexSortKey::Author => base_db::BibtexSortKey::Author,
+    });
+
     config.formatting.latex_indent.local = value.latexindent.local;
     config.formatting.latex_indent.modify_line_breaks = value.latexindent.modify_line_breaks;
     config.formatting.latex_indent.replacement = value.latexindent.replacement;
@@ -365,6 +449,10 @@ pub fn config(value: Options) -> Config {
     config.inlay_hints.label_references = value.inlay_hints.label_references.unwrap_or(true);
     config.inlay_hints.max_length = value.inlay_hints.max_length;
 
+    config.code_actions.extract_dir = value.code_actions.extract_directory;
+
+    config.citation.style = value.citation.style;
+
     config.completion.matcher = match value.completion.matcher {
         CompletionMatcher::Fuzzy => base_db::MatchingAlgo::Skim,
         CompletionMatcher::FuzzyIgnoreCase => base_db::MatchingAlgo::SkimIgnoreCase,
@@ -419,6 +507,17 @@ pub fn config(value: Options) -> Config {
         .label_reference_prefixes
         .extend(value.experimental.label_reference_prefixes);
 
+    for (command, kinds) in value.experimental.label_reference_kinds {
+        config
+            .syntax
+            .label_reference_commands
+            .insert(command.clone());
+
+        let label_reference_kinds = &mut config.syntax.label_reference_kinds;
+        label_reference_kinds.retain(|(other, _)| *other != command);
+        label_reference_kinds.push((command, kinds));
+    }
+
     config
         .syntax
         .label_reference_range_commands
@@ -426,3 +525,17 @@ pub fn config(value: Options) -> Config {
 
     config
 }
+
+fn key_case(case: KeyCase) -> base_db::KeyCase {
+    match case {
+        KeyCase::Preserve => base_db::KeyCase::Preserve,
+        KeyCase::Lower => base_db::KeyCase::Lower,
+        KeyCase::Upper => base_db::KeyCase::Upper,
+    }
+}
+
+/// Reads the settings that an editor would send from a JSON file.
+pub fn config_file(path: &Path) -> anyhow::Result<Config> {
+    let options: Options = serde_json::from_slice(&std::fs::read(path)?)?;
+    Ok(config(options))
+}
@@ -21,6 +21,7 @@ pub enum Structure {
     Class,
     Entry(BibtexEntryTypeCategory),
     Field,
+    FieldValue,
     Argument,
     GlossaryEntry,
 }
@@ -52,6 +53,7 @@ impl Structure {
             Self::Entry(BibtexEntryTypeCategory::Part) => CompletionItemKind::OPERATOR,
             Self::Entry(BibtexEntryTypeCategory::Thesis) => CompletionItemKind::UNIT,
             Self::Field => CompletionItemKind::FIELD,
+            Self::FieldValue => CompletionItemKind::VALUE,
             Self::Argument => CompletionItemKind::VALUE,
             Self::GlossaryEntry => CompletionItemKind::KEYWORD,
         }
@@ -4,6 +4,7 @@ use base_db::{
     Config, Document, DocumentLocation, HoverSymbolConfig, Workspace,
     data::BibtexEntryTypeCategory, util::RenderedObject,
 };
+use code_actions::{CodeAction, CodeActionKind, DocumentChange};
 use definition::DefinitionResult;
 use diagnostics::{BibError, ChktexSeverity, Diagnostic, TexError};
 use folding::{FoldingRange, FoldingRangeKind};
@@ -435,6 +436,97 @@ pub fn workspace_edit(result: RenameResult, new_name: &str) -> lsp_types::Worksp
     lsp_types::WorkspaceEdit::new(changes)
 }
 
+pub fn code_action(
+    workspace: &Workspace,
+    action: CodeAction,
+    client_flags: &ClientFlags,
+) -> Option<lsp_types::CodeAction> {
+    let kind = match action.kind {
+        CodeActionKind::RefactorExtract => lsp_types::CodeActionKind::REFACTOR_EXTRACT,
+        CodeActionKind::RefactorInline => lsp_types::CodeActionKind::REFACTOR_INLINE,
+        CodeActionKind::RefactorRewrite => lsp_types::CodeActionKind::REFACTOR_REWRITE,
+    };
+
+    let mut operations = Vec::new();
+    for change in action.changes {
+        let (uri, edit) = match change {
+            DocumentChange::CreateFile { uri, text } => {
+                let options = lsp_types::CreateFileOptions {
+                    overwrite: Some(false),
+                    ignore_if_exists: Some(false),
+                };
+
+                operations.push(lsp_types::DocumentChangeOperation::Op(
+                    lsp_types::ResourceOp::Create(lsp_types::CreateFile {
+                        uri: self::uri(&uri),
+                        options: Some(options),
+                        annotation_id: None,
+                    }),
+                ));
+
+                let range = lsp_types::Range::default();
+                (uri, lsp_types::TextEdit::new(range, text))
+            }
+            DocumentChange::Edit { uri, range, text } => {
+                let document = workspace.lookup(&uri)?;
+                let range = document.line_index.line_col_lsp_range(range)?;
+                (uri, lsp_types::TextEdit::new(range, text))
+            }
+            DocumentChange::Snippet {
+                uri,
+                range,
+                snippet,
+                text,
+            } => {
+                let document = workspace.lookup(&uri)?;
+                let range = document.line_index.line_col_lsp_range(range)?;
+                let text = if client_flags.snippet_text_edits {
+                    snippet
+                } else {
+                    text
+                };
+
+                (uri, lsp_types::TextEdit::new(range, text))
+            }
+        };
+
+        let uri = self::uri(&uri);
+
+        // The ranges of a `TextDocumentEdit` refer to the document before any of them is applied.
+        match operations.last_mut() {
+            Some(lsp_types::DocumentChangeOperation::Edit(last))
+                if last.text_document.uri == uri =>
+            {
+                last.edits.push(lsp_types::OneOf::Left(edit));
+                continue;
+            }
+            _ => {}
+        }
+
+        operations.push(lsp_types::DocumentChangeOperation::Edit(
+            lsp_types::TextDocumentEdit {
+                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                    uri,
+                    version: None,
+                },
+                edits: vec![lsp_types::OneOf::Left(edit)],
+            },
+        ));
+    }
+
+    let edit = lsp_types::WorkspaceEdit {
+        document_changes: Some(lsp_types::DocumentChanges::Operations(operations)),
+        ..lsp_types::WorkspaceEdit::default()
+    };
+
+    Some(lsp_types::CodeAction {
+        title: action.title,
+        kind: Some(kind),
+        edit: Some(edit),
+        ..lsp_types::CodeAction::default()
+    })
+}
+
 pub fn location(location: DocumentLocation) -> Option<lsp_types::Location> {
     let document = location.document;
     let range = document.line_index.line_col_lsp_range(location.range)?;